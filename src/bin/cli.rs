@@ -0,0 +1,280 @@
+use crate::{
+    database::{DataBase, Features, PkgKey, PkgWithFeatures},
+    err,
+    local_registry::local_pkgs,
+    Result,
+};
+use clap::{Args as ClapArgs, Parser, Subcommand, ValueEnum};
+use semver::Version;
+use std::{fmt, path::PathBuf};
+use term_rustdoc::{tree::ExportFormat, util::XString};
+
+/// A TUI for Rust docs.
+#[derive(Parser, Debug, Default)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+pub struct Args {
+    /// Local Cargo.toml or the dir containing it. Members of a workspace are all listed.
+    /// These pkgs are shown on top of Local Registry panel in DashBoard.
+    pub paths: Vec<PathBuf>,
+    /// Document private items for local pkgs. This can be toggled in DashBoard too.
+    #[arg(long)]
+    pub document_private_items: bool,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Open the doc of a pkg in TUI. The doc is compiled first if it's not cached,
+    /// in which case the pkg is searched in local registry and sysroot.
+    Open(PkgQuery),
+    /// Compile docs for a local Cargo.toml or the dir containing it, and cache them.
+    /// All members are compiled for a workspace.
+    Compile {
+        path: PathBuf,
+        #[command(flatten)]
+        features: FeatureArgs,
+        /// Build for the target triple. Defaults to the host.
+        #[arg(long)]
+        target: Option<String>,
+        /// Document private items.
+        #[arg(long)]
+        document_private_items: bool,
+    },
+    /// List all cached docs.
+    ListCache,
+    /// Remove cached docs. Without feature flags or `--target`, docs with any features
+    /// or targets are removed.
+    Rm(PkgQuery),
+    /// Export the outline, declarations and docs of a cached doc into a self-contained file.
+    Export {
+        #[command(flatten)]
+        query: PkgQuery,
+        #[arg(long, short, value_enum, default_value_t)]
+        format: Format,
+        /// Output file. Defaults to `name-version.ext` under current dir.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Remove the target dir shared by doc compilations to free disk space.
+    /// Cached docs are kept. Don't run it when docs are being compiled.
+    CleanTarget,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default)]
+pub enum Format {
+    #[default]
+    Markdown,
+    Html,
+    Text,
+}
+
+impl From<Format> for ExportFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Markdown => ExportFormat::Markdown,
+            Format::Html => ExportFormat::Html,
+            Format::Text => ExportFormat::Text,
+        }
+    }
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct PkgQuery {
+    /// `name` or `name@version`. All versions are matched if the version is not given.
+    pkg: String,
+    #[command(flatten)]
+    features: FeatureArgs,
+    /// The target triple. Docs for the host are preferred if not given.
+    #[arg(long)]
+    target: Option<String>,
+}
+
+impl fmt::Display for PkgQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pkg)?;
+        if let Some(features) = self.features() {
+            write!(f, " [{features:?}]")?;
+        }
+        if let Some(target) = &self.target {
+            write!(f, " --target {target}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PkgQuery {
+    pub fn name_ver(&self) -> (&str, Option<&str>) {
+        match self.pkg.split_once('@') {
+            Some((name, ver)) => (name, Some(ver)),
+            None => (&self.pkg, None),
+        }
+    }
+
+    /// None means no feature flag is given.
+    pub fn features(&self) -> Option<Features> {
+        self.features.features()
+    }
+
+    /// None means no target is given.
+    pub fn target(&self) -> Option<XString> {
+        self.target.as_deref().map(XString::from)
+    }
+
+    /// Check if the cached pkg matches the query. Features and the target are ignored
+    /// if not given.
+    pub fn matches(&self, key: &PkgKey) -> bool {
+        let (name, ver) = self.name_ver();
+        key.name() == name
+            && ver.is_none_or(|ver| key.ver_str() == ver)
+            && self.features().is_none_or(|f| *key.features() == f)
+            && self
+                .target
+                .as_deref()
+                .is_none_or(|t| key.target() == Some(t))
+    }
+
+    /// The latest version is preferred among matched docs, and the host and default
+    /// features are preferred if they're not given.
+    pub fn rank(key: &PkgKey) -> (bool, bool, Version) {
+        (
+            key.target().is_none(),
+            matches!(key.features(), Features::Default),
+            key.version(),
+        )
+    }
+}
+
+#[derive(ClapArgs, Debug, Default)]
+pub struct FeatureArgs {
+    /// Space or comma separated list of features to activate.
+    #[arg(long, short = 'F', value_delimiter = ',')]
+    features: Vec<String>,
+    /// Activate all available features.
+    #[arg(long)]
+    all_features: bool,
+    /// Do not activate the `default` feature.
+    #[arg(long)]
+    no_default_features: bool,
+}
+
+impl FeatureArgs {
+    fn features(&self) -> Option<Features> {
+        let mut selected: Vec<XString> = self
+            .features
+            .iter()
+            .flat_map(|f| f.split_whitespace())
+            .filter(|f| *f != "default")
+            .map(XString::from)
+            .collect();
+        selected.sort_unstable();
+        selected.dedup();
+        let selected = selected.into_boxed_slice();
+        Some(
+            match (
+                self.all_features,
+                self.no_default_features,
+                selected.is_empty(),
+            ) {
+                (true, _, _) => Features::All,
+                (false, true, true) => Features::NoDefault,
+                (false, true, false) => Features::NoDefaultPlus(selected),
+                (false, false, false) => Features::DefaultPlus(selected),
+                (false, false, true) => return None,
+            },
+        )
+    }
+}
+
+/// Run subcommands out of TUI. `open` is handled in DashBoard instead.
+pub fn run(cmd: Command) -> Result<()> {
+    let db = DataBase::init_without_sender()?;
+    match cmd {
+        Command::Open(query) => Err(err!("`open {query}` should run in TUI. This is a bug.")),
+        Command::Compile {
+            path,
+            features,
+            target,
+            document_private_items,
+        } => {
+            let pkgs = local_pkgs(&path)?;
+            if pkgs.is_empty() {
+                return Err(err!("No pkg is found in {}", path.display()));
+            }
+            let features = features.features().unwrap_or_default();
+            let mut failed = 0;
+            for info in pkgs {
+                let pkg = PkgWithFeatures {
+                    features: features.clone(),
+                    info,
+                    document_private_items,
+                    target: target.as_deref().map(XString::from),
+                };
+                match db.compile_doc_in_place(pkg) {
+                    Ok(cache) => println!("Cached {}", cache.pkg),
+                    Err(err) => {
+                        eprintln!("{err:?}");
+                        failed += 1;
+                    }
+                }
+            }
+            if failed == 0 {
+                Ok(())
+            } else {
+                Err(err!("Failed to compile {failed} pkg(s)"))
+            }
+        }
+        Command::ListCache => {
+            let mut caches = db.all_caches()?;
+            caches.sort_unstable_by(|a, b| {
+                (a.pkg.name(), a.pkg.version()).cmp(&(b.pkg.name(), b.pkg.version()))
+            });
+            for cache in &caches {
+                println!("{}", cache.pkg);
+            }
+            Ok(())
+        }
+        Command::Rm(query) => {
+            let caches = db.all_caches()?;
+            let mut removed = 0;
+            for cache in caches.iter().filter(|cache| query.matches(&cache.pkg)) {
+                cache.remove()?;
+                println!("Removed {}", cache.pkg);
+                removed += 1;
+            }
+            if removed == 0 {
+                Err(err!("No cached doc matches `{query}`"))
+            } else {
+                Ok(())
+            }
+        }
+        Command::Export {
+            query,
+            format,
+            output,
+        } => {
+            let caches = db.all_caches()?;
+            let cache = caches
+                .iter()
+                .filter(|cache| query.matches(&cache.pkg))
+                .max_by_key(|cache| PkgQuery::rank(&cache.pkg))
+                .ok_or_else(|| err!("No cached doc matches `{query}`"))?;
+            let format = ExportFormat::from(format);
+            let output = output.unwrap_or_else(|| {
+                let (name, ver) = (cache.pkg.name(), cache.pkg.ver_str());
+                PathBuf::from(format!("{name}-{ver}.{}", format.extension()))
+            });
+            let doc = db.inline_external_reexports(cache.load_doc()?);
+            std::fs::write(&output, doc.export(format))?;
+            println!("Exported {} to {}", cache.pkg, output.display());
+            Ok(())
+        }
+        Command::CleanTarget => {
+            match db.clean_target_dir()? {
+                Some(dir) => println!("Removed {}", dir.display()),
+                None => println!("The target dir doesn't exist"),
+            }
+            Ok(())
+        }
+    }
+}
@@ -1,58 +1,302 @@
 use ratatui::prelude::{Color, Modifier, Style};
+use term_rustdoc::tree::{Tag, TagGroup};
 
-pub const BG_CURSOR: Color = Color::Green;
-pub const BG_CURSOR_LINE: Color = Color::from_u32(0x0029335b); // #29335b
-pub const FG_CURSOR_LINE: Color = Color::from_u32(0x00FFD48E); // #FFD48E
+/// The theme in use, i.e. a built-in theme with overrides from the config.
+pub fn theme() -> &'static Theme {
+    &crate::config::config().theme
+}
 
-pub const FG_FEATURES: Color = Color::Cyan;
-pub const FG_VERSION: Color = Color::from_u32(0x00686363); // #686363
+/// No style on purpose, i.e. the default fg and bg of the terminal.
+pub const NEW: Style = Style::new();
 
-pub const PKG_NAME: Style = Style {
-    fg: Some(Color::White),
-    add_modifier: Modifier::BOLD,
-    ..Style::new()
-};
+const fn fg(color: Color) -> Style {
+    Style::new().fg(color)
+}
 
-pub const PKG_VERSION: Style = Style {
-    fg: Some(FG_VERSION),
-    ..Style::new()
-};
+const fn bfg(color: Color) -> Style {
+    Style::new().fg(color).add_modifier(Modifier::BOLD)
+}
 
-pub const PKG_FEATURES: Style = Style {
-    fg: Some(FG_FEATURES),
-    add_modifier: Modifier::ITALIC,
-    ..Style::new()
-};
+const fn rgb(rgb: u32) -> Color {
+    Color::from_u32(rgb)
+}
 
-// Database Panel
-pub const LOADED: Style = Style {
-    fg: Some(Color::from_u32(0x00FFD48E)), // #FFD48E
-    ..Style::new()
-};
-pub const CACHED: Style = Style {
-    fg: Some(Color::from_u32(0x006FA2FF)), // #6FA2FF
-    ..Style::new()
+macro_rules! styles {
+    ($( $(#[$doc:meta])* $field:ident, )+) => {
+        /// Styles of UI, markdown elements and tree tags, plus the syntect theme
+        /// to highlight code blocks.
+        #[derive(Clone, Debug)]
+        pub struct Theme {
+            $( $(#[$doc])* pub $field: Style, )+
+            /// Colors of tags indexed by [`TagGroup`].
+            tags: [Color; TagGroup::ALL.len()],
+            /// A theme name in syntect's default themes, or a path to a `.tmTheme` file.
+            pub syntax: String,
+        }
+
+        impl Theme {
+            /// Field names to be overridden in `[theme.styles]`.
+            pub const STYLES: &[&str] = &[$(stringify!($field),)+];
+
+            /// Returns false if the name is not a style.
+            pub fn set_style(&mut self, name: &str, style: Style) -> bool {
+                match name {
+                    $(stringify!($field) => self.$field = style,)+
+                    _ => return false,
+                }
+                true
+            }
+        }
+    };
+}
+
+styles! {
+    /// The cursor in inputs.
+    cursor,
+    /// The selected line in lists.
+    cursor_line,
+    pkg_name,
+    pkg_version,
+    pkg_features,
+    pkg_toml,
+    /// Docs in Database panel.
+    loaded,
+    cached,
+    holdon,
+    /// Docs failed to compile, and the error text in Database panel.
+    failed,
+    /// Features enabled by other features in Features Selection.
+    feature_locked,
+    /// Features enabled by users, but already enabled by other features.
+    feature_needless,
+    /// The focused panel on Page.
+    focused_panel,
+    /// The heading of the visible contents in Navigation panel.
+    navi_heading,
+    declaration_border,
+    /// Jumpable items in declarations and intra-doc links.
+    jump,
+    tree_glyph,
+    /// The glyph bg and the name fg of the current line in item trees.
+    tree_cursor,
+    md_heading,
+    quote_block,
+    footnote,
+    link,
+    intra_code,
+    image,
+    list_marker,
+    task_done,
+    /// The fences of code blocks.
+    code_fence,
+    /// Code blocks in unknown languages.
+    code_fallback,
+}
+
+impl Theme {
+    /// Built-in theme names.
+    pub const BUILTIN: [&str; 3] = ["dark", "light", "high-contrast"];
+
+    pub fn builtin(name: &str) -> Option<Theme> {
+        let (mut theme, syntax) = match name {
+            "dark" => (DARK, "base16-ocean.dark"),
+            "light" => (LIGHT, "InspiredGitHub"),
+            "high-contrast" => (HIGH_CONTRAST, "base16-eighties.dark"),
+            _ => return None,
+        };
+        theme.syntax = syntax.to_owned();
+        Some(theme)
+    }
+
+    pub fn tag(&self, tag: Tag) -> Style {
+        tag.style_with(self.tag_color(tag.group()))
+    }
+
+    pub fn tag_color(&self, group: TagGroup) -> Color {
+        self.tags[group as usize]
+    }
+
+    pub fn set_tag_color(&mut self, group: TagGroup, color: Color) {
+        self.tags[group as usize] = color;
+    }
+
+    /// The bg of the selected line. Reset the bg if the theme doesn't set one.
+    pub fn cursor_line_bg(&self) -> Color {
+        self.cursor_line.bg.unwrap_or(Color::Reset)
+    }
+
+    pub fn cursor_line_fg(&self) -> Color {
+        self.cursor_line.fg.unwrap_or(Color::Reset)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::builtin("dark").unwrap()
+    }
+}
+
+const DARK: Theme = Theme {
+    cursor: Style::new().fg(Color::White).bg(Color::Green),
+    cursor_line: fg(rgb(0xFFD48E)).bg(rgb(0x29335b)), // #FFD48E on #29335b
+    pkg_name: bfg(Color::White),
+    pkg_version: fg(rgb(0x686363)), // #686363
+    pkg_features: fg(Color::Cyan).add_modifier(Modifier::ITALIC),
+    pkg_toml: bfg(Color::Green),
+    loaded: fg(rgb(0xFFD48E)),  // #FFD48E
+    cached: fg(rgb(0x6FA2FF)),  // #6FA2FF
+    holdon: fg(rgb(0xFF768C)),  // #FF768C
+    failed: bfg(rgb(0xFF5F5F)), // #FF5F5F
+    feature_locked: bfg(Color::LightGreen),
+    feature_needless: bfg(Color::Red),
+    focused_panel: Style::new().bg(Color::Rgb(20, 19, 18)), // #141312
+    navi_heading: fg(Color::DarkGray).bg(Color::LightCyan),
+    declaration_border: fg(Color::Gray),
+    jump: bfg(rgb(0x4083d6)), // #4083d6
+    tree_glyph: fg(Color::Gray),
+    tree_cursor: fg(Color::Black).bg(Color::DarkGray),
+    md_heading: bfg(Color::LightCyan),
+    quote_block: fg(Color::Rgb(186, 85, 211)), // #BA55D3
+    footnote: fg(Color::LightMagenta),
+    link: fg(Color::Rgb(30, 144, 255)),        // #1E90FF
+    intra_code: fg(Color::Rgb(255, 184, 162)), // #FFB8A2
+    image: fg(Color::Rgb(192, 192, 192)),      // #C0C0C0
+    list_marker: fg(Color::Green),
+    task_done: fg(Color::LightYellow),
+    code_fence: bfg(Color::Red),
+    code_fallback: fg(Color::LightRed),
+    tags: DARK_TAGS,
+    syntax: String::new(),
 };
-pub const HOLDON: Style = Style {
-    fg: Some(Color::from_u32(0x00FF768C)), // #FF768C
-    ..Style::new()
+
+const DARK_TAGS: [Color; TagGroup::ALL.len()] = {
+    let mut tags = [Color::Reset; TagGroup::ALL.len()];
+    let mut i = 0;
+    while i < tags.len() {
+        tags[i] = TagGroup::ALL[i].color();
+        i += 1;
+    }
+    tags
 };
 
-pub const PKG_TOML: Style = Style {
-    fg: Some(Color::Green),
-    add_modifier: Modifier::BOLD,
-    ..Style::new()
+/// For light backgrounds: dark and saturated colors.
+const LIGHT: Theme = Theme {
+    cursor: Style::new().fg(Color::White).bg(Color::Green),
+    cursor_line: fg(rgb(0x7A4B00)).bg(rgb(0xD6E4FF)), // #7A4B00 on #D6E4FF
+    pkg_name: bfg(Color::Black),
+    pkg_version: fg(rgb(0x6E7781)), // #6E7781
+    pkg_features: fg(rgb(0x00788C)).add_modifier(Modifier::ITALIC), // #00788C
+    pkg_toml: bfg(rgb(0x1A7F37)),   // #1A7F37
+    loaded: fg(rgb(0xB35900)),      // #B35900
+    cached: fg(rgb(0x0550AE)),      // #0550AE
+    holdon: fg(rgb(0xCF222E)),      // #CF222E
+    failed: bfg(rgb(0xA40E26)),     // #A40E26
+    feature_locked: bfg(rgb(0x1A7F37)),
+    feature_needless: bfg(rgb(0xCF222E)),
+    focused_panel: Style::new().bg(rgb(0xF0F0F0)), // #F0F0F0
+    navi_heading: fg(Color::Black).bg(rgb(0xB6E3FF)), // #B6E3FF
+    declaration_border: fg(rgb(0x8C959F)),         // #8C959F
+    jump: bfg(rgb(0x0550AE)),
+    tree_glyph: fg(rgb(0x8C959F)),
+    tree_cursor: fg(Color::White).bg(rgb(0x8C959F)),
+    md_heading: bfg(rgb(0x0550AE)),
+    quote_block: fg(rgb(0x8250DF)), // #8250DF
+    footnote: fg(rgb(0xBF3989)),    // #BF3989
+    link: fg(rgb(0x0969DA)),        // #0969DA
+    intra_code: fg(rgb(0x953800)),  // #953800
+    image: fg(rgb(0x6E7781)),
+    list_marker: fg(rgb(0x1A7F37)),
+    task_done: fg(rgb(0x9A6700)), // #9A6700
+    code_fence: bfg(rgb(0xCF222E)),
+    code_fallback: fg(rgb(0xCF222E)),
+    tags: LIGHT_TAGS,
+    syntax: String::new(),
 };
 
-// Page
-pub const HEAD: Style = Style {
-    fg: Some(Color::DarkGray),
-    bg: Some(Color::LightCyan),
-    ..Style::new()
+/// Ordered as [`TagGroup::ALL`].
+const LIGHT_TAGS: [Color; TagGroup::ALL.len()] = [
+    rgb(0x5C6B00), // module
+    rgb(0x0E6A7E), // struct
+    rgb(0x1F7A8C), // field
+    rgb(0x1B7F5E), // union
+    rgb(0xB0206E), // enum
+    rgb(0x8E2B85), // variant
+    rgb(0xC25E00), // trait
+    rgb(0xB5332C), // function
+    rgb(0x8A7A00), // constant
+    rgb(0x2B2BAF), // static
+    rgb(0x6A3FB0), // type_alias
+    rgb(0x0F811D), // macro_decl
+    rgb(0x2E8540), // macro_func
+    rgb(0x5A7D00), // macro_attr
+    rgb(0x4D7600), // macro_derive
+    rgb(0xC2185B), // inherent_impl
+    rgb(0xA65E2E), // trait_impl
+    rgb(0xC25E00), // auto_impl
+    rgb(0x8F7300), // blanket_impl
+    rgb(0x0550AE), // implementor
+    Color::Black,  // implementations
+    rgb(0x8C959F), // private
+    rgb(0x57606A), // other
+];
+
+/// For dark backgrounds: only the basic colors with bold or underlined texts.
+const HIGH_CONTRAST: Theme = Theme {
+    cursor: Style::new().fg(Color::Black).bg(Color::LightGreen),
+    cursor_line: fg(Color::Black).bg(Color::Yellow),
+    pkg_name: bfg(Color::White),
+    pkg_version: fg(Color::Gray),
+    pkg_features: fg(Color::LightCyan).add_modifier(Modifier::ITALIC),
+    pkg_toml: bfg(Color::LightGreen),
+    loaded: bfg(Color::Yellow),
+    cached: bfg(Color::LightBlue),
+    holdon: bfg(Color::LightRed),
+    failed: bfg(Color::Red).add_modifier(Modifier::UNDERLINED),
+    feature_locked: bfg(Color::LightGreen),
+    feature_needless: bfg(Color::LightRed),
+    focused_panel: Style::new().bg(Color::Black),
+    navi_heading: fg(Color::Black).bg(Color::White),
+    declaration_border: fg(Color::White),
+    jump: bfg(Color::LightBlue).add_modifier(Modifier::UNDERLINED),
+    tree_glyph: fg(Color::White),
+    tree_cursor: fg(Color::Black).bg(Color::White),
+    md_heading: bfg(Color::White).add_modifier(Modifier::UNDERLINED),
+    quote_block: fg(Color::LightMagenta),
+    footnote: fg(Color::LightMagenta),
+    link: fg(Color::LightBlue).add_modifier(Modifier::UNDERLINED),
+    intra_code: fg(Color::LightYellow),
+    image: fg(Color::Gray),
+    list_marker: bfg(Color::LightGreen),
+    task_done: bfg(Color::Yellow),
+    code_fence: bfg(Color::LightRed),
+    code_fallback: fg(Color::LightRed),
+    tags: HIGH_CONTRAST_TAGS,
+    syntax: String::new(),
 };
-pub const SET: Style = Style::new().bg(Color::Rgb(20, 19, 18)); // #141312
-pub const NEW: Style = Style::new();
-pub const DECLARATION_BORDER: Style = Style::new().fg(Color::Gray);
-pub const JUMP: Style = Style::new()
-    .fg(Color::from_u32(0x004083d6))
-    .add_modifier(Modifier::BOLD); // #4083d6
+
+/// Ordered as [`TagGroup::ALL`].
+const HIGH_CONTRAST_TAGS: [Color; TagGroup::ALL.len()] = [
+    Color::LightYellow,  // module
+    Color::LightCyan,    // struct
+    Color::Cyan,         // field
+    Color::LightGreen,   // union
+    Color::LightMagenta, // enum
+    Color::Magenta,      // variant
+    Color::Yellow,       // trait
+    Color::LightRed,     // function
+    Color::Yellow,       // constant
+    Color::LightBlue,    // static
+    Color::Magenta,      // type_alias
+    Color::Green,        // macro_decl
+    Color::LightGreen,   // macro_func
+    Color::LightGreen,   // macro_attr
+    Color::Green,        // macro_derive
+    Color::LightRed,     // inherent_impl
+    Color::Yellow,       // trait_impl
+    Color::Yellow,       // auto_impl
+    Color::Yellow,       // blanket_impl
+    Color::LightBlue,    // implementor
+    Color::White,        // implementations
+    Color::Gray,         // private
+    Color::White,        // other
+];
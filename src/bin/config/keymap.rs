@@ -0,0 +1,489 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use rustc_hash::FxHashMap as HashMap;
+use std::fmt::{self, Write};
+
+/// Where a key press is dispatched to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// Checked before other scopes.
+    Global,
+    DashBoard,
+    Page,
+    /// The popup to search items on Page.
+    PageSearch,
+    /// The popup to list candidate types for a generic parameter on Page.
+    PageCandidates,
+    Help,
+}
+
+impl Scope {
+    const ALL: [Scope; 6] = [
+        Scope::Global,
+        Scope::DashBoard,
+        Scope::Page,
+        Scope::PageSearch,
+        Scope::PageCandidates,
+        Scope::Help,
+    ];
+
+    /// The table name under `[keymap]` in config file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Scope::Global => "global",
+            Scope::DashBoard => "dashboard",
+            Scope::Page => "page",
+            Scope::PageSearch => "page_search",
+            Scope::PageCandidates => "page_candidates",
+            Scope::Help => "help",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Scope::Global => "Global",
+            Scope::DashBoard => "DashBoard",
+            Scope::Page => "Page",
+            Scope::PageSearch => "Page Search",
+            Scope::PageCandidates => "Page Candidates",
+            Scope::Help => "Help",
+        }
+    }
+
+    /// Printable chars and Backspace are used to edit the input in these scopes.
+    /// Global keys are checked in all scopes, thus they can't be used either.
+    fn has_text_input(self) -> bool {
+        matches!(self, Scope::Global | Scope::DashBoard | Scope::PageSearch)
+    }
+}
+
+macro_rules! actions {
+    ($($variant:ident => $name:literal,)+) => {
+        /// What a key press does. An action is available in a scope only if
+        /// it's listed in [`DEFAULT`] for that scope.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum Action {
+            $($variant,)+
+        }
+
+        impl Action {
+            /// The key in a scope table in config file.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Action::$variant => $name,)+
+                }
+            }
+
+            fn from_name(name: &str) -> Option<Action> {
+                match name {
+                    $($name => Some(Action::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+actions! {
+    Quit => "quit",
+    SwitchFocus => "switch_focus",
+    ToggleHelp => "toggle_help",
+    CursorUp => "cursor_up",
+    CursorDown => "cursor_down",
+    CursorTop => "cursor_top",
+    CursorMiddle => "cursor_middle",
+    CursorBottom => "cursor_bottom",
+    ScrollHome => "scroll_home",
+    ScrollEnd => "scroll_end",
+    PageUp => "page_up",
+    PageDown => "page_down",
+    Confirm => "confirm",
+    Close => "close",
+    ClearInput => "clear_input",
+    SwitchSearchSource => "switch_search_source",
+    SwitchSort => "switch_sort",
+    SwitchList => "switch_list",
+    SwitchPanel => "switch_panel",
+    Remove => "remove",
+    TogglePathInput => "toggle_path_input",
+    TogglePrivateItems => "toggle_private_items",
+    ToggleNoteInput => "toggle_note_input",
+    RestoreSession => "restore_session",
+    ToggleSelect => "toggle_select",
+    CompileSelected => "compile_selected",
+    CompileDependencies => "compile_dependencies",
+    NextNavi => "next_action",
+    PreviousNavi => "previous_action",
+    ExpandCurrentModule => "expand_current_module",
+    ExpandAll => "expand_all",
+    ExpandZeroLevel => "expand_zero_level",
+    ExpandToFirstLevel => "expand_to_first_level",
+    ToggleMarkdown => "toggle_markdown",
+    OpenSearch => "open_search",
+    HistoryBack => "history_back",
+    HistoryForward => "history_forward",
+    ToggleBookmark => "toggle_bookmark",
+    ShowCandidates => "show_candidates",
+    NextLink => "next_link",
+    PreviousLink => "previous_link",
+}
+
+/// Default keys and descriptions for actions in each scope.
+#[rustfmt::skip]
+const DEFAULT: &[(Scope, Action, &[&str], &str)] = &[
+    (Scope::Global, Action::Quit, &["ctrl-q"], "Quit."),
+    (Scope::Global, Action::SwitchFocus, &["ctrl-w"], "Switch between DashBoard and Page."),
+    (Scope::Global, Action::ToggleHelp, &["f1"], "Toggle Help."),
+
+    (Scope::DashBoard, Action::CursorUp, &["up"], "Move the cursor up a line."),
+    (Scope::DashBoard, Action::CursorDown, &["down"], "Move the cursor down a line."),
+    (Scope::DashBoard, Action::ScrollHome, &["home"], "Scroll to the top."),
+    (Scope::DashBoard, Action::ScrollEnd, &["end"], "Scroll to the bottom."),
+    (Scope::DashBoard, Action::PageUp, &["pageup"], "Scroll up half a screen."),
+    (Scope::DashBoard, Action::PageDown, &["pagedown"], "Scroll down half a screen."),
+    (Scope::DashBoard, Action::Confirm, &["enter"], "Load a doc, open a bookmark, select a pkg to compile, show or retry a failed compilation, or confirm the input."),
+    (Scope::DashBoard, Action::Close, &["esc"], "Close Selection or Error popup, or the input of a local path or a note."),
+    (Scope::DashBoard, Action::SwitchPanel, &["tab"], "Switch among Database, Registry and Bookmarks panels."),
+    (Scope::DashBoard, Action::Remove, &["delete"], "Unload a doc, cancel a compilation, or remove a bookmark."),
+    (Scope::DashBoard, Action::ClearInput, &["ctrl-c"], "Clear out the input."),
+    (Scope::DashBoard, Action::SwitchSearchSource, &["ctrl-f"], "Switch the panels to search pkgs in."),
+    (Scope::DashBoard, Action::SwitchSort, &["ctrl-s"], "Switch the sorting of docs in Database panel."),
+    (Scope::DashBoard, Action::TogglePathInput, &["ctrl-o"], "Toggle the input of a local path."),
+    (Scope::DashBoard, Action::TogglePrivateItems, &["ctrl-p"], "Toggle documenting private items for local pkgs."),
+    (Scope::DashBoard, Action::ToggleNoteInput, &["ctrl-n"], "Toggle the input of a note on the bookmark."),
+    (Scope::DashBoard, Action::RestoreSession, &["ctrl-r"], "Restore the last session."),
+    (Scope::DashBoard, Action::ToggleSelect, &["ctrl-t"], "Select a pkg in Registry panel to compile in a batch, or unselect it."),
+    (Scope::DashBoard, Action::CompileSelected, &["ctrl-e"], "Compile docs of selected pkgs in a batch."),
+    (Scope::DashBoard, Action::CompileDependencies, &["ctrl-d"], "Compile docs of all dependencies in Cargo.lock of the local pkg."),
+
+    (Scope::Page, Action::CursorUp, &["up", "k"], "Move the cursor up a line."),
+    (Scope::Page, Action::CursorDown, &["down", "j"], "Move the cursor down a line."),
+    (Scope::Page, Action::CursorTop, &["H"], "Move the cursor to the top line in view."),
+    (Scope::Page, Action::CursorMiddle, &["M"], "Move the cursor to the middle line in view."),
+    (Scope::Page, Action::CursorBottom, &["L"], "Move the cursor to the bottom line in view."),
+    (Scope::Page, Action::ScrollHome, &["home"], "Scroll to the top."),
+    (Scope::Page, Action::ScrollEnd, &["end"], "Scroll to the bottom."),
+    (Scope::Page, Action::PageUp, &["pageup"], "Scroll up half a screen."),
+    (Scope::Page, Action::PageDown, &["pagedown"], "Scroll down half a screen."),
+    (Scope::Page, Action::NextNavi, &["right", "tab", "l"], "Next navigation action."),
+    (Scope::Page, Action::PreviousNavi, &["left", "h"], "Previous navigation action."),
+    (Scope::Page, Action::Confirm, &["enter"], "Expand or fold a module in outline, or follow the selected or first intra-doc link in view."),
+    (Scope::Page, Action::ExpandCurrentModule, &["m"], "Only expand the current module."),
+    (Scope::Page, Action::ExpandAll, &["/"], "Expand all modules."),
+    (Scope::Page, Action::ExpandZeroLevel, &["0"], "Only expand items directly under the root module."),
+    (Scope::Page, Action::ExpandToFirstLevel, &["1"], "Expand items till the first level modules."),
+    (Scope::Page, Action::ToggleMarkdown, &["d"], "Toggle the raw markdown highlighted by syntect."),
+    (Scope::Page, Action::OpenSearch, &["f"], "Search items in the crate."),
+    (Scope::Page, Action::HistoryBack, &["backspace", "ctrl-o"], "Go back to the position before last jump."),
+    (Scope::Page, Action::HistoryForward, &["ctrl-n"], "Go forward to the position left by going back."),
+    (Scope::Page, Action::ToggleBookmark, &["b"], "Bookmark the item under the cursor, or remove the bookmark."),
+    (Scope::Page, Action::ShowCandidates, &["g"], "List concrete types that meet the trait bounds on a generic parameter of the item, or functions returning or accepting them."),
+    (Scope::Page, Action::NextLink, &["n"], "Select the next intra-doc link in view."),
+    (Scope::Page, Action::PreviousLink, &["N"], "Select the previous intra-doc link in view."),
+
+    (Scope::PageSearch, Action::CursorUp, &["up"], "Move the cursor up a line."),
+    (Scope::PageSearch, Action::CursorDown, &["down"], "Move the cursor down a line."),
+    (Scope::PageSearch, Action::ScrollHome, &["home"], "Scroll to the top."),
+    (Scope::PageSearch, Action::ScrollEnd, &["end"], "Scroll to the bottom."),
+    (Scope::PageSearch, Action::PageUp, &["pageup"], "Scroll up half a screen."),
+    (Scope::PageSearch, Action::PageDown, &["pagedown"], "Scroll down half a screen."),
+    (Scope::PageSearch, Action::Confirm, &["enter"], "Jump to the item under the cursor."),
+    (Scope::PageSearch, Action::Close, &["esc"], "Close the search popup."),
+    (Scope::PageSearch, Action::ClearInput, &["ctrl-c"], "Clear out the input."),
+    (Scope::PageSearch, Action::SwitchSearchSource, &["ctrl-f"], "Switch among searching paths, docs and signatures."),
+
+    (Scope::PageCandidates, Action::CursorUp, &["up"], "Move the cursor up a line."),
+    (Scope::PageCandidates, Action::CursorDown, &["down"], "Move the cursor down a line."),
+    (Scope::PageCandidates, Action::ScrollHome, &["home"], "Scroll to the top."),
+    (Scope::PageCandidates, Action::ScrollEnd, &["end"], "Scroll to the bottom."),
+    (Scope::PageCandidates, Action::PageUp, &["pageup"], "Scroll up half a screen."),
+    (Scope::PageCandidates, Action::PageDown, &["pagedown"], "Scroll down half a screen."),
+    (Scope::PageCandidates, Action::Confirm, &["enter"], "Jump to the type or function under the cursor."),
+    (Scope::PageCandidates, Action::Close, &["esc"], "Close the popup."),
+    (Scope::PageCandidates, Action::SwitchPanel, &["tab"], "List candidates for the next generic parameter."),
+    (Scope::PageCandidates, Action::SwitchSearchSource, &["ctrl-f"], "Switch between types in current crate and in all loaded docs."),
+    (Scope::PageCandidates, Action::SwitchList, &["ctrl-l"], "Switch among listing types, functions returning them and functions accepting them."),
+
+    (Scope::Help, Action::CursorUp, &["up"], "Scroll up a line."),
+    (Scope::Help, Action::CursorDown, &["down"], "Scroll down a line."),
+    (Scope::Help, Action::ScrollHome, &["home"], "Scroll to the top."),
+    (Scope::Help, Action::ScrollEnd, &["end"], "Scroll to the bottom."),
+    (Scope::Help, Action::PageUp, &["pageup"], "Scroll up 5 lines."),
+    (Scope::Help, Action::PageDown, &["pagedown"], "Scroll down 5 lines."),
+];
+
+/// A key press with modifiers.
+///
+/// Shift is not kept for chars, because the case of the char already tells it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    fn new(code: KeyCode, mut modifiers: KeyModifiers) -> Key {
+        if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Key { code, modifiers }
+    }
+
+    pub fn from_event(event: &KeyEvent) -> Key {
+        Key::new(event.code, event.modifiers)
+    }
+
+    /// Parse keys like `ctrl-q`, `f1`, `pageup`, `shift-tab`, `j` or `L`.
+    /// Modifiers and names are case insensitive, but single chars are not.
+    pub fn parse(s: &str) -> Result<Key, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+        while let Some((prefix, remain)) = rest.split_once('-').filter(|(_, r)| !r.is_empty()) {
+            modifiers |= match &*prefix.to_ascii_lowercase() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => break,
+            };
+            rest = remain;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match &*rest.to_ascii_lowercase() {
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "space" => KeyCode::Char(' '),
+                f => match f.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("`{s}` is not a valid key")),
+                },
+            },
+        };
+        Ok(Key::new(code, modifiers))
+    }
+
+    /// Typing a printable char or Backspace when an input is shown.
+    fn edits_text(self) -> bool {
+        let no_ctrl_alt = !self
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        no_ctrl_alt && matches!(self.code, KeyCode::Char(_) | KeyCode::Backspace)
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, text) in [
+            (KeyModifiers::CONTROL, "Ctrl-"),
+            (KeyModifiers::ALT, "Alt-"),
+            (KeyModifiers::SHIFT, "Shift-"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(text)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => f.write_char(c),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::Esc => f.write_str("Esc"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+/// Effective keys of an action in a scope.
+#[derive(Debug)]
+struct Binding {
+    scope: Scope,
+    action: Action,
+    keys: Vec<Key>,
+    desc: &'static str,
+}
+
+/// Keys to actions in each scope, with defaults overridden by the config file.
+#[derive(Debug)]
+pub struct KeyMap {
+    map: HashMap<(Scope, Key), Action>,
+    /// In the order of [`DEFAULT`].
+    bindings: Vec<Binding>,
+}
+
+/// The `[keymap]` table in config file: scope name to action name to keys.
+pub type KeyMapConfig = HashMap<String, HashMap<String, Vec<String>>>;
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        KeyMap::new(KeyMapConfig::default(), &mut Vec::new())
+    }
+}
+
+impl KeyMap {
+    /// Build the keymap from the user config. Invalid entries and conflicts are pushed
+    /// into problems, in which case the conflicting key is kept for the first action.
+    pub fn new(config: KeyMapConfig, problems: &mut Vec<String>) -> KeyMap {
+        let mut bindings: Vec<Binding> = DEFAULT
+            .iter()
+            .map(|&(scope, action, keys, desc)| Binding {
+                scope,
+                action,
+                keys: keys.iter().map(|k| Key::parse(k).unwrap()).collect(),
+                desc,
+            })
+            .collect();
+
+        for (scope_name, actions) in config {
+            let Some(scope) = Scope::ALL.into_iter().find(|s| s.name() == scope_name) else {
+                problems.push(format!("`[keymap.{scope_name}]` is not a known scope"));
+                continue;
+            };
+            for (action_name, keys) in actions {
+                let binding = Action::from_name(&action_name).and_then(|action| {
+                    bindings
+                        .iter_mut()
+                        .find(|b| b.scope == scope && b.action == action)
+                });
+                let Some(binding) = binding else {
+                    problems.push(format!(
+                        "`{action_name}` is not an action in `[keymap.{scope_name}]`"
+                    ));
+                    continue;
+                };
+                binding.keys = keys
+                    .iter()
+                    .filter_map(|key| {
+                        Key::parse(key)
+                            .map_err(|err| {
+                                problems.push(format!("{err} for `{scope_name}.{action_name}`"))
+                            })
+                            .ok()
+                    })
+                    .collect();
+            }
+        }
+
+        let mut map = HashMap::default();
+        for binding in &mut bindings {
+            let (scope, action) = (binding.scope, binding.action);
+            // only keep effective keys
+            binding.keys.retain(|&key| {
+                let other = if let Some(global) = map
+                    .get(&(Scope::Global, key))
+                    .filter(|_| scope != Scope::Global)
+                {
+                    format!("`global.{}`", Action::name(*global))
+                } else if scope.has_text_input() && key.edits_text() {
+                    String::from("typing in the input")
+                } else if let Some(other) = map.get(&(scope, key)) {
+                    format!("`{}.{}`", scope.name(), Action::name(*other))
+                } else {
+                    map.insert((scope, key), action);
+                    return true;
+                };
+                problems.push(format!(
+                    "`{key}` for `{}.{}` conflicts with {other}",
+                    scope.name(),
+                    action.name()
+                ));
+                false
+            });
+        }
+
+        KeyMap { map, bindings }
+    }
+
+    /// The action bound to the key press in the scope. Global actions are not included.
+    pub fn action(&self, scope: Scope, event: &KeyEvent) -> Option<Action> {
+        self.map.get(&(scope, Key::from_event(event))).copied()
+    }
+
+    pub fn keys(&self, scope: Scope, action: Action) -> &[Key] {
+        self.bindings
+            .iter()
+            .find(|b| b.scope == scope && b.action == action)
+            .map_or(&[], |b| &b.keys)
+    }
+
+    /// The first key of the action, used in hints like ` Ctrl-f to switch `.
+    pub fn hint(&self, scope: Scope, action: Action) -> String {
+        match self.keys(scope, action).first() {
+            Some(key) => key.to_string(),
+            None => format!("<{}>", action.name()),
+        }
+    }
+
+    /// Keys of the action in markdown, like `` `j` / `Down` ``.
+    fn keys_markdown(&self, scope: Scope, action: Action) -> String {
+        let keys = self.keys(scope, action);
+        if keys.is_empty() {
+            return String::from("(unbound)");
+        }
+        let keys: Vec<_> = keys.iter().map(|key| format!("`{key}`")).collect();
+        keys.join(" / ")
+    }
+
+    /// Fill the placeholders like `{page.cursor_down}` in the help template with
+    /// effective keys, and append the full keymap.
+    pub fn help_markdown(&self, template: &str) -> String {
+        let mut help = String::with_capacity(template.len() * 2);
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            help.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let placeholder = rest[1..].find('}').and_then(|end| {
+                let (scope, action) = rest[1..end + 1].split_once('.')?;
+                let scope = Scope::ALL.into_iter().find(|s| s.name() == scope)?;
+                Some((scope, Action::from_name(action)?, end + 2))
+            });
+            match placeholder {
+                Some((scope, action, len)) => {
+                    help.push_str(&self.keys_markdown(scope, action));
+                    rest = &rest[len..];
+                }
+                None => {
+                    help.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        help.push_str(rest);
+
+        help.push_str(
+            "\n# KeyMap\n\nAll key bindings in effect. See Config section for customization.\n",
+        );
+        for scope in Scope::ALL {
+            _ = write!(help, "\n## {}\n\n", scope.title());
+            for binding in self.bindings.iter().filter(|b| b.scope == scope) {
+                let keys = self.keys_markdown(scope, binding.action);
+                _ = writeln!(
+                    help,
+                    "* `{}` ({keys}): {}",
+                    binding.action.name(),
+                    binding.desc
+                );
+            }
+        }
+        help
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,98 @@
+use super::*;
+use crossterm::event::KeyEventKind;
+
+fn event(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent {
+        code,
+        modifiers,
+        kind: KeyEventKind::Press,
+        state: crossterm::event::KeyEventState::NONE,
+    }
+}
+
+#[test]
+fn parse_and_display_keys() {
+    let keys: Vec<_> = [
+        "ctrl-q",
+        "f1",
+        "pageup",
+        "shift-tab",
+        "j",
+        "L",
+        "shift-l",
+        "-",
+        "ctrl--",
+        "space",
+    ]
+    .iter()
+    .map(|k| Key::parse(k).unwrap().to_string())
+    .collect();
+    assert_eq!(
+        keys,
+        ["Ctrl-q", "F1", "PageUp", "BackTab", "j", "L", "L", "-", "Ctrl--", "Space"]
+    );
+    assert!(Key::parse("ctrl-").is_err());
+    assert!(Key::parse("f13").is_err());
+    assert!(Key::parse("hyper-x").is_err());
+}
+
+#[test]
+fn shift_is_ignored_for_chars() {
+    let keymap = KeyMap::default();
+    let uppercase = event(KeyCode::Char('L'), KeyModifiers::SHIFT);
+    assert_eq!(
+        keymap.action(Scope::Page, &uppercase),
+        Some(Action::CursorBottom)
+    );
+}
+
+#[test]
+fn override_and_conflicts() {
+    let config: KeyMapConfig = toml::from_str(
+        r#"
+page.toggle_bookmark = ["B"]
+page.open_search = ["j", "ctrl-w", "s"]
+dashboard.restore_session = ["r"]
+dashboard.no_such_action = ["x"]
+nowhere.quit = ["q"]
+"#,
+    )
+    .unwrap();
+    let mut problems = Vec::new();
+    let keymap = KeyMap::new(config, &mut problems);
+    problems.sort_unstable();
+    assert_eq!(
+        problems,
+        [
+            "`Ctrl-w` for `page.open_search` conflicts with `global.switch_focus`",
+            "`[keymap.nowhere]` is not a known scope",
+            "`j` for `page.open_search` conflicts with `page.cursor_down`",
+            "`no_such_action` is not an action in `[keymap.dashboard]`",
+            "`r` for `dashboard.restore_session` conflicts with typing in the input",
+        ]
+    );
+
+    let bookmark = event(KeyCode::Char('B'), KeyModifiers::SHIFT);
+    assert_eq!(
+        keymap.action(Scope::Page, &bookmark),
+        Some(Action::ToggleBookmark)
+    );
+    let b = event(KeyCode::Char('b'), KeyModifiers::NONE);
+    assert_eq!(keymap.action(Scope::Page, &b), None);
+    let s = event(KeyCode::Char('s'), KeyModifiers::NONE);
+    assert_eq!(keymap.action(Scope::Page, &s), Some(Action::OpenSearch));
+    assert_eq!(keymap.hint(Scope::Page, Action::OpenSearch), "s");
+}
+
+#[test]
+fn help_placeholders() {
+    let keymap = KeyMap::default();
+    let help =
+        keymap.help_markdown("Press {page.cursor_down}, {not.placeholder} or {page.nothing}.");
+    let first = help.lines().next().unwrap();
+    assert_eq!(
+        first,
+        "Press `Down` / `j`, {not.placeholder} or {page.nothing}."
+    );
+    assert!(help.contains("* `quit` (`Ctrl-q`): Quit."));
+}
@@ -0,0 +1,100 @@
+mod keymap;
+mod theme;
+
+pub use self::keymap::{Action, KeyMap, Scope};
+
+use crate::{color::Theme, err, Result};
+use serde::Deserialize;
+use std::{path::PathBuf, sync::OnceLock};
+use syntect::highlighting::Theme as SyntaxTheme;
+
+/// The content of `config.toml`. All fields are optional.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    keymap: keymap::KeyMapConfig,
+    theme: theme::ThemeConfig,
+    build: BuildConfig,
+}
+
+/// The `[build]` table in config file.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct BuildConfig {
+    /// The max number of docs compiled at the same time.
+    jobs: usize,
+}
+
+impl Default for BuildConfig {
+    fn default() -> Self {
+        BuildConfig { jobs: 2 }
+    }
+}
+
+/// User configuration merged with defaults.
+pub struct Config {
+    pub keymap: KeyMap,
+    pub theme: Theme,
+    /// The syntect theme to highlight code blocks.
+    pub syntax: SyntaxTheme,
+    /// The max number of docs compiled at the same time, and the others wait in a queue.
+    pub jobs: usize,
+    /// Invalid entries and key conflicts found in the config file.
+    pub problems: Vec<String>,
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Config is loaded once when it's first used.
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(load)
+}
+
+pub fn keymap() -> &'static KeyMap {
+    &config().keymap
+}
+
+/// `config.toml` under [`dirs::config_dir`] + `term-rustdoc` folder,
+/// e.g. `~/.config/term-rustdoc/config.toml` on Linux.
+pub fn path() -> Result<PathBuf> {
+    let mut path = dirs::config_dir().ok_or_else(|| err!("Can't find the config_dir"))?;
+    path.extend(["term-rustdoc", "config.toml"]);
+    Ok(path)
+}
+
+fn load() -> Config {
+    let mut problems = Vec::new();
+    let file = read_config_file().unwrap_or_else(|err| {
+        problems.push(format!("{err:#}"));
+        ConfigFile::default()
+    });
+    let keymap = KeyMap::new(file.keymap, &mut problems);
+    let dir = path()
+        .ok()
+        .and_then(|p| p.parent().map(|dir| dir.to_owned()));
+    let (theme, syntax) = theme::load(file.theme, dir.as_deref(), &mut problems);
+    let jobs = file.build.jobs.max(1);
+    if file.build.jobs == 0 {
+        problems.push(String::from("`build.jobs` should be at least 1"));
+    }
+    for problem in &problems {
+        error!("Config: {problem}");
+    }
+    info!("Config loaded");
+    Config {
+        keymap,
+        theme,
+        syntax,
+        jobs,
+        problems,
+    }
+}
+
+fn read_config_file() -> Result<ConfigFile> {
+    let path = path()?;
+    if !path.exists() {
+        return Ok(ConfigFile::default());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    toml::from_str(&content).map_err(|e| err!("Failed to parse {}:\n{e}", path.display()))
+}
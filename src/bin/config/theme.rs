@@ -0,0 +1,172 @@
+use crate::color::Theme;
+use ratatui::style::{Color, Modifier, Style};
+use rustc_hash::FxHashMap as HashMap;
+use serde::Deserialize;
+use std::path::Path;
+use syntect::highlighting::{Theme as SyntaxTheme, ThemeSet};
+use term_rustdoc::tree::TagGroup;
+
+/// `[theme]` in config file, or the content of a theme file in `themes` folder.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    /// A built-in theme, or a theme file name without `.toml` in `themes` folder.
+    /// A theme file can only be based on a built-in theme.
+    base: Option<String>,
+    /// A theme name in syntect's default themes, or a path to a `.tmTheme` file.
+    syntax: Option<String>,
+    styles: HashMap<String, StyleConfig>,
+    /// Colors of tag groups.
+    tags: HashMap<String, String>,
+}
+
+/// A style replaces the whole style in the base theme.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+struct StyleConfig {
+    fg: Option<String>,
+    bg: Option<String>,
+    /// E.g. `bold`, `italic`, `underlined` or `reversed`.
+    modifiers: Vec<String>,
+}
+
+/// The theme with overrides from config file and the loaded syntect theme.
+///
+/// `dir` is the folder of config file, which contains `themes` folder and is
+/// the base of relative `.tmTheme` paths.
+pub fn load(
+    config: ThemeConfig,
+    dir: Option<&Path>,
+    problems: &mut Vec<String>,
+) -> (Theme, SyntaxTheme) {
+    let mut theme = base_theme(config.base.as_deref(), dir, problems);
+    apply(config, &mut theme, "theme", problems);
+    let syntax = syntax_theme(&theme.syntax, dir).unwrap_or_else(|err| {
+        problems.push(err);
+        default_syntax_theme()
+    });
+    (theme, syntax)
+}
+
+fn base_theme(base: Option<&str>, dir: Option<&Path>, problems: &mut Vec<String>) -> Theme {
+    let name = base.unwrap_or("dark");
+    if let Some(theme) = Theme::builtin(name) {
+        return theme;
+    }
+    let file = match read_theme_file(name, dir) {
+        Ok(file) => file,
+        Err(err) => {
+            problems.push(err);
+            return Theme::default();
+        }
+    };
+    let base = file.base.as_deref().unwrap_or("dark");
+    let mut theme = Theme::builtin(base).unwrap_or_else(|| {
+        problems.push(format!(
+            "`{base}` in theme `{name}` is not a built-in theme: {:?}",
+            Theme::BUILTIN
+        ));
+        Theme::default()
+    });
+    apply(file, &mut theme, name, problems);
+    theme
+}
+
+fn read_theme_file(name: &str, dir: Option<&Path>) -> Result<ThemeConfig, String> {
+    let not_found = || {
+        format!(
+            "`{name}` is neither a built-in theme {:?} nor a theme file in `themes` folder",
+            Theme::BUILTIN
+        )
+    };
+    let path = dir
+        .map(|dir| dir.join("themes").join(format!("{name}.toml")))
+        .filter(|path| path.exists())
+        .ok_or_else(not_found)?;
+    let content = std::fs::read_to_string(&path)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+    toml::from_str(&content).map_err(|err| format!("Failed to parse {}:\n{err}", path.display()))
+}
+
+/// `source` is `theme` for `[theme]` in config file, or the name of a theme file.
+fn apply(config: ThemeConfig, theme: &mut Theme, source: &str, problems: &mut Vec<String>) {
+    if let Some(syntax) = config.syntax {
+        theme.syntax = syntax;
+    }
+    for (name, style) in config.styles {
+        let field = format!("{source}.styles.{name}");
+        match parse_style(&style, &field) {
+            Ok(style) => {
+                if !theme.set_style(&name, style) {
+                    problems.push(format!(
+                        "`{name}` is not a style in `{source}.styles`: {:?}",
+                        Theme::STYLES
+                    ));
+                }
+            }
+            Err(err) => problems.push(err),
+        }
+    }
+    for (name, color) in config.tags {
+        let Some(group) = TagGroup::from_name(&name) else {
+            let groups = TagGroup::ALL.map(TagGroup::name);
+            problems.push(format!(
+                "`{name}` is not a tag group in `{source}.tags`: {groups:?}"
+            ));
+            continue;
+        };
+        match parse_color(&color, &format!("{source}.tags.{name}")) {
+            Ok(color) => theme.set_tag_color(group, color),
+            Err(err) => problems.push(err),
+        }
+    }
+}
+
+fn parse_style(style: &StyleConfig, field: &str) -> Result<Style, String> {
+    let mut parsed = Style::new();
+    if let Some(fg) = &style.fg {
+        parsed.fg = Some(parse_color(fg, field)?);
+    }
+    if let Some(bg) = &style.bg {
+        parsed.bg = Some(parse_color(bg, field)?);
+    }
+    for modifier in &style.modifiers {
+        let m = Modifier::from_name(&modifier.to_ascii_uppercase())
+            .ok_or_else(|| format!("`{modifier}` for `{field}` is not a modifier"))?;
+        parsed.add_modifier |= m;
+    }
+    Ok(parsed)
+}
+
+/// A color name like `lightred`, an ANSI index like `8` or a hex like `#FF8C29`.
+fn parse_color(color: &str, field: &str) -> Result<Color, String> {
+    color
+        .parse()
+        .map_err(|_| format!("`{color}` for `{field}` is not a color"))
+}
+
+fn syntax_theme(syntax: &str, dir: Option<&Path>) -> Result<SyntaxTheme, String> {
+    let mut set = ThemeSet::load_defaults();
+    if let Some(theme) = set.themes.remove(syntax) {
+        return Ok(theme);
+    }
+    let path = Path::new(syntax);
+    let path = match dir {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path.to_owned(),
+    };
+    if !path.exists() {
+        let names: Vec<_> = set.themes.keys().collect();
+        return Err(format!(
+            "Syntax theme `{syntax}` is neither a default theme {names:?} nor a .tmTheme file"
+        ));
+    }
+    ThemeSet::get_theme(&path).map_err(|err| format!("Failed to load {}: {err}", path.display()))
+}
+
+fn default_syntax_theme() -> SyntaxTheme {
+    syntax_theme(&Theme::default().syntax, None).unwrap()
+}
+
+#[cfg(test)]
+mod tests;
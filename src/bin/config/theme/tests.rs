@@ -0,0 +1,75 @@
+use super::*;
+use term_rustdoc::tree::Tag;
+
+#[test]
+fn builtin_themes() {
+    for name in Theme::BUILTIN {
+        let (theme, _) = load(
+            toml::from_str(&format!("base = {name:?}")).unwrap(),
+            None,
+            &mut Vec::new(),
+        );
+        assert!(syntax_theme(&theme.syntax, None).is_ok(), "{name}");
+    }
+    // the dark theme keeps the default tag styles
+    let theme = Theme::default();
+    for tag in [Tag::Struct, Tag::Functions, Tag::Field, Tag::Unknown] {
+        assert_eq!(theme.tag(tag), tag.style());
+    }
+}
+
+#[test]
+fn overrides_and_problems() {
+    let config: ThemeConfig = toml::from_str(
+        r##"
+base = "light"
+syntax = "no-such.tmTheme"
+styles.link = { fg = "blue", modifiers = ["underlined"] }
+styles.jump = { fg = "#12" }
+styles.cursor = { bg = "red", modifiers = ["blinking"] }
+styles.nothing = {}
+tags.struct = "#FF0000"
+tags.structs = "red"
+"##,
+    )
+    .unwrap();
+    let mut problems = Vec::new();
+    let (theme, _) = load(config, None, &mut problems);
+    problems.sort_unstable();
+    assert_eq!(problems.len(), 5, "{problems:#?}");
+    assert!(problems[0].starts_with("Syntax theme `no-such.tmTheme` is neither"));
+    assert_eq!(problems[1], "`#12` for `theme.styles.jump` is not a color");
+    assert_eq!(
+        problems[2],
+        "`blinking` for `theme.styles.cursor` is not a modifier"
+    );
+    assert!(problems[3].starts_with("`nothing` is not a style in `theme.styles`"));
+    assert!(problems[4].starts_with("`structs` is not a tag group in `theme.tags`"));
+
+    let light = Theme::builtin("light").unwrap();
+    assert_eq!(
+        theme.link,
+        Style::new()
+            .fg(Color::Blue)
+            .add_modifier(Modifier::UNDERLINED)
+    );
+    assert_eq!(theme.jump, light.jump);
+    assert_eq!(theme.cursor, light.cursor);
+    assert_eq!(theme.tag_color(TagGroup::Struct), Color::Rgb(255, 0, 0));
+    assert_eq!(
+        theme.tag(Tag::Struct),
+        Tag::Struct.style_with(Color::Rgb(255, 0, 0))
+    );
+    assert_eq!(
+        theme.tag_color(TagGroup::Enum),
+        light.tag_color(TagGroup::Enum)
+    );
+
+    let mut problems = Vec::new();
+    load(
+        toml::from_str(r#"base = "solarized""#).unwrap(),
+        None,
+        &mut problems,
+    );
+    assert!(problems[0].starts_with("`solarized` is neither a built-in theme"));
+}
@@ -1,17 +1,19 @@
 mod ui;
 
-use crate::{event::Sender, fuzzy::Fuzzy, Result};
+use crate::{cli::Args, event::Sender, fuzzy::Fuzzy, Result};
 use ratatui::layout::Rect;
 
 use self::ui::UI;
 
+pub use self::ui::PendingJump;
+
 pub struct DashBoard {
     ui: UI,
 }
 
 impl DashBoard {
-    pub fn new(full: Rect, fuzzy: Fuzzy, sender: Sender) -> Result<Self> {
-        let ui = UI::new(full, fuzzy, sender);
+    pub fn new(full: Rect, fuzzy: Fuzzy, sender: Sender, args: &Args) -> Result<Self> {
+        let ui = UI::new(full, fuzzy, sender, args);
         Ok(DashBoard { ui })
     }
 
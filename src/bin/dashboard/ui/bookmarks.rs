@@ -0,0 +1,171 @@
+use crate::{
+    color::theme,
+    database::{Bookmark, DataBase, PkgKey},
+    ui::{render_line, LineState, Scroll, Surround},
+};
+use ratatui::prelude::{Buffer, Rect};
+use term_rustdoc::util::{xformat, XString};
+
+#[derive(Default)]
+pub struct Bookmarks {
+    list: Vec<Bookmark>,
+}
+
+impl std::ops::Deref for Bookmarks {
+    type Target = [Bookmark];
+
+    fn deref(&self) -> &Self::Target {
+        &self.list
+    }
+}
+
+impl LineState for Bookmark {
+    type State = Option<(PkgKey, XString)>;
+
+    fn state(&self) -> Self::State {
+        Some((self.pkg.clone(), self.path.clone()))
+    }
+
+    fn is_identical(&self, state: &Self::State) -> bool {
+        state
+            .as_ref()
+            .is_some_and(|(pkg, path)| self.pkg == *pkg && self.path == *path)
+    }
+}
+
+/// Bookmarked items in cached docs.
+#[derive(Default)]
+pub struct BookmarksUI {
+    inner: Scroll<Bookmarks>,
+    border: Surround,
+}
+
+impl BookmarksUI {
+    pub fn init(db: &DataBase) -> Self {
+        let mut ui = BookmarksUI::default();
+        match db.all_bookmarks() {
+            Ok(list) => ui.set_list(list),
+            Err(err) => error!("Failed to read bookmarks:\n{err}"),
+        }
+        ui
+    }
+
+    fn set_list(&mut self, mut list: Vec<Bookmark>) {
+        list.sort_unstable_by(|a, b| (a.pkg.name(), &a.path).cmp(&(b.pkg.name(), &b.path)));
+        self.inner.lines.list = list;
+        if !self.inner.check_if_can_return_to_previous_cursor() {
+            self.inner.start = 0;
+            self.inner.cursor.y = 0;
+        }
+    }
+
+    /// Add the bookmark if it doesn't exist, or remove it.
+    ///
+    /// Returns true if the bookmark is added.
+    pub fn toggle(&mut self, bookmark: Bookmark, db: &DataBase) -> bool {
+        let mut list = std::mem::take(&mut self.inner.lines.list);
+        let found = list
+            .iter()
+            .position(|b| b.pkg == bookmark.pkg && b.path == bookmark.path);
+        let added = if let Some(idx) = found {
+            let removed = list.remove(idx);
+            if let Err(err) = db.remove_bookmark(&removed) {
+                error!("Failed to remove the bookmark {}:\n{err}", removed.path);
+            }
+            false
+        } else {
+            if let Err(err) = db.save_bookmark(&bookmark) {
+                error!("Failed to save the bookmark {}:\n{err}", bookmark.path);
+            }
+            list.push(bookmark);
+            true
+        };
+        self.set_list(list);
+        added
+    }
+
+    /// Remove the bookmark under the cursor or the position on screen.
+    pub fn remove(&mut self, y: Option<u16>, db: &DataBase) {
+        if let Some(bookmark) = self.get(y).cloned() {
+            self.toggle(bookmark, db);
+        }
+    }
+
+    /// Save the note of the current bookmark.
+    pub fn set_note(&mut self, note: &str, db: &DataBase) {
+        let idx = self.inner.start + self.inner.cursor.y as usize;
+        if let Some(bookmark) = self.inner.lines.list.get_mut(idx) {
+            bookmark.note = note.trim().to_owned();
+            if let Err(err) = db.save_bookmark(bookmark) {
+                error!("Failed to save the note for {}:\n{err}", bookmark.path);
+            }
+        }
+    }
+
+    /// When y is Some, it comes from a mouse click posotion.
+    /// When y is None, it comes from a key press.
+    pub fn get(&self, y: Option<u16>) -> Option<&Bookmark> {
+        y.map_or_else(
+            || self.inner.get_line_of_current_cursor(),
+            |y| self.inner.get_line_on_screen(y),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.all_lines().is_empty()
+    }
+
+    pub fn scroll_text(&mut self) -> &mut Scroll<Bookmarks> {
+        &mut self.inner
+    }
+}
+
+/// Rendering
+impl BookmarksUI {
+    pub fn set_area(&mut self, surround: Surround) {
+        self.inner.area = surround.inner();
+        self.border = surround;
+    }
+
+    pub fn render(&self, buf: &mut Buffer, current: bool) {
+        self.border.render(buf);
+
+        let text = &self.inner;
+        let Some(lines) = text.visible_lines() else {
+            return;
+        };
+        let Rect { x, mut y, .. } = text.area;
+        let width = text.area.width as usize;
+
+        // render current selected bookmark
+        if current && text.get_line_of_current_cursor().is_some() {
+            let row = text.area.y + text.cursor.y;
+            let bg = theme().cursor_line_bg();
+            for col in x..text.area.width + x {
+                buf[(col, row)].set_bg(bg);
+            }
+        }
+
+        let theme = theme();
+        for (start, bookmark) in (text.start + 1..).zip(lines) {
+            let num = xformat!("{start:02}. ");
+            let ver = xformat!(" v{} ", bookmark.pkg.ver_str());
+            let note = if bookmark.note.is_empty() {
+                XString::default()
+            } else {
+                xformat!(" # {}", bookmark.note)
+            };
+            let line = [
+                (&*num, theme.pkg_name),
+                (&*bookmark.path, theme.pkg_name),
+                (&*ver, theme.pkg_version),
+                (&*note, theme.pkg_features),
+            ];
+            render_line(line, buf, x, y, width);
+            y += 1;
+        }
+
+        let text = xformat!(" Total {} ", self.inner.total_len());
+        self.border.render_only_bottom_right_text(buf, &text);
+    }
+}
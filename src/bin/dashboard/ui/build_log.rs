@@ -0,0 +1,139 @@
+//! A popup that shows the error of a failed compilation, which can be retried from here.
+
+use crate::{
+    color::theme,
+    config::{keymap, Action, Scope},
+    database::{PkgKey, PkgWithFeatures},
+    ui::{render_line, LineState, Scroll, Surround},
+};
+use ratatui::{
+    prelude::{Alignment, Buffer, Line, Rect},
+    widgets::{Block, Borders},
+};
+use term_rustdoc::util::XString;
+
+/// The error text wrapped in the popup width.
+#[derive(Default)]
+pub struct LogLines {
+    lines: Vec<XString>,
+}
+
+impl std::ops::Deref for LogLines {
+    type Target = [XString];
+
+    fn deref(&self) -> &Self::Target {
+        &self.lines
+    }
+}
+
+impl LineState for XString {
+    type State = XString;
+
+    fn state(&self) -> Self::State {
+        self.clone()
+    }
+
+    fn is_identical(&self, state: &Self::State) -> bool {
+        self == state
+    }
+}
+
+#[derive(Default)]
+pub struct BuildLog {
+    inner: Scroll<LogLines>,
+    border: Surround,
+    /// The pkg to compile again on retry.
+    pkg: Option<PkgWithFeatures>,
+    /// The error text including the full stderr from cargo.
+    err: String,
+}
+
+impl BuildLog {
+    pub fn new(key: &PkgKey, pkg: PkgWithFeatures, err: String, outer: Rect) -> Self {
+        let confirm = keymap().hint(Scope::DashBoard, Action::Confirm);
+        let close = keymap().hint(Scope::DashBoard, Action::Close);
+        let hint = format!(" {confirm} to retry / {close} to close ");
+        let block = Block::new()
+            .title(format!(" Failed to compile {key} "))
+            .title_bottom(Line::from(hint).alignment(Alignment::Right))
+            .borders(Borders::ALL);
+        let mut log = BuildLog {
+            border: Surround::new(block, Rect::default()),
+            pkg: Some(pkg),
+            err,
+            ..Default::default()
+        };
+        log.update_area(outer);
+        log
+    }
+
+    /// Wrap the error text again if the width changes.
+    pub fn update_area(&mut self, outer: Rect) {
+        if let Some(inner) = self.border.update_area(outer) {
+            let width = inner.width as usize;
+            self.inner.area = inner;
+            self.inner.lines.lines = wrap(&self.err, width);
+            self.inner.start = 0;
+            self.inner.cursor.y = 0;
+        }
+    }
+
+    /// The pkg to compile again. The popup should be closed afterwards.
+    pub fn take_pkg(&mut self) -> Option<PkgWithFeatures> {
+        self.pkg.take()
+    }
+
+    pub fn contains(&self, position: (u16, u16)) -> bool {
+        self.border.area().contains(position.into())
+    }
+
+    pub fn scroll_text(&mut self) -> &mut Scroll<LogLines> {
+        &mut self.inner
+    }
+
+    pub fn render(&self, buf: &mut Buffer) {
+        self.border.render(buf);
+
+        let text = &self.inner;
+        let Some(lines) = text.visible_lines() else {
+            return;
+        };
+        let Rect { x, mut y, .. } = text.area;
+        let width = text.area.width as usize;
+
+        if text.get_line_of_current_cursor().is_some() {
+            let row = text.area.y + text.cursor.y;
+            let bg = theme().cursor_line_bg();
+            for col in x..text.area.width + x {
+                buf[(col, row)].set_bg(bg);
+            }
+        }
+
+        let theme = theme();
+        for line in lines {
+            let style = if line.starts_with("error") {
+                theme.failed
+            } else {
+                theme.pkg_version
+            };
+            render_line([(&**line, style)], buf, x, y, width);
+            y += 1;
+        }
+    }
+}
+
+/// Wrap each line in the width, but keep the indentation of the first piece.
+fn wrap(err: &str, width: usize) -> Vec<XString> {
+    let width = width.max(1);
+    err.lines()
+        .flat_map(|line| {
+            if line.is_empty() {
+                return vec![XString::default()];
+            }
+            textwrap::wrap(line, width)
+                .into_iter()
+                .map(|piece| XString::from(&*piece))
+                .collect()
+        })
+        .collect()
+}
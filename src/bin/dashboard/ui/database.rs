@@ -2,14 +2,15 @@ mod cache;
 
 use self::cache::{Cache, CacheID, Count, SortKind};
 use crate::{
-    color::BG_CURSOR_LINE,
-    database::{CachedDocInfo, DataBase, Features, PkgKey, PkgWithFeatures},
+    cli::PkgQuery,
+    color::theme,
+    database::{CachedDocInfo, DataBase, Dependencies, LoadedDocs, PkgKey, PkgWithFeatures},
     event::Sender,
     fuzzy::Fuzzy,
     ui::{render_line, Scroll, Surround},
 };
 use ratatui::prelude::{Buffer, Rect};
-use term_rustdoc::{tree::CrateDoc, util::xformat};
+use term_rustdoc::util::{xformat, XString};
 
 #[derive(Default)]
 pub struct PkgDocs {
@@ -20,6 +21,8 @@ pub struct PkgDocs {
     /// because we need to sort caches for displaying, thus both lengths should equal.
     indices: Vec<CacheID>,
     fuzzy: Option<Fuzzy>,
+    /// The doc to be loaded once it's compiled, e.g. for `open` subcommand.
+    load_when_cached: Option<PkgKey>,
 }
 
 impl std::ops::Deref for PkgDocs {
@@ -115,19 +118,59 @@ impl DataBaseUI {
     /// But when the pkg is cached before, there will be a duplicate.
     /// In this case, this method will change its status.
     pub fn compile_doc(&mut self, pkg: PkgWithFeatures) {
-        if let Some(pkg_key) = self.pkg_docs().db.compile_doc(pkg) {
+        let key = PkgKey::from_pkg(&pkg);
+        if self.is_in_progress(&key) {
+            info!(?key, "the doc is being compiled");
+            return;
+        }
+        if let Some(handle) = self.pkg_docs().db.compile_doc(pkg) {
             let caches = &mut self.pkg_docs().caches;
-            if let Some(old) = caches.iter_mut().find(|cache| **cache == pkg_key) {
-                *old = Cache::new_being_cached(pkg_key);
+            if let Some(old) = caches.iter_mut().find(|cache| **cache == key) {
+                *old = Cache::new_being_cached(handle);
             } else {
                 let id = CacheID(caches.len());
-                caches.push(Cache::new_being_cached(pkg_key));
+                caches.push(Cache::new_being_cached(handle));
                 self.pkg_docs().indices.push(id);
             }
             self.sort_caches();
         }
     }
 
+    fn is_in_progress(&self, key: &PkgKey) -> bool {
+        let caches = &self.inner.lines.caches;
+        caches.iter().any(|cache| cache.is_in_progress(key))
+    }
+
+    /// Show the status line from cargo on the doc being compiled.
+    pub fn receive_progress(&mut self, key: &PkgKey, line: XString) {
+        let caches = &mut self.pkg_docs().caches;
+        if let Some(cache) = caches.iter_mut().find(|cache| cache.is_in_progress(key)) {
+            cache.set_progress(line);
+        }
+    }
+
+    /// The pkg and error text of a failed doc, which can be compiled again.
+    ///
+    /// y is Some for a mouse click, and None for a key press.
+    pub fn get_failure(&self, y: Option<u16>) -> Option<(PkgKey, PkgWithFeatures, String)> {
+        let line = y.map_or_else(
+            || self.inner.get_line_of_current_cursor(),
+            |y| self.inner.get_line_on_screen(y),
+        );
+        let cache = self.inner.lines.caches.get(line?.0)?;
+        let (handle, err) = cache.failure()?;
+        Some((handle.key().clone(), handle.pkg().clone(), err.to_owned()))
+    }
+
+    pub fn receive_failure(&mut self, key: &PkgKey, err: String) {
+        _ = (self.pkg_docs().load_when_cached).take_if(|k| k == key);
+        let caches = &mut self.pkg_docs().caches;
+        if let Some(cache) = caches.iter_mut().find(|cache| cache.is_in_progress(key)) {
+            cache.fail(err);
+        }
+        self.sort_caches();
+    }
+
     /// Sort the Cache vec because the inner states have changed.
     fn sort_caches(&mut self) {
         let kind = self.pkg_docs().caches_sort;
@@ -147,8 +190,8 @@ impl DataBaseUI {
     ///
     /// This method doesn't mean deleting the db file, so it won't
     /// apply for Cached kind.
-    /// It doesn't means removing the being-cached kind either, because
-    /// for now there is no way to cancel a compilation task.
+    /// For the being-cached kind, the compilation is cancelled; and the failed
+    /// kind is dismissed.
     pub fn downgrade(&mut self, y: Option<u16>) {
         let line = y.map_or_else(
             || self.inner.get_line_of_current_cursor(),
@@ -156,6 +199,9 @@ impl DataBaseUI {
         );
         if let Some(id) = line.map(|id| id.0) {
             if let Some(loaded) = self.inner.lines.caches.get_mut(id) {
+                if loaded.cancel() {
+                    return self.remove_cache(id);
+                }
                 if let Some(key) = loaded.downgrade() {
                     self.inner.lines.db.send_downgraded_doc(key);
                 }
@@ -164,6 +210,26 @@ impl DataBaseUI {
             }
         }
     }
+
+    /// Remove a doc being compiled or failed from the list. But if the doc was cached
+    /// before the compilation, it turns back to the cached one.
+    fn remove_cache(&mut self, id: usize) {
+        let pkg_docs = self.pkg_docs();
+        let key = pkg_docs.caches[id].pkg_key();
+        let cached = pkg_docs
+            .db
+            .all_caches()
+            .map(|caches| caches.into_iter().find(|info| info.pkg == *key));
+        match cached {
+            Ok(Some(info)) => pkg_docs.caches[id] = Cache::new_unloaded(info),
+            Ok(None) => {
+                pkg_docs.caches.remove(id);
+                self.clear_and_reset();
+            }
+            Err(err) => error!("Failed to read CachedDocInfo:\n{err}"),
+        }
+        self.sort_caches();
+    }
 }
 
 /// Rendering
@@ -187,25 +253,28 @@ impl DataBaseUI {
         let text = &self.inner;
         if current && text.get_line_of_current_cursor().is_some() {
             let row = text.area.y + text.cursor.y;
+            let bg = theme().cursor_line_bg();
             for col in x..text.area.width + x {
-                buf[(col, row)].set_bg(BG_CURSOR_LINE);
+                buf[(col, row)].set_bg(bg);
             }
         }
 
         let pkgs = &text.lines.caches;
         for id in ids {
             let num = xformat!("{start:02}. ");
-            let [(kind, style_kind), (name, style_name), (ver, style_ver), (feat, style_feat)] =
-                pkgs[id.0].line();
+            let [kind, name, ver, feat, target, status] = pkgs[id.0].line();
             let line = [
-                (kind, style_kind),
-                (" ", style_kind),
-                (&*num, style_name),
-                (name, style_name),
-                (" v", style_ver),
-                (ver, style_ver),
-                (" ", style_feat),
-                (feat, style_feat),
+                kind,
+                (" ", kind.1),
+                (&*num, name.1),
+                name,
+                (" v", ver.1),
+                ver,
+                (" ", feat.1),
+                feat,
+                target,
+                ("  ", status.1),
+                status,
             ];
             render_line(line, buf, x, y, width);
             start += 1;
@@ -241,6 +310,7 @@ impl DataBaseUI {
     }
 
     pub fn receive_compiled_doc(&mut self, info: CachedDocInfo) {
+        let load = (self.pkg_docs().load_when_cached).take_if(|key| *key == info.pkg);
         let key = &info.pkg;
         let caches = &mut self.pkg_docs().caches;
         if let Some(cache) = caches.iter_mut().find(|cache| cache.is_in_progress(key)) {
@@ -251,26 +321,94 @@ impl DataBaseUI {
             caches.push(Cache::new_unloaded(info));
             self.pkg_docs().indices.push(id);
         }
+        if let Some(key) = load {
+            let pkg_docs = self.pkg_docs();
+            if let Some(cache) = pkg_docs.caches.iter_mut().find(|c| *c.pkg_key() == key) {
+                cache.load_doc(&pkg_docs.db);
+            }
+        }
+        self.sort_caches();
+    }
+
+    /// Load the doc that matches the query. See [`PkgQuery::rank`] for the preference.
+    ///
+    /// Returns false if no cached doc is matched.
+    pub fn load_matched_doc(&mut self, query: &PkgQuery) -> bool {
+        let pkg_docs = self.pkg_docs();
+        let matched = pkg_docs
+            .caches
+            .iter_mut()
+            .filter(|cache| cache.loadable() && query.matches(cache.pkg_key()))
+            .max_by_key(|cache| PkgQuery::rank(cache.pkg_key()));
+        let Some(cache) = matched else {
+            return false;
+        };
+        cache.load_doc(&pkg_docs.db);
+        self.sort_caches();
+        true
+    }
+
+    /// Load the cached doc with the exact PkgKey. Returns false if it's not loadable.
+    pub fn load_doc_by_key(&mut self, key: &PkgKey) -> bool {
+        let pkg_docs = self.pkg_docs();
+        let found = pkg_docs
+            .caches
+            .iter_mut()
+            .find(|cache| cache.loadable() && cache.pkg_key() == key);
+        let Some(cache) = found else {
+            return false;
+        };
+        cache.load_doc(&pkg_docs.db);
         self.sort_caches();
+        true
+    }
+
+    /// Check if the doc is cached, no matter whether it's loaded or not.
+    pub fn is_cached(&self, key: &PkgKey) -> bool {
+        self.inner
+            .lines
+            .caches
+            .iter()
+            .any(|cache| cache.pkg_key() == key)
+    }
+
+    /// Compile the doc and load it when it's ready.
+    pub fn compile_and_load_doc(&mut self, pkg: PkgWithFeatures) {
+        let key = PkgKey::from_pkg(&pkg);
+        self.pkg_docs().load_when_cached = Some(key);
+        self.compile_doc(pkg);
     }
 
     pub fn is_empty(&self) -> bool {
         self.inner.all_lines().is_empty()
     }
 
+    pub fn db(&self) -> &DataBase {
+        &self.inner.lines.db
+    }
+
     pub fn scroll_text(&mut self) -> &mut Scroll<PkgDocs> {
         &mut self.inner
     }
 
-    pub fn get_loaded_doc(&self, key: &PkgKey) -> Option<CrateDoc> {
+    pub fn get_loaded_doc(&self, key: &PkgKey) -> Option<LoadedDocs> {
         let iter = &mut self.inner.lines.caches.iter();
         iter.find_map(|cache| cache.get_loaded_doc(key))
     }
 
-    pub fn get_current_pkg(&self) -> Option<(&str, &str, &Features)> {
+    /// Docs of direct dependencies loaded in background for the loaded doc.
+    /// They're dropped if the doc has been downgraded.
+    pub fn receive_dependencies(&mut self, mut deps: Dependencies) {
+        let iter = &mut self.inner.lines.caches.iter_mut();
+        if !iter.any(|cache| cache.set_dependencies(&mut deps)) {
+            info!(?deps.key, "dependencies are dropped because the doc is not loaded");
+        }
+    }
+
+    pub fn get_current_pkg(&self) -> Option<&PkgKey> {
         if let Some(idx) = self.inner.get_line_of_current_cursor().map(|id| id.0) {
             if let Some(cache) = self.inner.lines.caches.get(idx) {
-                return Some(cache.pkg_feat());
+                return Some(cache.pkg_key());
             }
         }
         None
@@ -2,16 +2,15 @@ mod inner;
 mod util;
 
 use self::inner::CacheInner;
-use crate::color::{PKG_FEATURES, PKG_NAME, PKG_VERSION};
-use crate::database::{CachedDocInfo, DataBase, Features, PkgKey};
+use crate::color::theme;
+use crate::database::{
+    BuildHandle, CachedDocInfo, DataBase, Dependencies, Features, LoadedDocs, PkgKey,
+};
 use ratatui::prelude::Style;
 use semver::Version;
-use std::time::SystemTime;
 use std::{cmp::Ordering, mem};
-use term_rustdoc::{
-    tree::CrateDoc,
-    util::{xformat, XString},
-};
+use std::{path::Path, time::SystemTime};
+use term_rustdoc::util::{xformat, XString};
 
 pub use self::util::{CacheID, Count, LoadedDoc, SortKind};
 
@@ -19,15 +18,23 @@ pub use self::util::{CacheID, Count, LoadedDoc, SortKind};
 pub struct Cache {
     inner: CacheInner,
     features: XString,
+    /// ` --target triple` or empty for the host.
+    target: XString,
+    /// The latest progress from cargo for the doc being compiled.
+    status: XString,
     ver: Version,
 }
 
 impl Cache {
-    pub fn new_being_cached(pkg_key: PkgKey) -> Cache {
+    pub fn new_being_cached(handle: BuildHandle) -> Cache {
+        let pkg_key = handle.key();
         Cache {
             ver: pkg_key.version(),
             features: xformat!("{:?}", pkg_key.features()),
-            inner: CacheInner::BeingCached(pkg_key, SystemTime::now()),
+            target: target_text(pkg_key),
+            // replaced by the status from cargo once the compilation starts
+            status: XString::const_new("Queued"),
+            inner: CacheInner::BeingCached(handle, SystemTime::now()),
         }
     }
 
@@ -35,17 +42,49 @@ impl Cache {
         Cache {
             ver: info.pkg.version(),
             features: xformat!("{:?}", info.pkg.features()),
+            target: target_text(&info.pkg),
+            status: XString::const_new(""),
             inner: CacheInner::Unloaded(info),
         }
     }
 
-    pub fn pkg_feat(&self) -> (&str, &str, &Features) {
-        let pkg = self.inner.pkg_key();
-        (pkg.name(), pkg.ver_str(), pkg.features())
+    pub fn is_in_progress(&self, key: &PkgKey) -> bool {
+        matches!(&self.inner, CacheInner::BeingCached(handle, _) if handle.key() == key)
     }
 
-    pub fn is_in_progress(&self, key: &PkgKey) -> bool {
-        matches!(&self.inner, CacheInner::BeingCached(pkg, _) if pkg == key)
+    /// Show the latest status line from cargo for the doc being compiled.
+    pub fn set_progress(&mut self, line: XString) {
+        if let CacheInner::BeingCached(_, _) = self.inner {
+            self.status = line;
+        }
+    }
+
+    /// The doc being compiled turns into a failed one with the error text.
+    pub fn fail(&mut self, err: String) {
+        if let CacheInner::BeingCached(handle, started) = &self.inner {
+            self.inner = CacheInner::Failed(handle.clone(), *started, err);
+        }
+    }
+
+    /// The build handle and error text of a failed doc.
+    pub fn failure(&self) -> Option<(&BuildHandle, &str)> {
+        match &self.inner {
+            CacheInner::Failed(handle, _, err) => Some((handle, err)),
+            _ => None,
+        }
+    }
+
+    /// Cancel the compilation, and returns true for a doc being compiled or failed,
+    /// which should be removed from the list.
+    pub fn cancel(&self) -> bool {
+        match &self.inner {
+            CacheInner::BeingCached(handle, _) => {
+                handle.cancel();
+                true
+            }
+            CacheInner::Failed(_, _, _) => true,
+            _ => false,
+        }
     }
 
     pub fn loadable(&self) -> bool {
@@ -56,8 +95,10 @@ impl Cache {
     /// Be aware to write old valid value back after replacement.
     fn empty_state() -> Cache {
         Cache {
-            inner: CacheInner::BeingCached(PkgKey::empty_state(), SystemTime::now()),
+            inner: CacheInner::Failed(BuildHandle::empty_state(), SystemTime::now(), String::new()),
             features: XString::const_new(""),
+            target: XString::const_new(""),
+            status: XString::const_new(""),
             ver: Version::new(0, 0, 0),
         }
     }
@@ -68,16 +109,18 @@ impl Cache {
             CacheInner::Unloaded(unloaded) => {
                 old = match unloaded.load_doc() {
                     Ok(doc) => {
-                        let key = Box::new(unloaded.pkg.clone());
-                        if let Err(err) = db.send_doc(key) {
-                            error!("Loaded Error:\n{err}");
-                        }
+                        // the doc is sent to Page once dependencies are loaded
+                        db.load_dependencies(&unloaded, doc.clone());
                         Cache {
                             inner: CacheInner::Loaded(LoadedDoc {
                                 info: unloaded,
                                 doc,
+                                dependencies: None,
+                                missing: Vec::new(),
                             }),
                             features: old.features,
+                            target: old.target,
+                            status: old.status,
                             ver: old.ver,
                         }
                     }
@@ -88,11 +131,11 @@ impl Cache {
                         );
                         match unloaded.load_pkg_info_features() {
                             Ok(pkg) => match db.compile_doc(pkg) {
-                                Some(key) => {
+                                Some(handle) => {
                                     // FIXME: use UI to notify the doc is being recompiled,
                                     // for now we only see a HOLDON icon after clicking CACHE.
-                                    info!("Recompiling the doc for {key:?}!");
-                                    *self = Cache::new_being_cached(key);
+                                    info!("Recompiling the doc for {:?}!", handle.key());
+                                    *self = Cache::new_being_cached(handle);
                                     return;
                                 }
                                 None => error!("Failed to recompile the doc"),
@@ -102,19 +145,25 @@ impl Cache {
                         Cache {
                             inner: CacheInner::Unloaded(unloaded),
                             features: old.features,
+                            target: old.target,
+                            status: old.status,
                             ver: old.ver,
                         }
                     }
                 }
             }
             CacheInner::Loaded(loaded) => {
-                let key = Box::new(loaded.info.pkg.clone());
-                if let Err(err) = db.send_doc(key) {
-                    error!("Loaded Error:\n{err}");
+                if loaded.dependencies.is_some() {
+                    let key = Box::new(loaded.info.pkg.clone());
+                    if let Err(err) = db.send_doc(key) {
+                        error!("Loaded Error:\n{err}");
+                    }
                 }
                 old = Cache {
                     inner: CacheInner::Loaded(loaded),
                     features: old.features,
+                    target: old.target,
+                    status: old.status,
                     ver: old.ver,
                 };
             }
@@ -123,13 +172,33 @@ impl Cache {
         *self = old;
     }
 
-    pub fn get_loaded_doc(&self, key: &PkgKey) -> Option<CrateDoc> {
+    /// The loaded doc, docs of its direct dependencies and the uncached dependencies.
+    pub fn get_loaded_doc(&self, key: &PkgKey) -> Option<LoadedDocs> {
         match &self.inner {
-            CacheInner::Loaded(loaded) if loaded.info.pkg == *key => Some(loaded.doc.clone()),
+            CacheInner::Loaded(loaded) if loaded.info.pkg == *key => {
+                let deps = loaded.dependencies.clone().unwrap_or_default();
+                Some((loaded.doc.clone(), deps, loaded.missing.clone()))
+            }
             _ => None,
         }
     }
 
+    /// Returns true if the dependencies are received by the loaded doc with the key.
+    /// The doc is replaced if items are inlined from them.
+    pub fn set_dependencies(&mut self, deps: &mut Dependencies) -> bool {
+        match &mut self.inner {
+            CacheInner::Loaded(loaded) if loaded.info.pkg == deps.key => {
+                if let Some(inlined) = deps.inlined.take() {
+                    loaded.doc = inlined;
+                }
+                loaded.dependencies = Some(mem::take(&mut deps.docs));
+                loaded.missing = mem::take(&mut deps.missing);
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub fn downgrade(&mut self) -> Option<Box<PkgKey>> {
         let mut key = None;
         let mut old = mem::replace(self, Cache::empty_state());
@@ -142,18 +211,24 @@ impl Cache {
         key
     }
 
-    pub fn line(&self) -> [(&str, Style); 4] {
+    pub fn line(&self) -> [(&str, Style); 6] {
         let kind = self.inner.kind();
         let key = self.inner.pkg_key();
+        let status = match &self.inner {
+            CacheInner::Failed(_, _, err) => (error_summary(err), theme().failed),
+            _ => (&*self.status, theme().pkg_version),
+        };
         [
             kind,
-            (key.name(), PKG_NAME),
-            (key.ver_str(), PKG_VERSION),
-            (&self.features, PKG_FEATURES),
+            (key.name(), theme().pkg_name),
+            (key.ver_str(), theme().pkg_version),
+            (&self.features, theme().pkg_features),
+            (&self.target, theme().pkg_version),
+            status,
         ]
     }
 
-    fn pkg_key(&self) -> &PkgKey {
+    pub fn pkg_key(&self) -> &PkgKey {
         self.inner.pkg_key()
     }
 
@@ -166,86 +241,31 @@ impl Cache {
             CacheInner::Loaded(_) => count.loaded += 1,
             CacheInner::Unloaded(_) => count.unloaded += 1,
             CacheInner::BeingCached(_, _) => count.in_progress += 1,
+            CacheInner::Failed(_, _, _) => count.failed += 1,
         }
     }
 }
 
 impl Cache {
-    /// Sort by name, version and features, in groups.
+    /// Sort by name, version, features and target, in groups.
     pub fn cmp_by_pkg_key_grouped(&self, other: &Self) -> Ordering {
-        match (&self.inner, &other.inner) {
-            (CacheInner::Loaded(a), CacheInner::Loaded(b)) => {
-                match a.info.pkg.name().cmp(b.info.pkg.name()) {
-                    Ordering::Equal => match self.ver.cmp(&other.ver) {
-                        Ordering::Equal => {
-                            let features1 = a.info.pkg.features();
-                            let features2 = b.info.pkg.features();
-                            features1.cmp(features2)
-                        }
-                        ord => ord,
-                    },
-                    ord => ord,
-                }
-            }
-            (CacheInner::Loaded(_), _) => Ordering::Less,
-            (CacheInner::BeingCached(_, _), CacheInner::Loaded(_)) => Ordering::Greater,
-            (CacheInner::BeingCached(a, _), CacheInner::BeingCached(b, _)) => {
-                match a.name().cmp(b.name()) {
-                    Ordering::Equal => match self.ver.cmp(&other.ver) {
-                        Ordering::Equal => {
-                            let features1 = a.features();
-                            let features2 = b.features();
-                            features1.cmp(features2)
-                        }
-                        ord => ord,
-                    },
-                    ord => ord,
-                }
-            }
-            (CacheInner::BeingCached(_, _), CacheInner::Unloaded(_)) => Ordering::Less,
-            (CacheInner::Unloaded(a), CacheInner::Unloaded(b)) => {
-                match a.pkg.name().cmp(b.pkg.name()) {
-                    Ordering::Equal => match self.ver.cmp(&other.ver) {
-                        Ordering::Equal => {
-                            let features1 = a.pkg.features();
-                            let features2 = b.pkg.features();
-                            features1.cmp(features2)
-                        }
-                        ord => ord,
-                    },
-                    ord => ord,
-                }
-            }
-            (CacheInner::Unloaded(_), _) => Ordering::Greater,
-        }
+        let group = self.inner.group().cmp(&other.inner.group());
+        group.then_with(|| self.cmp_by_pkg_key_for_all(other))
     }
 
     /// Recent ones are first, in groups.
     pub fn cmp_by_time_grouped(&self, other: &Self) -> Ordering {
-        match (&self.inner, &other.inner) {
-            (CacheInner::Loaded(a), CacheInner::Loaded(b)) => {
-                b.info.started_time().cmp(&a.info.started_time())
-            }
-            (CacheInner::Loaded(_), _) => Ordering::Less,
-            (CacheInner::BeingCached(_, _), CacheInner::Loaded(_)) => Ordering::Greater,
-            (CacheInner::BeingCached(_, a), CacheInner::BeingCached(_, b)) => b.cmp(a),
-            (CacheInner::BeingCached(_, _), CacheInner::Unloaded(_)) => Ordering::Less,
-            (CacheInner::Unloaded(a), CacheInner::Unloaded(b)) => {
-                b.started_time().cmp(&a.started_time())
-            }
-            (CacheInner::Unloaded(_), _) => Ordering::Greater,
-        }
+        let group = self.inner.group().cmp(&other.inner.group());
+        group.then_with(|| self.cmp_by_time_for_all(other))
     }
 
-    /// Sort by name, version and features, for all.
+    /// Sort by name, version, features, target, private items and local pkg dir, for all.
     pub fn cmp_by_pkg_key_for_all(&self, other: &Self) -> Ordering {
         let name = self.pkg_key().name();
         match name.cmp(other.inner.pkg_key().name()) {
             Ordering::Equal => match self.ver.cmp(&other.ver) {
                 Ordering::Equal => {
-                    let features1 = self.pkg_key().features();
-                    let features2 = other.inner.pkg_key().features();
-                    features1.cmp(features2)
+                    key_options(self.pkg_key()).cmp(&key_options(other.inner.pkg_key()))
                 }
                 ord => ord,
             },
@@ -262,11 +282,39 @@ impl Cache {
         match &self.inner {
             CacheInner::Loaded(loaded) => loaded.info.started_time(),
             CacheInner::Unloaded(unloaded) => unloaded.started_time(),
-            CacheInner::BeingCached(_, time) => *time,
+            CacheInner::BeingCached(_, time) | CacheInner::Failed(_, time, _) => *time,
         }
     }
 }
 
+/// The first line from rustc or cargo that starts with `error`, otherwise the first line.
+fn error_summary(err: &str) -> &str {
+    let mut lines = err.lines();
+    let first = lines.clone().next().unwrap_or_default();
+    lines
+        .find(|line| line.starts_with("error"))
+        .unwrap_or(first)
+}
+
+fn key_options(key: &PkgKey) -> (&Features, Option<&str>, bool, Option<&Path>) {
+    (key.features(), key.target(), key.private(), key.local())
+}
+
+/// The target, private items and the local pkg dir if any.
+fn target_text(key: &PkgKey) -> XString {
+    let mut text = key
+        .target()
+        .map(|t| xformat!(" --target {t}"))
+        .unwrap_or_default();
+    if key.private() {
+        text.push_str(" --document-private-items");
+    }
+    if key.local().is_some() {
+        text.push_str(" (local)");
+    }
+    text
+}
+
 impl PartialEq<PkgKey> for Cache {
     fn eq(&self, other: &PkgKey) -> bool {
         self.inner.pkg_key() == other
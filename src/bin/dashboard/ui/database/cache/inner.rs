@@ -1,7 +1,7 @@
 use super::LoadedDoc;
 use crate::{
-    color::{CACHED, HOLDON, LOADED},
-    database::{CachedDocInfo, PkgKey},
+    color::theme,
+    database::{BuildHandle, CachedDocInfo, PkgKey},
 };
 use ratatui::prelude::Style;
 use std::time::SystemTime;
@@ -12,7 +12,9 @@ pub enum CacheInner {
     /// cached but not loaded docs
     Unloaded(CachedDocInfo),
     /// pkgs which is being sent to compile doc
-    BeingCached(PkgKey, SystemTime),
+    BeingCached(BuildHandle, SystemTime),
+    /// pkgs failed to compile doc, with the error text including the stderr from cargo
+    Failed(BuildHandle, SystemTime, String),
 }
 
 impl CacheInner {
@@ -20,15 +22,26 @@ impl CacheInner {
         match self {
             CacheInner::Loaded(load) => &load.info.pkg,
             CacheInner::Unloaded(unload) => &unload.pkg,
-            CacheInner::BeingCached(pk, _) => pk,
+            CacheInner::BeingCached(handle, _) | CacheInner::Failed(handle, _, _) => handle.key(),
+        }
+    }
+
+    /// Groups are ordered as Loaded, HoldOn, Failed and Cached.
+    pub fn group(&self) -> u8 {
+        match self {
+            CacheInner::Loaded(_) => 0,
+            CacheInner::BeingCached(_, _) => 1,
+            CacheInner::Failed(_, _, _) => 2,
+            CacheInner::Unloaded(_) => 3,
         }
     }
 
     pub fn kind(&self) -> (&'static str, Style) {
         match self {
-            CacheInner::Loaded(_) => ("[Loaded]", LOADED),
-            CacheInner::Unloaded(_) => ("[Cached]", CACHED),
-            CacheInner::BeingCached(_, _) => ("[HoldOn]", HOLDON),
+            CacheInner::Loaded(_) => ("[Loaded]", theme().loaded),
+            CacheInner::Unloaded(_) => ("[Cached]", theme().cached),
+            CacheInner::BeingCached(_, _) => ("[HoldOn]", theme().holdon),
+            CacheInner::Failed(_, _, _) => ("[Failed]", theme().failed),
         }
     }
 }
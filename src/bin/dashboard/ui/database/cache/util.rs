@@ -1,11 +1,18 @@
 use super::Cache;
-use crate::{database::CachedDocInfo, ui::LineState};
+use crate::{
+    database::{CachedDocInfo, CrateReq, PkgKey},
+    ui::LineState,
+};
 use std::cmp::Ordering;
 use term_rustdoc::{tree::CrateDoc, util::XString};
 
 pub struct LoadedDoc {
     pub info: CachedDocInfo,
     pub doc: CrateDoc,
+    /// Docs of direct dependencies, or None if they're being loaded in background.
+    pub dependencies: Option<Vec<(PkgKey, CrateDoc)>>,
+    /// External crates whose docs in the versions used are not cached.
+    pub missing: Vec<CrateReq>,
 }
 
 pub struct CacheID(pub usize);
@@ -27,6 +34,7 @@ pub struct Count {
     pub loaded: usize,
     pub unloaded: usize,
     pub in_progress: usize,
+    pub failed: usize,
 }
 
 impl Count {
@@ -36,6 +44,7 @@ impl Count {
             loaded,
             unloaded,
             in_progress,
+            failed,
         } = self;
         let mut text = XString::const_new(" ");
         if loaded != 0 {
@@ -47,7 +56,10 @@ impl Count {
         if in_progress != 0 {
             write!(&mut text, "HoldOn: {in_progress} / ").unwrap();
         }
-        let total = loaded + unloaded + in_progress;
+        if failed != 0 {
+            write!(&mut text, "Failed: {failed} / ").unwrap();
+        }
+        let total = loaded + unloaded + in_progress + failed;
         if total != 0 {
             write!(&mut text, "Total: {total} ").unwrap();
         }
@@ -1,3 +1,5 @@
+mod bookmarks;
+mod build_log;
 mod database;
 mod registry;
 mod search;
@@ -5,14 +7,24 @@ mod ver_feat_toml;
 mod version_features;
 
 use self::{
-    database::DataBaseUI, registry::Registry, search::Search, ver_feat_toml::PkgToml,
+    bookmarks::BookmarksUI,
+    build_log::BuildLog,
+    database::DataBaseUI,
+    registry::Registry,
+    search::{Mode, Search},
+    ver_feat_toml::PkgToml,
     version_features::VersionFeatures,
 };
 use crate::{
-    database::{CachedDocInfo, PkgKey},
+    cli::{Args, Command, PkgQuery},
+    database::{
+        Bookmark, CachedDocInfo, Dependencies, Features, LoadedDocs, PkgKey, PkgWithFeatures,
+        Session,
+    },
     event::Sender,
     frame::centered_rect,
     fuzzy::Fuzzy,
+    local_registry::lock_dependencies,
     ui::{ScrollOffset, Scrollable, Surround},
 };
 use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
@@ -20,7 +32,15 @@ use ratatui::{
     prelude::{Buffer, Constraint, Layout, Rect, Widget},
     widgets::{Block, Borders},
 };
-use term_rustdoc::tree::CrateDoc;
+use term_rustdoc::util::XString;
+
+/// Where the Page goes once the doc is loaded.
+pub enum PendingJump {
+    /// The item path of a bookmark.
+    Item(XString),
+    /// The state of last session.
+    Session(Box<Session>),
+}
 
 #[derive(Default)]
 pub struct UI {
@@ -29,29 +49,52 @@ pub struct UI {
     registry: Registry,
     pkg_toml: PkgToml,
     ver_feat: VersionFeatures,
+    bookmarks: BookmarksUI,
+    build_log: BuildLog,
+    /// Where the Page goes once the doc is loaded.
+    pending_jump: Option<(PkgKey, PendingJump)>,
+    /// The session saved on last quit, which is offered to restore until a doc is loaded.
+    last_session: Option<Session>,
     area: Area,
+    /// Document private items for pkgs from local paths.
+    document_private_items: bool,
 }
 
 impl UI {
     fn update_area(&mut self, full: Rect) {
         // skip rendering is the same area
-        if let Some([pkg_toml, search, db, registry]) = self.area.update(full) {
+        if let Some([pkg_toml, search, db, registry, bookmarks]) = self.area.update(full) {
             self.pkg_toml.set_area(pkg_toml);
-            // update areas of search, database and registry
+            // update areas of search, database, registry and bookmarks
             self.search.set_area(search);
             self.database.set_area(db);
             self.registry.set_area(registry);
+            self.bookmarks.set_area(bookmarks);
         }
         self.ver_feat.update_area(self.center());
+        self.build_log.update_area(self.center());
     }
 
-    pub fn new(full: Rect, fuzzy: Fuzzy, sender: Sender) -> Self {
+    pub fn new(full: Rect, fuzzy: Fuzzy, sender: Sender, args: &Args) -> Self {
+        let database = DataBaseUI::init(sender, fuzzy.clone());
         let mut ui = UI {
-            database: DataBaseUI::init(sender, fuzzy.clone()),
+            bookmarks: BookmarksUI::init(database.db()),
+            database,
             registry: Registry::new_local(fuzzy),
+            document_private_items: args.document_private_items,
             ..Default::default()
         };
-        ui.switch_panel(); // switch to database if caches are not empty
+        for path in args.paths.iter().rev() {
+            ui.registry.add_local_path(path);
+        }
+        if args.paths.is_empty() && !ui.database.is_empty() {
+            ui.area.current = Panel::Database;
+        }
+        if let Some(Command::Open(query)) = &args.command {
+            ui.open(query);
+        } else {
+            ui.init_last_session();
+        }
         ui.update_area(full);
         ui.update_pkg_toml();
         info!("DashBoard UI initialized.");
@@ -63,22 +106,30 @@ impl UI {
             Panel::Database => self.database.scroll_text() as &mut dyn Scrollable,
             Panel::LocalRegistry => self.registry.scroll_text(),
             Panel::VersionFeatures => &mut self.ver_feat,
+            Panel::Bookmarks => self.bookmarks.scroll_text(),
+            Panel::BuildLog => self.build_log.scroll_text(),
         }
     }
 
     fn update_pkg_toml(&mut self) {
         match self.area.current {
             Panel::Database => {
-                if let Some((name, ver, features)) = self.database.get_current_pkg() {
-                    self.pkg_toml.update_toml(name, ver, features);
+                if let Some(pkg) = self.database.get_current_pkg() {
+                    self.pkg_toml.update_toml_for_key(pkg);
                 }
             }
             Panel::LocalRegistry => {
                 if let Some((name, ver)) = self.registry.get_current_pkg() {
-                    self.pkg_toml.update_toml(name, ver, &Default::default());
+                    self.pkg_toml
+                        .update_toml(name, ver, &Default::default(), None);
                 }
             }
-            Panel::VersionFeatures => (),
+            Panel::Bookmarks => {
+                if let Some(bookmark) = self.bookmarks.get(None) {
+                    self.pkg_toml.update_toml_for_key(&bookmark.pkg);
+                }
+            }
+            Panel::VersionFeatures | Panel::BuildLog => (),
         };
     }
 
@@ -113,22 +164,44 @@ impl UI {
     }
 
     pub fn compile_or_load_doc(&mut self, y: Option<u16>) {
+        if y.is_none() {
+            match self.search.mode() {
+                Mode::LocalPath => return self.confirm_local_path(),
+                Mode::Note => return self.confirm_note(),
+                Mode::Search => (),
+            }
+        }
         match self.area.current {
-            Panel::Database => self.database.load_doc(y),
+            Panel::Database => {
+                if !self.open_build_log(y) {
+                    self.database.load_doc(y);
+                }
+            }
+            Panel::Bookmarks => self.open_bookmark(y),
             Panel::LocalRegistry => {
                 if let Some(pkg_info) = self.registry.get_pkg(y) {
+                    if pkg_info.is_sysroot() {
+                        // json docs from rust-docs-json have no version or feature to select
+                        let pkg = PkgWithFeatures::with_default_features(pkg_info);
+                        self.database.compile_doc(pkg);
+                        return;
+                    }
                     if !self.ver_feat.features().is_same_pkg(&pkg_info) {
-                        let all = self
-                            .registry
-                            .scroll_text()
-                            .lines
-                            .get_all_version(pkg_info.name());
+                        let all = if self.registry.is_local(&pkg_info) {
+                            // pkgs from local paths have the sole version
+                            vec![pkg_info.clone()]
+                        } else {
+                            self.registry
+                                .scroll_text()
+                                .lines
+                                .get_all_version(pkg_info.name())
+                        };
                         self.ver_feat = VersionFeatures::new(pkg_info, all, self.center());
                     }
                     if self.ver_feat.skip_selection() {
                         // no feature to select for sole local pkg, thus compile the doc directly
-                        if let Some(pkg) = self.ver_feat.features().pkg_with_features() {
-                            self.database.compile_doc(pkg)
+                        if let Some(pkg) = self.ver_feat.pkg_with_features() {
+                            self.compile_doc(pkg)
                         }
                     } else {
                         self.area.current = Panel::VersionFeatures;
@@ -138,16 +211,195 @@ impl UI {
             Panel::VersionFeatures => {
                 self.ver_feat.toggle_features();
             }
+            Panel::BuildLog if y.is_none() => self.retry_compilation(),
+            Panel::BuildLog => (),
         }
     }
 
-    fn comfirm_features_and_compile_doc(&mut self) {
-        if let Some(pkg) = self.ver_feat.features().pkg_with_features() {
+    /// Pop up the error of a failed doc in Database panel. Returns false if it's not failed.
+    fn open_build_log(&mut self, y: Option<u16>) -> bool {
+        let Some((key, pkg, err)) = self.database.get_failure(y) else {
+            return false;
+        };
+        self.build_log = BuildLog::new(&key, pkg, err, self.center());
+        self.area.current = Panel::BuildLog;
+        true
+    }
+
+    /// Compile the failed doc again with the same pkg, features and target.
+    fn retry_compilation(&mut self) {
+        if let Some(pkg) = self.build_log.take_pkg() {
             self.database.compile_doc(pkg);
+        }
+        self.area.current = Panel::Database;
+    }
+
+    fn comfirm_features_and_compile_doc(&mut self) {
+        if let Some(pkg) = self.ver_feat.pkg_with_features() {
+            self.compile_doc(pkg);
             self.area.current = Panel::Database;
         }
     }
 
+    /// Private items are only documented for pkgs from local paths.
+    fn compile_doc(&mut self, mut pkg: PkgWithFeatures) {
+        pkg.document_private_items =
+            self.document_private_items && self.registry.is_local(&pkg.info);
+        self.database.compile_doc(pkg);
+    }
+
+    /// Select the pkg under the cursor in Registry panel to compile in a batch, or unselect it.
+    pub fn toggle_select(&mut self) {
+        if let Panel::LocalRegistry = self.area.current {
+            self.registry.toggle_select();
+        }
+    }
+
+    /// Compile docs of selected pkgs with default features for the host.
+    pub fn compile_selected(&mut self) {
+        let selected = self.registry.take_selected();
+        if selected.is_empty() {
+            return;
+        }
+        info!("compile {} selected pkgs in a batch", selected.len());
+        for info in selected {
+            self.compile_doc(PkgWithFeatures::with_default_features(info));
+        }
+        self.area.current = Panel::Database;
+    }
+
+    /// Compile docs of dependencies in Cargo.lock of the local pkg under the cursor
+    /// in Registry panel. Cached docs and pkgs not found in local registry are skipped.
+    pub fn compile_dependencies(&mut self) {
+        if !matches!(self.area.current, Panel::LocalRegistry) {
+            return;
+        }
+        let Some(pkg) = self.registry.get_pkg(None) else {
+            return;
+        };
+        if !self.registry.is_local(&pkg) {
+            error!("{} is not a pkg from local paths", pkg.name());
+            return;
+        }
+        let deps = match lock_dependencies(pkg.path()) {
+            Ok(deps) => deps,
+            Err(err) => return error!("{err}"),
+        };
+        let (mut queued, mut missing) = (0, 0);
+        for (name, ver) in deps {
+            let Some(info) = self.registry.find_pkg(&name, Some(&ver)) else {
+                missing += 1;
+                continue;
+            };
+            let key = PkgKey::new(info.to_name_ver(), Features::Default, None);
+            if !self.database.is_cached(&key) {
+                self.compile_doc(PkgWithFeatures::with_default_features(info));
+                queued += 1;
+            }
+        }
+        info!(
+            queued,
+            missing,
+            "compile dependencies in Cargo.lock of {}",
+            pkg.name()
+        );
+        if queued != 0 {
+            self.area.current = Panel::Database;
+        }
+    }
+
+    /// Load the doc for `open` subcommand, or compile it first if not cached.
+    fn open(&mut self, query: &PkgQuery) {
+        if self.database.load_matched_doc(query) {
+            return;
+        }
+        let (name, ver) = query.name_ver();
+        let Some(info) = self.registry.find_pkg(name, ver) else {
+            error!("`{query}` is neither cached nor found in local registry");
+            return;
+        };
+        let document_private_items = self.document_private_items && self.registry.is_local(&info);
+        let pkg = PkgWithFeatures {
+            features: query.features().unwrap_or_default(),
+            info,
+            document_private_items,
+            target: query.target(),
+        };
+        self.database.compile_and_load_doc(pkg);
+        // show the progress in database panel
+        self.area.current = Panel::Database;
+    }
+
+    /// Load the doc of the bookmark, and the Page will jump to the item once it's loaded.
+    fn open_bookmark(&mut self, y: Option<u16>) {
+        let Some(bookmark) = self.bookmarks.get(y).cloned() else {
+            return;
+        };
+        if self.database.load_doc_by_key(&bookmark.pkg) {
+            self.pending_jump = Some((bookmark.pkg, PendingJump::Item(bookmark.path)));
+        } else {
+            error!(pkg = ?bookmark.pkg, "the doc of the bookmark is not cached");
+        }
+    }
+
+    /// The item path to jump to after the doc is loaded.
+    ///
+    /// Once a doc is loaded, the last session is no longer offered.
+    pub fn take_pending_jump(&mut self, key: &PkgKey) -> Option<PendingJump> {
+        self.last_session = None;
+        self.pending_jump
+            .take_if(|(pkg, _)| pkg == key)
+            .map(|(_, jump)| jump)
+    }
+
+    /// Offer the last session if its doc is still cached.
+    fn init_last_session(&mut self) {
+        let db = self.database.db();
+        let Some(session) = db.last_session() else {
+            return;
+        };
+        if self.database.is_cached(&session.pkg) {
+            self.last_session = Some(session);
+        } else if let Err(err) = db.remove_session() {
+            error!("Failed to remove the stale session:\n{err}");
+        }
+    }
+
+    /// Load the doc of last session, and the Page will restore the state once it's loaded.
+    pub fn restore_last_session(&mut self) {
+        let Some(session) = self.last_session.take() else {
+            return;
+        };
+        let pkg = session.pkg.clone();
+        if self.database.load_doc_by_key(&pkg) {
+            self.pending_jump = Some((pkg, PendingJump::Session(Box::new(session))));
+        } else {
+            error!(?pkg, "the doc of last session is not cached");
+        }
+    }
+
+    pub fn save_session(&self, session: &Session) {
+        if let Err(err) = self.database.db().save_session(session) {
+            error!("Failed to save the session:\n{err}");
+        }
+    }
+
+    /// Bookmark the item, or remove the bookmark if it exists.
+    pub fn toggle_bookmark(&mut self, pkg: PkgKey, path: XString) {
+        let bookmark = Bookmark::new(pkg, path);
+        let path = bookmark.path.clone();
+        let added = self.bookmarks.toggle(bookmark, self.database.db());
+        info!(%path, added, "toggled the bookmark");
+    }
+
+    pub fn toggle_private_items(&mut self) {
+        self.document_private_items = !self.document_private_items;
+        info!(
+            "document private items for local pkgs: {}",
+            self.document_private_items
+        );
+    }
+
     pub fn respond_to_char(&mut self, ch: char) {
         match self.area.current {
             Panel::VersionFeatures => {
@@ -155,6 +407,7 @@ impl UI {
                     self.comfirm_features_and_compile_doc();
                 }
             }
+            Panel::BuildLog => (),
             _ => self.push_char(ch),
         };
         self.update_pkg_toml();
@@ -164,22 +417,37 @@ impl UI {
         self.database.receive_compiled_doc(info);
     }
 
+    pub fn receive_progress(&mut self, key: &PkgKey, line: XString) {
+        self.database.receive_progress(key, line);
+    }
+
+    pub fn receive_failure(&mut self, key: &PkgKey, err: String) {
+        self.database.receive_failure(key, err);
+    }
+
+    /// Switch among Database, Registry and Bookmarks panels, but skip empty ones.
     pub fn switch_panel(&mut self) {
-        if self.database.is_empty() {
-            self.area.current = Panel::LocalRegistry;
-            return;
-        }
-        match self.area.current {
-            Panel::Database => self.area.current = Panel::LocalRegistry,
-            Panel::LocalRegistry => self.area.current = Panel::Database,
-            Panel::VersionFeatures => self.ver_feat.switch_panel(),
+        let (db, bookmarks) = (!self.database.is_empty(), !self.bookmarks.is_empty());
+        self.area.current = match self.area.current {
+            Panel::Database => Panel::LocalRegistry,
+            Panel::LocalRegistry if bookmarks => Panel::Bookmarks,
+            Panel::LocalRegistry | Panel::Bookmarks if db => Panel::Database,
+            Panel::LocalRegistry | Panel::Bookmarks => Panel::LocalRegistry,
+            Panel::VersionFeatures => {
+                self.ver_feat.switch_panel();
+                return;
+            }
+            Panel::BuildLog => return,
         };
         self.update_pkg_toml();
     }
 
-    pub fn close_ver_feat(&mut self) {
-        if matches!(self.area.current, Panel::VersionFeatures) {
-            self.area.current = Panel::LocalRegistry;
+    pub fn close_popup(&mut self) {
+        self.search.close_input();
+        match self.area.current {
+            Panel::VersionFeatures => self.area.current = Panel::LocalRegistry,
+            Panel::BuildLog => self.area.current = Panel::Database,
+            _ => (),
         }
     }
 
@@ -189,17 +457,26 @@ impl UI {
         }
     }
 
-    pub fn get_loaded_doc(&self, key: &PkgKey) -> Option<CrateDoc> {
+    /// The loaded doc, docs of its direct dependencies and the uncached dependencies.
+    pub fn get_loaded_doc(&self, key: &PkgKey) -> Option<LoadedDocs> {
         self.database.get_loaded_doc(key)
     }
 
+    pub fn receive_dependencies(&mut self, deps: Dependencies) {
+        self.database.receive_dependencies(deps);
+    }
+
     /// the full screen area
     pub fn get_full_area(&self) -> Rect {
         self.area.full
     }
 
+    /// Downgrade a loaded doc in Database panel, or remove a bookmark in Bookmarks panel.
     pub fn downgrade(&mut self, y: Option<u16>) {
-        self.database.downgrade(y);
+        match self.area.current {
+            Panel::Bookmarks => self.bookmarks.remove(y, self.database.db()),
+            _ => self.database.downgrade(y),
+        }
     }
 
     /// Returns true for hinting Frame can switch to Page, because no mouse interaction in DashBoard.
@@ -224,6 +501,17 @@ impl UI {
                     return false;
                 }
 
+                if matches!(self.area.current, Panel::BuildLog) {
+                    let log = self.build_log.scroll_text();
+                    if log.area.contains(position.into()) {
+                        log.set_cursor(event.row.saturating_sub(log.area.y));
+                    } else if !self.build_log.contains(position) {
+                        // left click out of range will back to Database panel
+                        self.area.current = Panel::Database;
+                    }
+                    return false;
+                }
+
                 let registry = self.registry.scroll_text();
                 if registry.area.contains(position.into()) {
                     let y = registry.area.y;
@@ -239,6 +527,15 @@ impl UI {
                     db.set_cursor(event.row.saturating_sub(y));
                     self.area.current = Panel::Database;
                     self.update_pkg_toml();
+                    return false;
+                }
+
+                let bookmarks = self.bookmarks.scroll_text();
+                if bookmarks.area.contains(position.into()) {
+                    let y = bookmarks.area.y;
+                    bookmarks.set_cursor(event.row.saturating_sub(y));
+                    self.area.current = Panel::Bookmarks;
+                    self.update_pkg_toml();
                 }
             }
             MouseEventKind::Down(MouseButton::Right) => {
@@ -255,10 +552,23 @@ impl UI {
                             return false;
                         }
                     }
+                    Panel::Bookmarks => {
+                        let bookmarks = self.bookmarks.scroll_text();
+                        if bookmarks.area.contains(position.into()) {
+                            let y = bookmarks.area.y;
+                            bookmarks.set_cursor(event.row.saturating_sub(y));
+                            self.bookmarks.remove(Some(event.row), self.database.db());
+                            self.update_pkg_toml();
+                            return false;
+                        }
+                    }
                     Panel::VersionFeatures if !self.ver_feat.contains(position) => {
                         // right click out of range will back to LocalRegistry panel
                         self.area.current = Panel::LocalRegistry
                     }
+                    Panel::BuildLog if !self.build_log.contains(position) => {
+                        self.area.current = Panel::Database
+                    }
                     _ => (),
                 }
                 return true;
@@ -282,17 +592,26 @@ impl Widget for &mut UI {
     fn render(self, full: Rect, buf: &mut Buffer) {
         self.update_area(full);
 
-        let [db, reg] = match self.area.current {
-            Panel::Database => [true, false],
-            Panel::LocalRegistry => [false, true],
+        let [db, reg, bookmarks] = match self.area.current {
+            Panel::Database => [true, false, false],
+            Panel::LocalRegistry => [false, true, false],
+            Panel::Bookmarks => [false, false, true],
             Panel::VersionFeatures => {
                 self.ver_feat.render(buf);
                 return;
             }
+            Panel::BuildLog => {
+                self.build_log.render(buf);
+                return;
+            }
         };
-        self.search.render(buf);
+        self.search.render(buf, self.document_private_items);
+        if let Some(session) = &self.last_session {
+            self.search.render_session_hint(buf, session);
+        }
         self.database.render(buf, db);
         self.registry.render(buf, reg);
+        self.bookmarks.render(buf, bookmarks);
         self.pkg_toml.render(buf);
     }
 }
@@ -310,11 +629,14 @@ enum Panel {
     #[default]
     LocalRegistry,
     VersionFeatures,
+    Bookmarks,
+    /// The error popup of a failed doc in Database panel.
+    BuildLog,
 }
 
 impl Area {
-    /// returns borders for search, database and registry
-    fn update(&mut self, full: Rect) -> Option<[Surround; 4]> {
+    /// returns borders for pkg_toml, search, database, registry and bookmarks
+    fn update(&mut self, full: Rect) -> Option<[Surround; 5]> {
         if self.full == full {
             return None;
         }
@@ -330,9 +652,13 @@ impl Area {
         let block = Block::new().borders(Borders::ALL);
         let search = Surround::new(block.clone(), search);
         let half = Constraint::Percentage(50);
-        let [db, reg] = Layout::horizontal([half, half]).areas(db_reg);
+        let [db_bookmarks, reg] = Layout::horizontal([half, half]).areas(db_reg);
+        let [db, bookmarks] =
+            Layout::vertical([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(db_bookmarks);
         let database = Surround::new(block.clone().title(" From Database "), db);
+        let bookmarks = Surround::new(block.clone().title(" Bookmarks "), bookmarks);
         let registry = Surround::new(block.title(" From Local Registry Src Dir "), reg);
-        Some([pkg_toml, search, database, registry])
+        Some([pkg_toml, search, database, registry, bookmarks])
     }
 }
@@ -1,11 +1,12 @@
 use crate::{
-    color::BG_CURSOR_LINE,
+    color::theme,
     fuzzy::Fuzzy,
-    local_registry::{LocalRegistry, PkgInfo},
+    local_registry::{local_pkgs, LocalRegistry, PkgInfo},
     ui::{render_line, LineState, Scroll, Surround},
 };
 use ratatui::prelude::{Buffer, Rect};
-use term_rustdoc::util::xformat;
+use std::path::{Path, PathBuf};
+use term_rustdoc::util::{xformat, XString};
 
 #[derive(Default)]
 pub(super) struct PkgLists {
@@ -16,6 +17,10 @@ pub(super) struct PkgLists {
     /// Local pkgs with all versions, which are used in FeaturesUI to select
     /// a version and corresponding features.
     local_all_versions: LocalRegistry,
+    /// Dirs of pkgs added from local paths instead of registry.
+    local_paths: Vec<PathBuf>,
+    /// Pkgs selected to compile in a batch.
+    selected: Vec<PkgInfo>,
     fuzzy: Option<Fuzzy>,
 }
 
@@ -37,10 +42,30 @@ impl PkgLists {
             filter: (0..registry.len()).map(LocalPkgsIndex).collect(),
             local: registry,
             local_all_versions: all,
+            local_paths: Vec::new(),
+            selected: Vec::new(),
             fuzzy: Some(fuzzy),
         }
     }
 
+    fn add_local_pkgs(&mut self, pkgs: Vec<PkgInfo>) {
+        for pkg in &pkgs {
+            if !self.is_local(pkg) {
+                self.local_paths.push(pkg.path().to_owned());
+            }
+        }
+        self.local.insert_local_pkgs(pkgs);
+        self.force_all();
+    }
+
+    fn is_local(&self, pkg: &PkgInfo) -> bool {
+        self.local_paths.iter().any(|path| path == pkg.path())
+    }
+
+    fn is_selected(&self, pkg: &PkgInfo) -> bool {
+        self.selected.iter().any(|p| p.path() == pkg.path())
+    }
+
     /// Get all versions for a pkg, but in reverse order. (Latest is first)
     pub fn get_all_version(&self, name: &str) -> Vec<PkgInfo> {
         let all = &self.local_all_versions;
@@ -162,31 +187,48 @@ impl Registry {
         // render current selected pkg
         if current && text.get_line_of_current_cursor().is_some() {
             let row = text.area.y + text.cursor.y;
+            let bg = theme().cursor_line_bg();
             for col in x..text.area.width + x {
-                buf[(col, row)].set_bg(BG_CURSOR_LINE);
+                buf[(col, row)].set_bg(bg);
             }
         }
 
-        let mut start = text.start + 1;
-        for line in lines {
+        for (start, line) in (text.start + 1..).zip(lines) {
             let pkg = &pkgs[line.0];
             let [(name, style_name), (ver, style_ver)] = pkg.styled_name_ver();
             let num = xformat!("{start:02}. ");
+            let selected = if text.lines.is_selected(pkg) {
+                "✓ "
+            } else {
+                ""
+            };
+            let mark = if pkg.is_sysroot() {
+                " (sysroot)"
+            } else if text.lines.is_local(pkg) {
+                " (local)"
+            } else {
+                ""
+            };
             // render name and version, but with extra info and styles
             let line = [
                 (&*num, style_name),
+                (selected, theme().pkg_features),
                 (name, style_name),
                 (" v", style_ver),
                 (ver, style_ver),
+                (mark, style_ver),
             ];
             render_line(line, buf, x, y, width);
             y += 1;
-            start += 1;
         }
 
         // write the match result to the border bottom line
+        let selected = match self.inner.lines.selected.len() {
+            0 => XString::default(),
+            n => xformat!(" Selected {n} /"),
+        };
         let text = xformat!(
-            " Got {} / Total {} ",
+            "{selected} Got {} / Total {} ",
             self.inner.total_len(),
             self.inner.lines.local.len()
         );
@@ -226,6 +268,54 @@ impl Registry {
         .map(|idx| pkgs[idx.0].clone())
     }
 
+    /// Add pkgs from a local Cargo.toml or the dir containing it, and move the cursor to
+    /// the first of them.
+    pub fn add_local_path(&mut self, path: &Path) {
+        match local_pkgs(path) {
+            Ok(pkgs) => {
+                info!("Found {} local pkgs from {}", pkgs.len(), path.display());
+                self.inner.lines.add_local_pkgs(pkgs);
+                self.inner.start = 0;
+                self.inner.cursor.y = 0;
+            }
+            Err(err) => error!("{err}"),
+        }
+    }
+
+    /// Find a pkg by name and version. The first one shown in the panel is used if
+    /// no version is given, which is usually the latest.
+    pub fn find_pkg(&self, name: &str, ver: Option<&str>) -> Option<PkgInfo> {
+        let matches = |pkg: &PkgInfo| pkg.name() == name && ver.is_none_or(|v| pkg.ver() == v);
+        let lists = &self.inner.lines;
+        if let Some(pkg) = lists.local.iter().find(|pkg| matches(pkg)) {
+            return Some(pkg.clone());
+        }
+        lists.get_all_version(name).into_iter().find(matches)
+    }
+
+    /// Select the pkg under the cursor to compile in a batch, or unselect it.
+    pub fn toggle_select(&mut self) {
+        let Some(pkg) = self.get_pkg(None) else {
+            return;
+        };
+        let selected = &mut self.inner.lines.selected;
+        if let Some(idx) = selected.iter().position(|p| p.path() == pkg.path()) {
+            selected.remove(idx);
+        } else {
+            selected.push(pkg);
+        }
+    }
+
+    /// Pkgs selected to compile in a batch, and the selection is cleared.
+    pub fn take_selected(&mut self) -> Vec<PkgInfo> {
+        std::mem::take(&mut self.inner.lines.selected)
+    }
+
+    /// Pkgs from local paths rather than registry.
+    pub fn is_local(&self, pkg: &PkgInfo) -> bool {
+        self.inner.lines.is_local(pkg)
+    }
+
     pub fn get_current_pkg(&self) -> Option<(&str, &str)> {
         if let Some(idx) = self.inner.get_line_of_current_cursor().map(|id| id.0) {
             if let Some(pkg) = self.inner.lines.local.get(idx) {
@@ -1,5 +1,13 @@
-use crate::ui::Surround;
-use ratatui::prelude::{Buffer, Color, Rect, Style};
+use super::Panel;
+use crate::{
+    color::theme,
+    config::{keymap, Action, Scope},
+    database::Session,
+    ui::{tail_within_width, Surround},
+};
+use ratatui::prelude::{Buffer, Rect, Style};
+use std::path::PathBuf;
+use term_rustdoc::util::xformat;
 
 #[derive(Default)]
 pub(super) struct Search {
@@ -7,6 +15,19 @@ pub(super) struct Search {
     area: Rect,
     source: Source,
     border: Surround,
+    mode: Mode,
+}
+
+/// What the input is used for.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub(super) enum Mode {
+    /// A search pattern for pkgs.
+    #[default]
+    Search,
+    /// A local path to Cargo.toml or its dir.
+    LocalPath,
+    /// The note on the current bookmark.
+    Note,
 }
 
 #[derive(Clone, Copy, Default, Debug)]
@@ -23,9 +44,43 @@ impl Search {
         self.border = border;
     }
 
-    fn render_border(&self, buf: &mut Buffer) {
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Close the input for a local path or a note, and back to search.
+    pub fn close_input(&mut self) {
+        if self.mode != Mode::Search {
+            self.mode = Mode::Search;
+            self.input.clear();
+        }
+    }
+
+    fn render_border(&self, buf: &mut Buffer, private: bool) {
         self.border.render(buf);
         // render border title
+        match self.mode {
+            Mode::Search => (),
+            Mode::LocalPath => {
+                let private = if private { "on" } else { "off" };
+                let key = keymap().hint(Scope::DashBoard, Action::TogglePrivateItems);
+                let text = xformat!(
+                    " Add Local Cargo.toml Or Its Dir (private items: {private}, {key} to toggle) "
+                );
+                self.border.render_only_top_left_text(buf, &text, 0);
+                return;
+            }
+            Mode::Note => {
+                let keymap = keymap();
+                let text = xformat!(
+                    " Note On The Bookmark ({} to save, {} to cancel) ",
+                    keymap.hint(Scope::DashBoard, Action::Confirm),
+                    keymap.hint(Scope::DashBoard, Action::Close)
+                );
+                self.border.render_only_top_left_text(buf, &text, 0);
+                return;
+            }
+        }
         let text = match self.source {
             Source::LocalRegistry => " Search Package In Local Registry ",
             Source::DataBase => " Search Package In Database ",
@@ -34,22 +89,25 @@ impl Search {
         self.border.render_only_top_left_text(buf, text, 0);
     }
 
-    pub fn render(&self, buf: &mut Buffer) {
-        self.render_border(buf);
+    /// Offer to restore the last session at the bottom of the border.
+    pub fn render_session_hint(&self, buf: &mut Buffer, session: &Session) {
+        let path = session.path.as_deref().unwrap_or_default();
+        let key = keymap().hint(Scope::DashBoard, Action::RestoreSession);
+        let text = xformat!(" {key} to restore last session: {} {path} ", session.pkg);
+        self.border.render_only_bottom_right_text(buf, &text);
+    }
+
+    pub fn render(&self, buf: &mut Buffer, private: bool) {
+        self.render_border(buf, private);
 
         let Rect { x, y, width, .. } = self.area;
         let width = width.saturating_sub(1) as usize;
-        let mut text = self.input.as_str();
         // show end half if the input exceeds the width
-        text = &text[text.len().saturating_sub(width)..];
+        let text = tail_within_width(&self.input, width);
         let (x, _) = buf.set_stringn(x, y, text, width, Style::new());
 
         // the last width is used as cursor
-        let cursor = Style {
-            bg: Some(Color::Green),
-            ..Default::default()
-        };
-        buf.set_stringn(x, y, " ", 1, cursor);
+        buf.set_stringn(x, y, " ", 1, theme().cursor);
     }
 }
 
@@ -62,6 +120,9 @@ impl super::UI {
 
     // update fuzzy matcher
     fn update_search(&mut self) {
+        if self.search.mode != Mode::Search {
+            return;
+        }
         match self.search.source {
             Source::LocalRegistry => self.registry.update_search(&self.search.input),
             Source::DataBase => self.database.update_search(&self.search.input),
@@ -92,6 +153,50 @@ impl super::UI {
         };
     }
 
+    /// Switch the input between searching pkgs and adding a local path.
+    pub fn toggle_path_input(&mut self) {
+        if self.search.mode == Mode::LocalPath {
+            self.search.close_input();
+        } else {
+            self.clear_input();
+            self.search.mode = Mode::LocalPath;
+        }
+    }
+
+    /// Edit the note on the bookmark under the cursor in Bookmarks panel.
+    pub fn toggle_note_input(&mut self) {
+        if self.search.mode == Mode::Note {
+            self.search.close_input();
+            return;
+        }
+        if !matches!(self.area.current, Panel::Bookmarks) {
+            return;
+        }
+        if let Some(bookmark) = self.bookmarks.get(None) {
+            let note = bookmark.note.clone();
+            self.clear_input();
+            self.search.input = note;
+            self.search.mode = Mode::Note;
+        }
+    }
+
+    pub(super) fn confirm_note(&mut self) {
+        self.bookmarks
+            .set_note(&self.search.input, self.database.db());
+        self.search.close_input();
+    }
+
+    pub(super) fn confirm_local_path(&mut self) {
+        let input = self.search.input.trim();
+        let path = match input.strip_prefix("~/").zip(dirs::home_dir()) {
+            Some((rest, home)) => home.join(rest),
+            None => PathBuf::from(input),
+        };
+        self.search.close_input();
+        self.registry.add_local_path(&path);
+        self.area.current = Panel::LocalRegistry;
+    }
+
     pub fn switch_search_source(&mut self) {
         self.search.source = match self.search.source {
             Source::LocalRegistry => Source::DataBase,
@@ -3,12 +3,12 @@
 //! Note: if the line is too long, you should move the cursor to see exceeding texts.
 
 use crate::{
-    color::{BG_CURSOR, PKG_TOML},
-    database::Features,
+    color::theme,
+    database::{Features, PkgKey},
     ui::{render_line, Surround},
 };
 use ratatui::{
-    prelude::{Alignment, Buffer, Color, Constraint, Layout, Line, Rect},
+    prelude::{Alignment, Buffer, Constraint, Layout, Line, Rect},
     widgets::{Block, Borders},
 };
 use std::fmt::Write;
@@ -38,7 +38,14 @@ pub fn split_for_pkg_toml(area: Rect) -> [Rect; 2] {
 }
 
 impl PkgToml {
-    pub fn update_toml(&mut self, name: &str, ver: &str, features: &Features) {
+    /// The target is appended as a comment, because it's not specified in a dependency.
+    pub fn update_toml(
+        &mut self,
+        name: &str,
+        ver: &str,
+        features: &Features,
+        target: Option<&str>,
+    ) {
         self.toml.clear();
         let buf = &mut self.toml;
         let _ = match features {
@@ -62,9 +69,16 @@ impl PkgToml {
                 write!(buf,"{name} = {{ version = {ver:?}, features = {feats:?}, default-features = false }}")
             }
         };
+        if let Some(target) = target {
+            let _ = write!(buf, " # --target {target}");
+        }
         self.toml_width = self.toml.width() as u16;
     }
 
+    pub fn update_toml_for_key(&mut self, pkg: &PkgKey) {
+        self.update_toml(pkg.name(), pkg.ver_str(), pkg.features(), pkg.target());
+    }
+
     pub fn set_area(&mut self, border: Surround) {
         self.inner = border.inner();
         self.border = border;
@@ -80,12 +94,17 @@ impl PkgToml {
         self.border.render(buf);
 
         let Rect { x, y, width, .. } = self.inner;
-        render_line(Some((&*self.toml, PKG_TOML)), buf, x, y, width as usize);
+        render_line(
+            Some((&*self.toml, theme().pkg_toml)),
+            buf,
+            x,
+            y,
+            width as usize,
+        );
 
         if self.toml_width > width {
             let cell = &mut buf[(width.saturating_sub(1) + x, y)];
-            cell.bg = BG_CURSOR;
-            cell.fg = Color::White;
+            cell.set_style(theme().cursor);
         }
     }
 }
@@ -1,7 +1,7 @@
 use super::ver_feat_toml;
 use crate::{
-    color::{BG_CURSOR_LINE, FG_CURSOR_LINE},
-    database::FeaturesUI,
+    color::theme,
+    database::{has_installed_targets, targets, FeaturesUI, PkgWithFeatures, Target, TargetKind},
     local_registry::{PkgInfo, PkgNameVersion},
     ui::{render_line, LineState, Scroll, Scrollable, Surround},
 };
@@ -9,14 +9,16 @@ use ratatui::{
     prelude::{Buffer, Constraint, Layout, Modifier, Rect, Style},
     widgets::{Block, Borders},
 };
+use term_rustdoc::util::XString;
 
 #[derive(Default)]
 pub struct VersionFeatures {
     features: FeaturesUI,
     versions: Versions,
+    targets: Targets,
     pkg_toml: ver_feat_toml::PkgToml,
     current: Panel,
-    /// Area covering features, versions and pkg_toml.
+    /// Area covering features, versions, targets and pkg_toml.
     area: Rect,
 }
 
@@ -25,15 +27,20 @@ enum Panel {
     #[default]
     Features,
     Versions,
+    Targets,
 }
 
-fn split_ver_feat(outer: Rect, ver_width: u16) -> [Rect; 2] {
-    if ver_width == 0 {
-        // don't show versions if zero width
-        [Rect::default(), outer]
-    } else {
-        Layout::horizontal([Constraint::Length(ver_width), Constraint::Min(0)]).areas(outer)
-    }
+/// Returns [Versions, Features, Targets] areas in horizontal split.
+fn split_ver_feat_target(outer: Rect, ver_width: u16, target_width: u16) -> [Rect; 3] {
+    let [ver, feat, target] = Layout::horizontal([
+        Constraint::Length(ver_width),
+        Constraint::Min(0),
+        Constraint::Length(target_width),
+    ])
+    .areas(outer);
+    // don't show versions if zero width
+    let ver = if ver_width == 0 { Rect::default() } else { ver };
+    [ver, feat, target]
 }
 
 impl VersionFeatures {
@@ -49,11 +56,14 @@ impl VersionFeatures {
         let [remain, pkg_toml_area] = ver_feat_toml::split_for_pkg_toml(outer);
         let mut pkg_toml = ver_feat_toml::PkgToml::default();
         pkg_toml.set_area(ver_feat_toml::surround(pkg_toml_area));
-        pkg_toml.update_toml(pkg_info.name(), pkg_info.ver(), &Default::default());
-        let [ver, feat] = split_ver_feat(remain, ver_width);
+        pkg_toml.update_toml(pkg_info.name(), pkg_info.ver(), &Default::default(), None);
+        let all_targets = targets();
+        let target_width = Targets::width(all_targets);
+        let [ver, feat, target] = split_ver_feat_target(remain, ver_width, target_width);
         VersionFeatures {
             features: FeaturesUI::new(pkg_info, feat),
             versions: Versions::new(all_verions, ver_width, ver),
+            targets: Targets::new(all_targets, target_width, target),
             pkg_toml,
             current: Panel::Features,
             area: outer,
@@ -62,7 +72,8 @@ impl VersionFeatures {
 
     pub fn switch_panel(&mut self) {
         self.current = match self.current {
-            Panel::Features => Panel::Versions,
+            Panel::Features => Panel::Targets,
+            Panel::Targets => Panel::Versions,
             Panel::Versions => Panel::Features,
         };
     }
@@ -70,10 +81,18 @@ impl VersionFeatures {
     pub fn update_pkg_toml(&mut self) {
         if let Some(a @ (name, ver, feat)) = self.features.get_current_pkg() {
             info!(?a);
-            self.pkg_toml.update_toml(name, ver, feat);
+            let target = self.targets.selected().map(|t| &*t.triple);
+            self.pkg_toml.update_toml(name, ver, feat, target);
         }
     }
 
+    /// The pkg with selected version, features and target.
+    pub fn pkg_with_features(&mut self) -> Option<PkgWithFeatures> {
+        let mut pkg = self.features.pkg_with_features()?;
+        pkg.target = self.targets.selected().and_then(Target::to_option);
+        Some(pkg)
+    }
+
     // pub fn scroll_text(&mut self) -> &mut dyn Scrollable {
     //     match self.current {
     //         Panel::Features => self.features.scroll_text(),
@@ -99,6 +118,11 @@ impl VersionFeatures {
                 }
                 self.current = Panel::Versions;
             }
+            let targets = &mut self.targets.inner;
+            if targets.area.contains(position.into()) {
+                targets.set_cursor(y.saturating_sub(targets.area.y));
+                self.current = Panel::Targets;
+            }
         }
         self.update_pkg_toml();
     }
@@ -114,9 +138,12 @@ impl VersionFeatures {
         }
     }
 
-    /// Skip selection popup when features requirements are met and single version.
+    /// Skip selection popup when features requirements are met, single version and
+    /// no target is installed other than the host.
     pub fn skip_selection(&self) -> bool {
-        self.versions.inner.total_len() == 1 && self.features.skip_selection()
+        self.versions.inner.total_len() == 1
+            && self.features.skip_selection()
+            && !has_installed_targets()
     }
 
     pub fn update_area(&mut self, outer: Rect) {
@@ -125,18 +152,25 @@ impl VersionFeatures {
         }
         let [outer, pkg_toml] = ver_feat_toml::split_for_pkg_toml(outer);
         self.pkg_toml.update_area(pkg_toml);
-        let [ver, feat] = split_ver_feat(outer, self.versions.inner.lines.max_width);
+        let [ver, feat, target] = split_ver_feat_target(
+            outer,
+            self.versions.inner.lines.max_width,
+            self.targets.inner.lines.max_width,
+        );
         self.features.update_area(feat);
         self.versions.update_area(ver);
+        self.targets.update_area(target);
     }
 
     pub fn render(&self, buf: &mut Buffer) {
-        let (feat, ver) = match self.current {
-            Panel::Features => (true, false),
-            Panel::Versions => (false, true),
+        let (feat, ver, target) = match self.current {
+            Panel::Features => (true, false, false),
+            Panel::Versions => (false, true, false),
+            Panel::Targets => (false, false, true),
         };
         self.features.render(buf, feat);
         self.versions.render(buf, ver);
+        self.targets.render(buf, target);
         self.pkg_toml.render(buf);
     }
 
@@ -150,6 +184,10 @@ impl VersionFeatures {
                     self.update_pkg_toml();
                 }
             }
+            Panel::Targets => {
+                f(&mut self.targets.inner);
+                self.update_pkg_toml();
+            }
         }
     }
 }
@@ -246,12 +284,13 @@ impl Versions {
         }
         if self.inner.get_line_of_current_cursor().is_some() {
             let current = self.inner.area.y + self.inner.cursor.y;
+            let theme = theme();
             for w in 0..self.inner.area.width {
                 let cell = &mut buf[(x + w, current)];
                 if current_line {
-                    cell.bg = BG_CURSOR_LINE;
+                    cell.bg = theme.cursor_line_bg();
                 }
-                cell.fg = FG_CURSOR_LINE;
+                cell.fg = theme.cursor_line_fg();
                 cell.modifier = Modifier::BOLD;
             }
         }
@@ -284,3 +323,101 @@ impl LineState for PkgInfo {
         state.name_ver() == [self.name(), self.ver()]
     }
 }
+
+/// The first target is the host, and the selected one is passed to `--target`.
+#[derive(Default)]
+struct Targets {
+    inner: Scroll<TargetsInner>,
+    border: Surround,
+}
+
+const HOST: &str = " (host)";
+
+impl Targets {
+    fn new(all: &'static [Target], max_width: u16, area: Rect) -> Self {
+        let border = Surround::new(Block::new().title("Target").borders(Borders::ALL), area);
+        Self {
+            inner: Scroll {
+                lines: TargetsInner { all, max_width },
+                area: border.inner(),
+                ..Default::default()
+            },
+            border,
+        }
+    }
+
+    /// The width including borders.
+    fn width(all: &[Target]) -> u16 {
+        let max = all.iter().map(|t| t.triple.len()).max().unwrap_or(0);
+        (max + HOST.len() + 2) as u16
+    }
+
+    fn selected(&self) -> Option<&Target> {
+        self.inner.get_line_of_current_cursor()
+    }
+
+    fn update_area(&mut self, area: Rect) {
+        if let Some(inner) = self.border.update_area(area) {
+            self.inner.area = inner;
+        }
+    }
+
+    fn render(&self, buf: &mut Buffer, current_line: bool) {
+        self.border.render(buf);
+
+        let width = self.inner.area.width as usize;
+        let Rect { x, mut y, .. } = self.inner.area;
+        let theme = theme();
+        if let Some(lines) = self.inner.visible_lines() {
+            for target in lines {
+                let triple = &*target.triple;
+                // targets without std installed are dimmed
+                let (style, host) = match target.kind {
+                    TargetKind::Host => (Style::new(), HOST),
+                    TargetKind::Installed => (Style::new(), ""),
+                    TargetKind::NotInstalled => (theme.pkg_version, ""),
+                };
+                let line = [(triple, style), (host, theme.pkg_version)];
+                render_line(line, buf, x, y, width);
+                y += 1;
+            }
+        }
+        if self.inner.get_line_of_current_cursor().is_some() {
+            let current = self.inner.area.y + self.inner.cursor.y;
+            for w in 0..self.inner.area.width {
+                let cell = &mut buf[(x + w, current)];
+                if current_line {
+                    cell.bg = theme.cursor_line_bg();
+                }
+                cell.fg = theme.cursor_line_fg();
+                cell.modifier = Modifier::BOLD;
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct TargetsInner {
+    all: &'static [Target],
+    max_width: u16,
+}
+
+impl std::ops::Deref for TargetsInner {
+    type Target = [Target];
+
+    fn deref(&self) -> &Self::Target {
+        self.all
+    }
+}
+
+impl LineState for Target {
+    type State = XString;
+
+    fn state(&self) -> Self::State {
+        self.triple.clone()
+    }
+
+    fn is_identical(&self, state: &Self::State) -> bool {
+        self.triple == *state
+    }
+}
@@ -0,0 +1,80 @@
+use super::{
+    cache_info::{remove_from_db, write_to_db},
+    pkg_key::PkgKey,
+    DataBase,
+};
+use crate::{Result, WrapErr};
+use term_rustdoc::util::XString;
+
+/// Bookmarks are stored in `index.db` alongside CachedDocInfo.
+const TABLE: &str = "Bookmark";
+
+type Key = (PkgKey, String);
+
+/// An item in a doc marked by the user, with an optional note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    pub pkg: PkgKey,
+    /// Full path of the item, like `crate::module::Item`.
+    pub path: XString,
+    pub note: String,
+}
+
+impl Bookmark {
+    pub fn new(pkg: PkgKey, path: XString) -> Self {
+        Bookmark {
+            pkg,
+            path,
+            note: String::new(),
+        }
+    }
+
+    fn key(&self) -> Key {
+        (self.pkg.clone(), self.path.to_string())
+    }
+}
+
+impl DataBase {
+    pub fn all_bookmarks(&self) -> Result<Vec<Bookmark>> {
+        use redb::ReadableTable;
+        let db = self.index_db()?;
+        let table = redb::TableDefinition::<Key, String>::new(TABLE);
+        let read_txn = db.begin_read()?;
+        let read_only_table = match read_txn.open_table(table) {
+            Ok(tab) => tab,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+            err => err.wrap_err_with(|| "Can't read Bookmark table from index.db")?,
+        };
+        let bookmarks: Vec<_> = read_only_table
+            .iter()?
+            .filter_map(|res| match res {
+                Ok((k, v)) => {
+                    let (pkg, path) = k.value();
+                    Some(Bookmark {
+                        pkg,
+                        path: path.into(),
+                        note: v.value(),
+                    })
+                }
+                Err(err) => {
+                    error!("Failed to read a bookmark in index.db:\n{err}");
+                    None
+                }
+            })
+            .collect();
+        info!("Succeefully read {} bookmarks", bookmarks.len());
+        Ok(bookmarks)
+    }
+
+    /// Add the bookmark, or update the note if the item is bookmarked.
+    pub fn save_bookmark(&self, bookmark: &Bookmark) -> Result<()> {
+        let db = self.index_db()?;
+        write_to_db::<Key, String>(&db, TABLE, &bookmark.key(), &bookmark.note)
+    }
+
+    pub fn remove_bookmark(&self, bookmark: &Bookmark) -> Result<()> {
+        let db = self.index_db()?;
+        remove_from_db::<Key, String>(&db, TABLE, &bookmark.key())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,322 @@
+//! Compile docs via `cargo rustdoc` in the background.
+//!
+//! The cargo process is spawned here instead of by `rustdoc_json::Builder`, because
+//! we need the child process to cancel the compilation, and its stderr to show progress.
+//!
+//! Compilations are queued in a thread pool, whose size is `build.jobs` in config file.
+//! They share a persistent target dir under the db dir, so dependencies built before are
+//! reused. Cargo locks the target dir, thus concurrent compilations take turns in it.
+//! The target dir can be removed by `term-rustdoc clean-target` to free disk space.
+
+use super::{features::Features, CachedDocInfo, PkgKey};
+use crate::{
+    config::config,
+    err,
+    event::{Event, Sender},
+    local_registry::PkgInfo,
+    Result, WrapErr,
+};
+use std::{
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+};
+use term_rustdoc::util::XString;
+
+/// Pkg info and local dir that are used to build the doc.
+#[derive(Clone, Default)]
+pub struct PkgWithFeatures {
+    pub features: Features,
+    pub info: PkgInfo,
+    /// Pass `--document-private-items` to rustdoc. Usually used for local pkgs.
+    pub document_private_items: bool,
+    /// Pass `--target` to cargo. None means the host.
+    pub target: Option<XString>,
+}
+
+impl PkgWithFeatures {
+    /// Default features for the host, which are used to compile pkgs in a batch.
+    pub fn with_default_features(info: PkgInfo) -> Self {
+        PkgWithFeatures {
+            info,
+            ..Default::default()
+        }
+    }
+}
+
+/// A doc being compiled, which can be cancelled by killing the cargo process.
+///
+/// The pkg is kept to retry the compilation once it fails.
+#[derive(Clone)]
+pub struct BuildHandle {
+    key: PkgKey,
+    pkg: PkgWithFeatures,
+    /// None before cargo is spawned, or after it exits.
+    child: Arc<Mutex<Option<Child>>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl BuildHandle {
+    fn new(pkg: PkgWithFeatures) -> Self {
+        let key = PkgKey::from_pkg(&pkg);
+        BuildHandle {
+            key,
+            pkg,
+            child: Arc::default(),
+            cancelled: Arc::default(),
+        }
+    }
+
+    /// A handle that is never built for temporary use.
+    pub fn empty_state() -> Self {
+        BuildHandle::new(PkgWithFeatures::default())
+    }
+
+    pub fn key(&self) -> &PkgKey {
+        &self.key
+    }
+
+    pub fn pkg(&self) -> &PkgWithFeatures {
+        &self.pkg
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+        if let Some(child) = self.child.lock().unwrap().as_mut() {
+            kill(child);
+        }
+        info!(?self.key, "the compilation is cancelled");
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Kill the child at once if the compilation is cancelled before cargo is spawned.
+    fn set_child(&self, mut child: Child) {
+        if self.is_cancelled() {
+            kill(&mut child);
+        }
+        *self.child.lock().unwrap() = Some(child);
+    }
+
+    fn wait(&self) -> Result<ExitStatus> {
+        let child = self.child.lock().unwrap().take();
+        let mut child = child.ok_or_else(|| err!("cargo is not spawned"))?;
+        Ok(child.wait()?)
+    }
+}
+
+/// Kill cargo with rustc and rustdoc processes spawned by it.
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // cargo is the leader of its process group
+        let group = format!("-{}", child.id());
+        let killed = Command::new("kill")
+            .args(["-s", "TERM", "--", &group])
+            .status();
+        if killed.is_ok_and(|status| status.success()) {
+            return;
+        }
+    }
+    if let Err(err) = child.kill() {
+        error!("Failed to kill cargo:\n{err}");
+    }
+}
+
+static POOL: OnceLock<rayon::ThreadPool> = OnceLock::new();
+
+/// The pool to run compilations in order, with at most `build.jobs` ones at the same time.
+fn pool() -> &'static rayon::ThreadPool {
+    POOL.get_or_init(|| {
+        let jobs = config().jobs;
+        info!("at most {jobs} docs are compiled at the same time");
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .thread_name(|idx| format!("build-{idx}"))
+            .build()
+            .expect("Failed to spawn threads to compile docs")
+    })
+}
+
+/// Queue the doc to compile in the background. Cargo's status lines are sent as
+/// `DocProgress` events, and `DocCompiled` or `DocFailed` is sent at the end.
+/// The error text of `DocFailed` contains the full stderr from cargo.
+///
+/// Nothing is sent if the compilation is cancelled, even before it starts.
+pub fn build(sender: Sender, db_dir: PathBuf, pkg: PkgWithFeatures) -> BuildHandle {
+    let handle = BuildHandle::new(pkg.clone());
+    let building = handle.clone();
+    pool().spawn(move || {
+        if building.is_cancelled() {
+            return;
+        }
+        let key = building.key();
+        let progress = |line: &str| {
+            let event = Event::DocProgress(Box::new(key.clone()), line.into());
+            if let Err(err) = sender.send(event) {
+                error!("Failed to send `DocProgress` event:\n{err}");
+            }
+        };
+        let event = match compile(db_dir, pkg, &building, progress) {
+            Ok(cache_info) => Event::DocCompiled(Box::new(cache_info)),
+            Err(_) if building.is_cancelled() => return,
+            Err(err) => {
+                error!("{err:?}");
+                // each error in the chain is on its own lines
+                let text = err.chain().map(|e| e.to_string()).collect::<Vec<_>>();
+                Event::DocFailed(Box::new(key.clone()), text.join("\n"))
+            }
+        };
+        if let Err(err) = sender.send(event) {
+            error!("Failed to send the event when the compilation ends:\n{err}");
+        }
+    });
+    handle
+}
+
+/// Compile the doc and save it into db in current thread.
+///
+/// For sysroot pkgs, the json doc from rust-docs-json component is saved instead.
+pub fn build_in_place(db_dir: PathBuf, pkg: PkgWithFeatures) -> Result<CachedDocInfo> {
+    compile(db_dir, pkg.clone(), &BuildHandle::new(pkg), |_| ())
+}
+
+fn compile(
+    db_dir: PathBuf,
+    pkg: PkgWithFeatures,
+    handle: &BuildHandle,
+    progress: impl FnMut(&str),
+) -> Result<CachedDocInfo> {
+    let target_dir = target_dir(&db_dir);
+    let mut cache_info = CachedDocInfo::new(PkgKey::from_pkg(&pkg), db_dir);
+    if pkg.info.is_sysroot() {
+        // json doc is shipped with the rust-docs-json component
+        let json_path = pkg.info.path().to_owned();
+        info!(?cache_info.pkg, "begin to load the doc from {}", json_path.display());
+        cache_info.meta_mut().set_finished_duration();
+        cache_info.save_doc(&json_path, pkg.info)?;
+        return Ok(cache_info);
+    }
+    let cargo_toml = pkg.info.path().join("Cargo.toml");
+    info!(?cache_info.pkg, "begin to compile the doc under {}", target_dir.display());
+    let json_path = run_cargo_rustdoc(&pkg, &cargo_toml, &target_dir, handle, progress)
+        .wrap_err_with(|| format!("Failed to compile {}", cargo_toml.display()))?;
+    let meta = cache_info.meta_mut();
+    meta.set_finished_duration();
+    let duration = meta.duration_as_secs();
+    info!(?cache_info.pkg, ?json_path, "succeefully compiled the doc in {duration:.2}s");
+    cache_info.save_doc(&json_path, pkg.info)?;
+    Ok(cache_info)
+}
+
+/// `db_dir/target` shared by all compilations.
+fn target_dir(db_dir: &Path) -> PathBuf {
+    db_dir.join("target")
+}
+
+/// Remove the shared target dir. Returns the dir if it exists and is removed.
+pub fn clean_target_dir(db_dir: &Path) -> Result<Option<PathBuf>> {
+    let dir = target_dir(db_dir);
+    if !dir.exists() {
+        return Ok(None);
+    }
+    std::fs::remove_dir_all(&dir)
+        .wrap_err_with(|| format!("Failed to remove {}", dir.display()))?;
+    info!("{} is removed", dir.display());
+    Ok(Some(dir))
+}
+
+/// Returns the path of the generated json doc, or the full stderr as the error.
+fn run_cargo_rustdoc(
+    pkg: &PkgWithFeatures,
+    cargo_toml: &Path,
+    target_dir: &Path,
+    handle: &BuildHandle,
+    mut progress: impl FnMut(&str),
+) -> Result<PathBuf> {
+    let mut cmd = cargo_rustdoc_command(pkg, cargo_toml, target_dir);
+    info!("Running {cmd:?}");
+    let mut child = cmd.spawn().wrap_err("Failed to run cargo")?;
+    let stderr = child.stderr.take();
+    handle.set_child(child);
+
+    let mut log = String::new();
+    let lines = stderr.into_iter().flat_map(|s| BufReader::new(s).lines());
+    for line in lines.map_while(|line| line.ok()) {
+        let trimmed = line.trim();
+        if line.starts_with(' ') && trimmed.starts_with(char::is_uppercase) {
+            // status lines like `   Compiling name v0.1.0` are right aligned
+            progress(trimmed);
+        }
+        log.push_str(&line);
+        log.push('\n');
+    }
+
+    let status = handle.wait()?;
+    if handle.is_cancelled() {
+        return Err(err!("The compilation is cancelled"));
+    }
+    if !status.success() {
+        let log = log.trim_end();
+        return Err(if log.is_empty() {
+            err!("cargo exited with {status}")
+        } else {
+            err!("{log}")
+        });
+    }
+    json_path(cargo_toml, target_dir, pkg.target.as_deref())
+}
+
+/// `cargo +nightly rustdoc --lib ... -- -Z unstable-options --output-format json`
+fn cargo_rustdoc_command(pkg: &PkgWithFeatures, cargo_toml: &Path, target_dir: &Path) -> Command {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["+nightly", "rustdoc", "--lib", "--color", "never"])
+        .arg("--manifest-path")
+        .arg(cargo_toml)
+        .arg("--target-dir")
+        .arg(target_dir);
+    if let Some(target) = &pkg.target {
+        cmd.args(["--target", target]);
+    }
+    let join = |features: &[XString]| features.join(",");
+    match &pkg.features {
+        Features::Default => &mut cmd,
+        Features::All => cmd.arg("--all-features"),
+        Features::DefaultPlus(f) => cmd.args(["--features", &join(f)]),
+        Features::NoDefault => cmd.arg("--no-default-features"),
+        Features::NoDefaultPlus(f) => cmd
+            .arg("--no-default-features")
+            .args(["--features", &join(f)]),
+    };
+    cmd.args(["--", "-Z", "unstable-options", "--output-format", "json"])
+        .args(["--cap-lints", "warn"]);
+    if pkg.document_private_items {
+        cmd.arg("--document-private-items");
+    }
+    cmd.stdout(Stdio::null()).stderr(Stdio::piped());
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+    cmd
+}
+
+/// `target_dir/[target/]doc/lib_name.json`
+fn json_path(cargo_toml: &Path, target_dir: &Path, target: Option<&str>) -> Result<PathBuf> {
+    let manifest = cargo_toml::Manifest::from_path(cargo_toml)?;
+    let name = manifest
+        .lib
+        .and_then(|lib| lib.name)
+        .or_else(|| manifest.package.map(|p| p.name))
+        .ok_or_else(|| err!("No lib or package name in {}", cargo_toml.display()))?;
+    let mut path = target_dir.to_owned();
+    path.extend(target);
+    path.push("doc");
+    path.push(name.replace('-', "_"));
+    path.set_extension("json");
+    Ok(path)
+}
@@ -1,5 +1,4 @@
 use super::{
-    features::Features,
     pkg_key::PkgKey,
     util::{decode, encode},
     DocMeta, PkgWithFeatures,
@@ -7,16 +6,17 @@ use super::{
 use crate::{
     database::util,
     err,
-    local_registry::{PkgInfo, PkgNameVersion},
+    local_registry::{LegacyPkgInfo, PkgInfo, PkgNameVersion},
     Result, WrapErr,
 };
+use semver::VersionReq;
 use serde::{Deserialize, Serialize};
 use std::{
     fs,
     path::{Path, PathBuf},
     time::{Instant, SystemTime},
 };
-use term_rustdoc::tree::CrateDoc;
+use term_rustdoc::{tree::CrateDoc, util::XString};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct CachedDocInfo {
@@ -34,18 +34,18 @@ impl CachedDocInfo {
         CachedDocInfo {
             pkg,
             db_file: db_dir,
-            meta: DocMeta::new(),
+            meta: DocMeta::new(None),
         }
     }
 
-    pub fn new(name_ver: PkgNameVersion, features: Features, mut db_dir: PathBuf) -> Self {
-        let fname = name_ver.doc_db_file_name();
+    pub fn new(pkg: PkgKey, mut db_dir: PathBuf) -> Self {
+        let fname = pkg.name_ver().doc_db_file_name();
         db_dir.push(&*fname);
-        let pkg = PkgKey::new(name_ver, features);
+        let meta = DocMeta::new(pkg.target());
         CachedDocInfo {
             pkg,
             db_file: db_dir,
-            meta: DocMeta::new(),
+            meta,
         }
     }
 
@@ -54,7 +54,9 @@ impl CachedDocInfo {
         debug!(?self.pkg, "Start to load");
         let db = redb::Database::open(&self.db_file)?;
         let bytes = read_from_doc_db::<PkgKey, Vec<u8>>(&db, "host-parsed", &self.pkg)?;
-        let doc = util::decode_with_xz(&bytes)?;
+        let doc: CrateDoc = util::decode_with_xz(&bytes)?;
+        // the index isn't cached, so build it now rather than at the first query
+        _ = doc.type_usages();
         info!(?self.pkg, "Loaded in {:.2}s", now.elapsed().as_secs_f32());
         Ok(doc)
     }
@@ -64,19 +66,29 @@ impl CachedDocInfo {
     /// FIXME: PkgInfo may contain invalid data because registry caches can be cleaned up.
     /// We should put forward a better way to determin if the broken doc can be recompiled.
     pub fn load_pkg_info_features(&self) -> Result<PkgWithFeatures> {
-        let db = redb::Database::create(&self.db_file)?;
-
-        // read PkgInfo from db
-        let bytes = read_from_doc_db::<PkgKey, Vec<u8>>(&db, "host-pkg-info", &self.pkg)?;
-        let info = decode::<PkgInfo>(&bytes)?;
-        info!(?self.pkg, "PkgInfo is succeefully read from db file `{}`", self.db_file.display());
-
+        let info = self.load_pkg_info()?;
         Ok(PkgWithFeatures {
             features: self.pkg.features().clone(),
             info,
+            document_private_items: self.pkg.private(),
+            target: self.pkg.target().map(XString::from),
         })
     }
 
+    /// Crate names of direct dependencies of the pkg and the versions it uses.
+    pub fn dependencies(&self) -> Result<Vec<(XString, VersionReq)>> {
+        Ok(self.load_pkg_info()?.dependencies())
+    }
+
+    fn load_pkg_info(&self) -> Result<PkgInfo> {
+        let db = redb::Database::create(&self.db_file)?;
+        let bytes = read_from_doc_db::<PkgKey, Vec<u8>>(&db, "host-pkg-info", &self.pkg)?;
+        let info = decode::<PkgInfo>(&bytes)
+            .or_else(|_| decode::<LegacyPkgInfo>(&bytes).map(PkgInfo::from))?;
+        info!(?self.pkg, "PkgInfo is succeefully read from db file `{}`", self.db_file.display());
+        Ok(info)
+    }
+
     pub fn save_doc(&self, json_path: &Path, pkg_info: PkgInfo) -> Result<()> {
         let file = fs::File::open(json_path).wrap_err_with(|| {
             format!(
@@ -124,6 +136,29 @@ impl CachedDocInfo {
         Ok(())
     }
 
+    /// Remove the doc from `index.db` and its `pkg-version.db`.
+    ///
+    /// NOTE: docs with different features share the same `pkg-version.db`, so the
+    /// db file is deleted only when no doc is left in it.
+    pub fn remove(&self) -> Result<()> {
+        let index = redb::Database::create(self.db_file.with_file_name("index.db"))?;
+        remove_from_db::<PkgKey, CachedDocInfo>(&index, "CachedDocInfo", &self.pkg)?;
+        if self.db_file.exists() {
+            let db = redb::Database::create(&self.db_file)?;
+            let mut empty = true;
+            for name in ["host-pkg-info", "host-json", "host-parsed"] {
+                empty &= remove_from_db::<PkgKey, Vec<u8>>(&db, name, &self.pkg)?;
+            }
+            drop(db);
+            if empty {
+                fs::remove_file(&self.db_file)?;
+                info!(?self.pkg, "{} is deleted", self.db_file.display());
+            }
+        }
+        info!(?self.pkg, "the doc is removed from db");
+        Ok(())
+    }
+
     pub(super) fn meta_mut(&mut self) -> &mut DocMeta {
         &mut self.meta
     }
@@ -133,7 +168,7 @@ impl CachedDocInfo {
     }
 }
 
-fn write_to_db<K, V>(
+pub(super) fn write_to_db<K, V>(
     db: &redb::Database,
     name: &str,
     key: &K::SelfType<'_>,
@@ -153,6 +188,32 @@ where
     Ok(())
 }
 
+/// Returns true if the table is empty after removal.
+pub(super) fn remove_from_db<K, V>(
+    db: &redb::Database,
+    name: &str,
+    key: &K::SelfType<'_>,
+) -> Result<bool>
+where
+    K: 'static + redb::Key,
+    V: 'static + redb::Value,
+{
+    use redb::ReadableTableMetadata;
+    let table = redb::TableDefinition::<K, V>::new(name);
+    let write_txn = db.begin_write()?;
+    let empty = {
+        let mut table = match write_txn.open_table(table) {
+            Ok(tab) => tab,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(true),
+            Err(err) => return Err(err.into()),
+        };
+        table.remove(key)?;
+        table.is_empty()?
+    };
+    write_txn.commit()?;
+    Ok(empty)
+}
+
 fn read_from_doc_db<K, V>(db: &redb::Database, name: &str, key: &K) -> Result<V>
 where
     K: 'static + for<'a> redb::Key<SelfType<'a> = K> + std::fmt::Debug,
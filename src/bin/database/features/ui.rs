@@ -3,13 +3,13 @@ use super::{
     Features,
 };
 use crate::{
-    color::{BG_CURSOR_LINE, FG_CURSOR_LINE, FG_FEATURES},
-    database::util::PkgWithFeatures,
+    color::theme,
+    database::PkgWithFeatures,
     local_registry::PkgInfo,
     ui::{render_line, LineState, Scroll, Surround},
 };
 use ratatui::{
-    prelude::{Buffer, Color, Modifier, Rect, Span, Style},
+    prelude::{Buffer, Modifier, Rect, Span, Style},
     widgets::{Block, Borders},
 };
 use smallvec::{smallvec, SmallVec};
@@ -84,52 +84,46 @@ impl Selected {
     }
 
     fn render_line(&self, feat: &XString) -> LineTexts {
-        const E: Style = Style::new();
-        const Y: Style = Style {
-            fg: Some(FG_FEATURES),
+        let theme = theme();
+        let features = theme.pkg_features.fg;
+        let e = Style::new();
+        let y = Style {
+            fg: features,
             add_modifier: Modifier::BOLD,
             ..Style::new()
         };
-        const H: Style = Style {
-            fg: Some(FG_FEATURES),
+        let h = Style {
+            fg: features,
             add_modifier: Modifier::ITALIC,
             ..Style::new()
         };
-        const L: Style = Style {
-            fg: Some(Color::LightGreen),
-            add_modifier: Modifier::BOLD,
-            ..Style::new()
-        };
-        const R: Style = Style {
-            fg: Some(Color::Red),
-            add_modifier: Modifier::BOLD,
-            ..Style::new()
-        };
+        let l = theme.feature_locked;
+        let r = theme.feature_needless;
         match self {
             Selected::Yes => {
                 smallvec![
-                    Text::new(" ".into(), Y),
-                    Text::new(" ".into(), E),
-                    Text::new(feat.clone(), Y),
+                    Text::new(" ".into(), y),
+                    Text::new(" ".into(), e),
+                    Text::new(feat.clone(), y),
                 ]
             }
-            Selected::No => smallvec![Text::new("   ".into(), E), Text::new(feat.clone(), E)],
+            Selected::No => smallvec![Text::new("   ".into(), e), Text::new(feat.clone(), e)],
             Selected::LockedBy(s) => {
                 smallvec![
-                    Text::new("🔒".into(), L),
-                    Text::new(" ".into(), E),
-                    Text::new(feat.clone(), L),
-                    Text::new(" Locked by these features: ".into(), H),
-                    Text::new(s.clone(), H),
+                    Text::new("🔒".into(), l),
+                    Text::new(" ".into(), e),
+                    Text::new(feat.clone(), l),
+                    Text::new(" Locked by these features: ".into(), h),
+                    Text::new(s.clone(), h),
                 ]
             }
             Selected::NeedlesslyEnabledBy(s) => {
                 smallvec![
-                    Text::new(" ".into(), R),
-                    Text::new(" ".into(), E),
-                    Text::new(feat.clone(), R),
-                    Text::new(" Already enabled by these features: ".into(), H),
-                    Text::new(s.clone(), H),
+                    Text::new(" ".into(), r),
+                    Text::new(" ".into(), e),
+                    Text::new(feat.clone(), r),
+                    Text::new(" Already enabled by these features: ".into(), h),
+                    Text::new(s.clone(), h),
                 ]
             }
         }
@@ -158,6 +152,8 @@ impl Select {
             pkg: Some(PkgWithFeatures {
                 features: Features::Default,
                 info: pkg_info,
+                document_private_items: false,
+                target: None,
             }),
             list: Vec::new(),
         };
@@ -244,7 +240,7 @@ impl FeaturesUI {
                 Span {
                     content: format!("{} ", pkg_info.name()).into(),
                     style: Style {
-                        fg: Some(FG_CURSOR_LINE),
+                        fg: Some(theme().cursor_line_fg()),
                         add_modifier: Modifier::BOLD,
                         ..Style::new()
                     },
@@ -331,7 +327,7 @@ impl FeaturesUI {
         if current_line && lines.get(cursor as usize).is_some() {
             let current = y + cursor;
             for offset in 0..area.width {
-                buf[(x + offset, current)].bg = BG_CURSOR_LINE;
+                buf[(x + offset, current)].bg = theme().cursor_line_bg();
             }
         }
         for feat in lines {
@@ -9,8 +9,7 @@ pub(super) struct DocMeta {
     cargo_version: String,
     /// the host field from `rustc_version`
     host_triple: XString,
-    /// TODO: the target platform. we haven't supported this other than host triple,
-    /// so usually this equals to host_triple.
+    /// the target platform passed to `--target`, or host_triple if not specified
     target_triple: XString,
     // /// For now, each doc is generated on local machine.
     // /// TODO:
@@ -40,7 +39,8 @@ impl Default for DocMeta {
 }
 
 impl DocMeta {
-    pub fn new() -> Self {
+    /// `target` is None for the host.
+    pub fn new(target: Option<&str>) -> Self {
         match std::process::Command::new("cargo")
             .args(["+nightly", "-Vv"])
             .output()
@@ -59,7 +59,7 @@ impl DocMeta {
                             }
                         })
                         .unwrap_or_default();
-                    let target_triple = host_triple.clone();
+                    let target_triple = target.map_or_else(|| host_triple.clone(), XString::from);
                     return DocMeta {
                         cargo_version,
                         host_triple,
@@ -1,7 +1,11 @@
+mod bookmark;
+mod build;
 mod cache_info;
 mod features;
 mod meta;
 mod pkg_key;
+mod session;
+mod target;
 mod util;
 
 use self::meta::DocMeta;
@@ -11,15 +15,37 @@ use crate::{
     Result,
 };
 use color_eyre::eyre::WrapErr;
-use std::path::PathBuf;
+use semver::VersionReq;
+use std::{path::PathBuf, thread};
+use term_rustdoc::{tree::CrateDoc, util::XString};
 
 pub use self::{
+    bookmark::Bookmark,
+    build::{BuildHandle, PkgWithFeatures},
     cache_info::CachedDocInfo,
     features::{Features, FeaturesUI},
     pkg_key::PkgKey,
-    util::PkgWithFeatures,
+    session::Session,
+    target::{has_installed_targets, targets, Target, TargetKind},
 };
 
+/// Docs of direct dependencies loaded in background for a doc.
+#[derive(Debug)]
+pub struct Dependencies {
+    pub key: PkgKey,
+    /// The doc with items re-exported from external crates inlined, if any.
+    pub inlined: Option<CrateDoc>,
+    pub docs: Vec<(PkgKey, CrateDoc)>,
+    /// External crates that don't have cached docs in the versions used by the doc.
+    pub missing: Vec<CrateReq>,
+}
+
+/// A crate name and the versions of the crate that can be used.
+pub type CrateReq = (XString, VersionReq);
+
+/// A loaded doc, docs of its direct dependencies, and dependencies without cached docs.
+pub type LoadedDocs = (CrateDoc, Vec<(PkgKey, CrateDoc)>, Vec<CrateReq>);
+
 #[derive(Default)]
 pub struct DataBase {
     /// [`dirs::data_local_dir`] + `term-rustdoc` folder
@@ -43,7 +69,34 @@ impl DataBase {
         })
     }
 
-    pub fn compile_doc(&self, pkg: PkgWithFeatures) -> Option<PkgKey> {
+    /// DataBase without a sender is used out of TUI, thus docs are compiled in place.
+    pub fn init_without_sender() -> Result<Self> {
+        let dir = crate::logger::data_dir()?;
+        Ok(DataBase {
+            dir: Some(dir),
+            sender: None,
+        })
+    }
+
+    /// Compile the doc in current thread, and return when the doc is saved.
+    pub fn compile_doc_in_place(&self, pkg: PkgWithFeatures) -> Result<CachedDocInfo> {
+        let dir = self
+            .dir
+            .clone()
+            .ok_or_else(|| err!("Can't compile the doc because the dir path is not set up"))?;
+        build::build_in_place(dir, pkg)
+    }
+
+    /// Remove the target dir shared by compilations. Returns the dir if it's removed.
+    pub fn clean_target_dir(&self) -> Result<Option<PathBuf>> {
+        let dir = self
+            .dir
+            .as_deref()
+            .ok_or_else(|| err!("Can't find the target dir because the dir path is not set up"))?;
+        build::clean_target_dir(dir)
+    }
+
+    pub fn compile_doc(&self, pkg: PkgWithFeatures) -> Option<BuildHandle> {
         let Some(parent) = self.dir.clone() else {
             error!("data_local_dir/term_rustdoc does not exist");
             return None;
@@ -52,17 +105,21 @@ impl DataBase {
             error!("DataBase doesn't have a sender. This is a bug.");
             return None;
         };
-        Some(util::build(sender, parent, pkg))
+        Some(build::build(sender, parent, pkg))
     }
 
-    pub fn all_caches(&self) -> Result<Vec<CachedDocInfo>> {
-        use redb::ReadableTable;
+    /// Open `index.db` which stores CachedDocInfo, Session and Bookmarks.
+    fn index_db(&self) -> Result<redb::Database> {
         let dir = self
             .dir
             .as_deref()
-            .ok_or_else(|| err!("Can't fetch all caches because the dir path is not set up"))?;
-        let db = redb::Database::create(dir.join("index.db"))
-            .wrap_err_with(|| "Can't create index.db")?;
+            .ok_or_else(|| err!("Can't open index.db because the dir path is not set up"))?;
+        redb::Database::create(dir.join("index.db")).wrap_err_with(|| "Can't create index.db")
+    }
+
+    pub fn all_caches(&self) -> Result<Vec<CachedDocInfo>> {
+        use redb::ReadableTable;
+        let db = self.index_db()?;
         let table = redb::TableDefinition::<PkgKey, CachedDocInfo>::new("CachedDocInfo");
         let read_txn = db.begin_read()?;
         let read_only_table = match read_txn.open_table(table) {
@@ -84,6 +141,95 @@ impl DataBase {
         Ok(info)
     }
 
+    /// Inline items re-exported from external crates if the docs of these crates are cached.
+    pub fn inline_external_reexports(&self, doc: CrateDoc) -> CrateDoc {
+        let names = doc.reexported_external_crates();
+        if names.is_empty() {
+            return doc;
+        }
+        let externals: Vec<_> = self
+            .load_external_docs(&names)
+            .into_iter()
+            .map(|(_, doc)| doc)
+            .collect();
+        doc.inline_external_reexports(&externals).unwrap_or(doc)
+    }
+
+    /// Load the latest cached docs of external crates by their names.
+    pub fn load_external_docs(&self, names: &[XString]) -> Vec<(PkgKey, CrateDoc)> {
+        match self.all_caches() {
+            Ok(caches) => {
+                let crates: Vec<_> = (names.iter())
+                    .map(|name| (name.clone(), VersionReq::STAR))
+                    .collect();
+                load_external_docs(&caches, &crates).0
+            }
+            Err(err) => {
+                error!("Failed to read caches for external crates:\n{err}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Load cached docs of direct dependencies that the doc refers to in background,
+    /// inline items re-exported from external crates with them, and send them back
+    /// for the Page to show the doc.
+    pub fn load_dependencies(&self, info: &CachedDocInfo, doc: CrateDoc) {
+        let Some(sender) = self.sender.clone() else {
+            error!("DataBase doesn't have a sender to send dependencies. This is a bug.");
+            return;
+        };
+        let referred = doc.external_crate_names();
+        let mut crates: Vec<_> = match info.dependencies() {
+            Ok(deps) => deps
+                .into_iter()
+                .filter(|(name, _)| referred.contains(&name.as_str()))
+                .collect(),
+            Err(err) => {
+                error!("Failed to read dependencies of {:?}:\n{err}", info.pkg);
+                Vec::new()
+            }
+        };
+        let deps: Vec<_> = crates.iter().map(|(name, _)| name.clone()).collect();
+        // items may be re-exported from crates that are not direct dependencies,
+        // whose versions are unknown
+        let reexported = doc.reexported_external_crates();
+        for name in &reexported {
+            if !deps.contains(name) {
+                crates.push((name.clone(), VersionReq::STAR));
+            }
+        }
+        // index.db is read here rather than in another thread that may race to open it
+        let caches = if crates.is_empty() {
+            Vec::new()
+        } else {
+            self.all_caches().unwrap_or_else(|err| {
+                error!("Failed to read caches for dependencies:\n{err}");
+                Vec::new()
+            })
+        };
+        let key = info.pkg.clone();
+        thread::spawn(move || {
+            let (mut docs, missing) = load_external_docs(&caches, &crates);
+            let inlined = if reexported.is_empty() {
+                None
+            } else {
+                let externals: Vec<_> = docs.iter().map(|(_, doc)| doc.clone()).collect();
+                doc.inline_external_reexports(&externals)
+            };
+            docs.retain(|(_, doc)| deps.iter().any(|name| name == doc.crate_name()));
+            let deps = Dependencies {
+                key,
+                inlined,
+                docs,
+                missing,
+            };
+            if let Err(err) = sender.send(Event::Dependencies(Box::new(deps))) {
+                error!("Failed to send loaded dependencies:\n{err}");
+            }
+        });
+    }
+
     pub fn send_doc(&self, key: Box<PkgKey>) -> Result<()> {
         if let Some(sender) = &self.sender {
             Ok(sender.send(Event::CrateDoc(key))?)
@@ -102,3 +248,47 @@ impl DataBase {
         }
     }
 }
+
+/// Load cached docs of external crates by their names and versions.
+///
+/// The latest cached version that meets the version requirement of a crate is used.
+/// Crates without such a cached doc are returned along with loaded docs.
+fn load_external_docs(
+    caches: &[CachedDocInfo],
+    crates: &[CrateReq],
+) -> (Vec<(PkgKey, CrateDoc)>, Vec<CrateReq>) {
+    let mut missing = Vec::new();
+    let externals: Vec<_> = crates
+        .iter()
+        .filter_map(|(name, req)| {
+            // crate names in rustdoc json are normalized with underscores
+            let found = caches
+                .iter()
+                .filter(|cache| cache.pkg.name().replace('-', "_") == *name)
+                .map(|cache| (cache.pkg.version(), cache))
+                .filter(|(ver, _)| req.matches(ver))
+                .max_by(|a, b| a.0.cmp(&b.0));
+            let Some((_, cache)) = found else {
+                missing.push((name.clone(), req.clone()));
+                return None;
+            };
+            match cache.load_doc() {
+                Ok(doc) => Some((cache.pkg.clone(), doc)),
+                Err(err) => {
+                    error!(
+                        "Failed to load {:?} as an external crate:\n{err}",
+                        cache.pkg
+                    );
+                    None
+                }
+            }
+        })
+        .collect();
+    info!(
+        ?missing,
+        "Found {} cached docs for {} external crates",
+        externals.len(),
+        crates.len()
+    );
+    (externals, missing)
+}
@@ -1,8 +1,12 @@
-use super::features::Features;
+use super::{features::Features, PkgWithFeatures};
 use crate::local_registry::PkgNameVersion;
 use semver::Version;
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+use term_rustdoc::util::XString;
 
 /// The key in doc db file.
 ///
@@ -10,22 +14,36 @@ use std::fmt;
 /// we can't directly compare the version string, and the parsed Version
 /// should be stored outside this struct.
 #[derive(Deserialize, Serialize, PartialEq, Eq, Clone)]
+#[serde(from = "RawPkgKey", into = "RawPkgKey")]
 pub struct PkgKey {
     name_ver: PkgNameVersion,
     /// features enabled/used when the doc is compiled
-    /// TODO: for now, we haven't supported feature selection.
     features: Features,
+    /// The target triple passed to `--target`. None means the host.
+    target: Option<XString>,
+    /// Whether `--document-private-items` is passed to rustdoc.
+    private: bool,
+    /// The pkg dir for a pkg from a local path, which differs from the registry pkg
+    /// with the same name and version. None means a registry or sysroot pkg.
+    local: Option<PathBuf>,
 }
 
 impl fmt::Debug for PkgKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let [name, ver] = self.name_ver.name_ver();
-        let features = &self.features;
-        if matches!(features, Features::Default) {
-            write!(f, "{name}_v{ver}")
-        } else {
-            write!(f, "{name}_v{ver} [{features:?}]")
-        }
+        write!(f, "{name}_v{ver}")?;
+        self.fmt_options(f)
+    }
+}
+
+/// `name@version` with features if not default and the target if not host,
+/// which is also accepted by CLI. Private items and the local pkg dir are appended
+/// if any, which are only informative.
+impl fmt::Display for PkgKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [name, ver] = self.name_ver.name_ver();
+        write!(f, "{name}@{ver}")?;
+        self.fmt_options(f)
     }
 }
 
@@ -34,11 +52,54 @@ impl PkgKey {
         PkgKey {
             name_ver,
             features: Features::Default,
+            target: None,
+            private: false,
+            local: None,
         }
     }
 
-    pub fn new(name_ver: PkgNameVersion, features: Features) -> PkgKey {
-        PkgKey { name_ver, features }
+    /// The key for a registry or sysroot pkg without private items.
+    pub fn new(name_ver: PkgNameVersion, features: Features, target: Option<XString>) -> PkgKey {
+        PkgKey {
+            name_ver,
+            features,
+            target,
+            private: false,
+            local: None,
+        }
+    }
+
+    /// The key of the doc compiled from the pkg.
+    pub fn from_pkg(pkg: &PkgWithFeatures) -> PkgKey {
+        let info = &pkg.info;
+        PkgKey {
+            name_ver: info.to_name_ver(),
+            features: pkg.features.clone(),
+            target: pkg.target.clone(),
+            private: pkg.document_private_items,
+            local: info.is_local().then(|| info.path().to_owned()),
+        }
+    }
+
+    fn fmt_options(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let features = &self.features;
+        if !matches!(features, Features::Default) {
+            write!(f, " [{features:?}]")?;
+        }
+        if let Some(target) = &self.target {
+            write!(f, " --target {target}")?;
+        }
+        if self.private {
+            f.write_str(" --document-private-items")?;
+        }
+        if let Some(local) = &self.local {
+            write!(f, " ({})", local.display())?;
+        }
+        Ok(())
+    }
+
+    pub fn name_ver(&self) -> &PkgNameVersion {
+        &self.name_ver
     }
 
     pub fn name(&self) -> &str {
@@ -61,14 +122,119 @@ impl PkgKey {
         &self.features
     }
 
+    /// None means the host target.
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// Whether private items are documented.
+    pub fn private(&self) -> bool {
+        self.private
+    }
+
+    /// The pkg dir if the pkg is from a local path.
+    pub fn local(&self) -> Option<&Path> {
+        self.local.as_deref()
+    }
+
     pub fn empty_state() -> PkgKey {
         PkgKey {
             name_ver: PkgNameVersion::empty_state(),
             features: Features::Default,
+            target: None,
+            private: false,
+            local: None,
         }
     }
 }
 
+/// The serialized form of PkgKey.
+///
+/// PkgKey is the key of tables in db files, so the bytes of keys for the host must stay
+/// the same as before targets are supported. Thus a target is encoded as a new variant
+/// after features variants instead of a new field, and so are private items and the
+/// local pkg dir, which are encoded in another variant to keep keys with targets.
+#[derive(Deserialize, Serialize)]
+struct RawPkgKey {
+    name_ver: PkgNameVersion,
+    features: RawFeatures,
+}
+
+/// The first variants must be in the same order as [`Features`].
+#[derive(Deserialize, Serialize)]
+enum RawFeatures {
+    Default,
+    All,
+    DefaultPlus(Box<[XString]>),
+    NoDefault,
+    NoDefaultPlus(Box<[XString]>),
+    Target(Features, XString),
+    Options(Features, KeyOptions),
+}
+
+#[derive(Deserialize, Serialize)]
+struct KeyOptions {
+    target: Option<XString>,
+    private: bool,
+    local: Option<PathBuf>,
+}
+
+impl From<PkgKey> for RawPkgKey {
+    fn from(key: PkgKey) -> Self {
+        let features = match (key.features, key.target) {
+            (features, target) if key.private || key.local.is_some() => {
+                let (private, local) = (key.private, key.local);
+                RawFeatures::Options(
+                    features,
+                    KeyOptions {
+                        target,
+                        private,
+                        local,
+                    },
+                )
+            }
+            (features, Some(target)) => RawFeatures::Target(features, target),
+            (Features::Default, None) => RawFeatures::Default,
+            (Features::All, None) => RawFeatures::All,
+            (Features::DefaultPlus(f), None) => RawFeatures::DefaultPlus(f),
+            (Features::NoDefault, None) => RawFeatures::NoDefault,
+            (Features::NoDefaultPlus(f), None) => RawFeatures::NoDefaultPlus(f),
+        };
+        RawPkgKey {
+            name_ver: key.name_ver,
+            features,
+        }
+    }
+}
+
+impl From<RawPkgKey> for PkgKey {
+    fn from(raw: RawPkgKey) -> Self {
+        let (features, target) = match raw.features {
+            RawFeatures::Default => (Features::Default, None),
+            RawFeatures::All => (Features::All, None),
+            RawFeatures::DefaultPlus(f) => (Features::DefaultPlus(f), None),
+            RawFeatures::NoDefault => (Features::NoDefault, None),
+            RawFeatures::NoDefaultPlus(f) => (Features::NoDefaultPlus(f), None),
+            RawFeatures::Target(features, target) => (features, Some(target)),
+            RawFeatures::Options(features, opts) => {
+                let KeyOptions {
+                    target,
+                    private,
+                    local,
+                } = opts;
+                return PkgKey {
+                    name_ver: raw.name_ver,
+                    features,
+                    target,
+                    private,
+                    local,
+                };
+            }
+        };
+        PkgKey::new(raw.name_ver, features, target)
+    }
+}
+
 impl redb::Value for PkgKey {
     type SelfType<'a> = PkgKey;
 
@@ -103,3 +269,42 @@ impl redb::Key for PkgKey {
         data1.cmp(data2)
     }
 }
+
+#[test]
+fn host_keys_keep_the_legacy_layout() {
+    use super::util::{decode, encode};
+
+    #[derive(Serialize)]
+    struct LegacyPkgKey {
+        name_ver: (XString, XString),
+        features: Features,
+    }
+    let legacy = encode(LegacyPkgKey {
+        name_ver: ("tokio".into(), "1.0.0".into()),
+        features: Features::DefaultPlus(["full".into()].into()),
+    })
+    .unwrap();
+    let key: PkgKey = decode(&legacy).unwrap();
+    assert_eq!(key.to_string(), r#"tokio@1.0.0 [DefaultPlus(["full"])]"#);
+    assert_eq!(encode(&key).unwrap(), legacy);
+
+    let target = Some("wasm32-unknown-unknown".into());
+    let key = PkgKey::new(key.name_ver, key.features, target);
+    assert_eq!(
+        key.to_string(),
+        r#"tokio@1.0.0 [DefaultPlus(["full"])] --target wasm32-unknown-unknown"#
+    );
+    let target_bytes = encode(&key).unwrap();
+    assert_eq!(decode::<PkgKey>(&target_bytes).unwrap(), key);
+
+    let mut private = key.clone();
+    private.private = true;
+    private.local = Some("/path/to/tokio".into());
+    assert_eq!(
+        private.to_string(),
+        r#"tokio@1.0.0 [DefaultPlus(["full"])] --target wasm32-unknown-unknown --document-private-items (/path/to/tokio)"#
+    );
+    let private_bytes = encode(&private).unwrap();
+    assert_ne!(private_bytes, target_bytes);
+    assert_eq!(decode::<PkgKey>(&private_bytes).unwrap(), private);
+}
@@ -0,0 +1,63 @@
+use super::{
+    cache_info::{remove_from_db, write_to_db},
+    pkg_key::PkgKey,
+    util::{decode, encode},
+    DataBase,
+};
+use crate::{Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use term_rustdoc::{tree::Fold, util::XString};
+
+/// The last session is stored in `index.db` alongside CachedDocInfo.
+const TABLE: &str = "Session";
+const KEY: &str = "last";
+
+/// The state of the Page when quitting, which can be restored on next start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub pkg: PkgKey,
+    /// Full path of the item under the outline cursor.
+    pub path: Option<XString>,
+    /// Fold kind and expanded modules of the module tree.
+    pub fold: Fold,
+    pub outline_start: usize,
+    pub outline_cursor: u16,
+    pub content_start: usize,
+}
+
+impl DataBase {
+    /// Read the last session. None if no session is saved or it can't be read.
+    pub fn last_session(&self) -> Option<Session> {
+        let read = || -> Result<Option<Session>> {
+            let db = self.index_db()?;
+            let table = redb::TableDefinition::<&str, Vec<u8>>::new(TABLE);
+            let read_txn = db.begin_read()?;
+            let read_only_table = match read_txn.open_table(table) {
+                Ok(tab) => tab,
+                Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+                err => err.wrap_err_with(|| "Can't read Session table from index.db")?,
+            };
+            match read_only_table.get(KEY)? {
+                Some(raw) => Ok(Some(decode(&raw.value())?)),
+                None => Ok(None),
+            }
+        };
+        read()
+            .map_err(|err| error!("Failed to read the last session:\n{err}"))
+            .ok()
+            .flatten()
+    }
+
+    pub fn save_session(&self, session: &Session) -> Result<()> {
+        let db = self.index_db()?;
+        write_to_db::<&str, Vec<u8>>(&db, TABLE, &KEY, &encode(session)?)?;
+        info!(?session.pkg, "saved the session");
+        Ok(())
+    }
+
+    pub fn remove_session(&self) -> Result<()> {
+        let db = self.index_db()?;
+        remove_from_db::<&str, Vec<u8>>(&db, TABLE, &KEY)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+//! Target triples that docs can be compiled for, queried from the nightly toolchain.
+
+use std::{process::Command, sync::OnceLock};
+use term_rustdoc::util::XString;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    Host,
+    /// The std of the target is installed via `rustup target add`.
+    Installed,
+    /// Listed by `rustc --print target-list`, but docs can't be compiled until it's installed.
+    NotInstalled,
+}
+
+#[derive(Debug)]
+pub struct Target {
+    pub triple: XString,
+    pub kind: TargetKind,
+}
+
+impl Target {
+    /// The value passed to `--target`. None for the host.
+    pub fn to_option(&self) -> Option<XString> {
+        (self.kind != TargetKind::Host).then(|| self.triple.clone())
+    }
+}
+
+static TARGETS: OnceLock<Vec<Target>> = OnceLock::new();
+
+/// All targets in the order of the host, installed targets and the others.
+///
+/// The first one is always the host, even if the toolchain can't be queried.
+pub fn targets() -> &'static [Target] {
+    TARGETS.get_or_init(query_targets)
+}
+
+/// Check if any target other than the host is installed.
+pub fn has_installed_targets() -> bool {
+    targets().iter().any(|t| t.kind == TargetKind::Installed)
+}
+
+fn query_targets() -> Vec<Target> {
+    let host = stdout("rustc", &["+nightly", "-vV"])
+        .and_then(|out| {
+            out.lines()
+                .find_map(|line| line.strip_prefix("host: ").map(XString::from))
+        })
+        .unwrap_or_else(|| XString::from("host"));
+    let installed = stdout(
+        "rustup",
+        &["target", "list", "--installed", "--toolchain", "nightly"],
+    )
+    .unwrap_or_default();
+    let installed: Vec<_> = installed
+        .lines()
+        .map(str::trim)
+        .filter(|t| !t.is_empty() && *t != host)
+        .collect();
+    let all = stdout("rustc", &["+nightly", "--print", "target-list"]).unwrap_or_default();
+    let others = all
+        .lines()
+        .map(str::trim)
+        .filter(|t| !t.is_empty() && *t != host && !installed.contains(t))
+        .map(XString::from)
+        .collect::<Vec<_>>();
+
+    let mut targets = Vec::with_capacity(installed.len() + others.len() + 1);
+    targets.push(Target {
+        triple: host,
+        kind: TargetKind::Host,
+    });
+    targets.extend(installed.iter().map(|t| Target {
+        triple: XString::from(*t),
+        kind: TargetKind::Installed,
+    }));
+    targets.extend(others.into_iter().map(|triple| Target {
+        triple,
+        kind: TargetKind::NotInstalled,
+    }));
+    info!(
+        "{} targets are found, {} installed",
+        targets.len(),
+        installed.len()
+    );
+    targets
+}
+
+/// Run the command and return the stdout if it succeeds.
+fn stdout(cmd: &str, args: &[&str]) -> Option<String> {
+    match Command::new(cmd).args(args).output() {
+        Ok(output) if output.status.success() => {
+            Some(String::from_utf8_lossy(&output.stdout).into_owned())
+        }
+        Ok(output) => {
+            let err = String::from_utf8_lossy(&output.stderr);
+            error!("Failed to run `{cmd} {}`:\n{err}", args.join(" "));
+            None
+        }
+        Err(err) => {
+            error!("Failed to run `{cmd} {}`:\n{err}", args.join(" "));
+            None
+        }
+    }
+}
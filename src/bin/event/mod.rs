@@ -1,5 +1,5 @@
 use crate::{
-    database::{CachedDocInfo, PkgKey},
+    database::{CachedDocInfo, Dependencies, PkgKey},
     Result,
 };
 use crossterm::event::{
@@ -10,6 +10,7 @@ use std::{
     thread,
     time::{Duration, Instant},
 };
+use term_rustdoc::util::XString;
 
 /// Terminal events.
 #[derive(Debug)]
@@ -24,8 +25,14 @@ pub enum Event {
     Resize(u16, u16),
     /// Pkg doc that's compiled and written into its db file.
     DocCompiled(Box<CachedDocInfo>),
+    /// A status line from cargo when compiling the doc, e.g. `Compiling name v0.1.0`.
+    DocProgress(Box<PkgKey>, XString),
+    /// The doc failed to compile, with the error text.
+    DocFailed(Box<PkgKey>, String),
     /// Compiled and loaded doc for Page.
     CrateDoc(Box<PkgKey>),
+    /// Loaded docs of direct dependencies for the doc to be shown on Page.
+    Dependencies(Box<Dependencies>),
     /// Downgraded doc which may or may not be the current one.
     Downgraded(Box<PkgKey>),
 }
@@ -1,4 +1,7 @@
-use crate::ui::{MarkdownAndHeading, ScrollMarkdown, Surround};
+use crate::{
+    config::{config, keymap, Action, Scope},
+    ui::{MarkdownAndHeading, ScrollMarkdown, Surround},
+};
 use ratatui::{
     layout::Alignment,
     prelude::{Buffer, Rect},
@@ -56,7 +59,9 @@ impl Help {
 
 fn split_surround(full: Rect) -> Surround {
     let outer = centered_rect(full, 80, 80);
-    let title = Line::from(" Press F1 to toggle this Help ").alignment(Alignment::Right);
+    let key = keymap().hint(Scope::Global, Action::ToggleHelp);
+    let title =
+        Line::from(format!(" Press {key} to toggle this Help ")).alignment(Alignment::Right);
     Surround::new(
         Block::new()
             .title(" Help ")
@@ -73,9 +78,25 @@ struct HelpMarkdown {
 
 impl HelpMarkdown {
     fn new(border: Surround) -> Self {
-        let inner = MarkdownAndHeading::new(Self::HELP, border.inner());
+        let inner = MarkdownAndHeading::new(&Self::help(), border.inner());
         HelpMarkdown { inner, border }
     }
 
+    /// The template with keys from the effective keymap, and problems in config on top.
+    fn help() -> String {
+        let config = config();
+        let help = config.keymap.help_markdown(Self::HELP);
+        if config.problems.is_empty() {
+            return help;
+        }
+        let mut problems = String::from("# Config Problems\n\n");
+        for problem in &config.problems {
+            problems.push_str("* ");
+            problems.push_str(problem);
+            problems.push('\n');
+        }
+        problems + "\n" + &help
+    }
+
     const HELP: &'static str = include_str!("help.md");
 }
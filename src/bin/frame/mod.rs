@@ -5,7 +5,7 @@ mod util;
 pub use self::util::centered_rect;
 
 use self::help::Help;
-use crate::{dashboard::DashBoard, page::Page};
+use crate::{config::config, dashboard::DashBoard, page::Page};
 use ratatui::prelude::{Buffer, Rect, Widget};
 
 pub struct Frame {
@@ -27,7 +27,13 @@ enum Focus {
 
 impl Frame {
     pub fn new(dash_board: DashBoard) -> Frame {
-        let (page, focus, help, should_quit) = Default::default();
+        let (page, help, should_quit) = Default::default();
+        // show problems in config on top of Help
+        let focus = if config().problems.is_empty() {
+            Focus::DashBoard
+        } else {
+            Focus::Help
+        };
         Frame {
             dash_board,
             page,
@@ -60,7 +66,11 @@ impl Frame {
         })
     }
 
+    /// Save the Page state for next start before quitting.
     fn quit(&mut self) {
+        if let Some(session) = self.page.session() {
+            self.dash_board.ui().save_session(&session);
+        }
         self.should_quit = true;
     }
 }
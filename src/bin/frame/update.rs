@@ -1,5 +1,12 @@
 use super::{help::Help, Focus, Frame};
-use crate::{dashboard::DashBoard, event::Event, page::Page, ui::ScrollOffset};
+use crate::{
+    config::{keymap, Action, Scope},
+    dashboard::{DashBoard, PendingJump},
+    database::PkgKey,
+    event::Event,
+    page::Page,
+    ui::ScrollOffset,
+};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
 impl Frame {
@@ -10,54 +17,92 @@ impl Frame {
             Event::Resize(_, _) => {}
             Event::MouseDoubleClick(x, y) => self.update_for_double_click((x, y)),
             Event::DocCompiled(info) => self.dash_board.ui().receive_compiled_doc(*info),
-            Event::CrateDoc(pkg_key) => {
-                let ui = &self.dash_board.ui();
-                if let Some(doc) = ui.get_loaded_doc(&pkg_key) {
-                    match Page::new(*pkg_key, doc, ui.get_full_area()) {
-                        Ok(page) => {
-                            self.page = page;
-                            self.switch_to_page();
-                        }
-                        Err(err) => error!("Failed to construct a Page:\n{err}"),
-                    }
-                }
+            Event::DocProgress(key, line) => self.dash_board.ui().receive_progress(&key, line),
+            Event::DocFailed(key, err) => self.dash_board.ui().receive_failure(&key, err),
+            Event::CrateDoc(pkg_key) => self.open_page(*pkg_key),
+            Event::Dependencies(deps) => {
+                let pkg_key = deps.key.clone();
+                self.dash_board.ui().receive_dependencies(*deps);
+                self.open_page(pkg_key);
             }
             Event::Downgraded(pkg_key) => self.page.drop(&pkg_key),
         };
     }
 
-    fn update_for_key(&mut self, key_event: KeyEvent) {
-        if key_event.modifiers == KeyModifiers::CONTROL {
-            #[allow(clippy::single_match)]
-            match key_event.code {
-                KeyCode::Char('w') => {
-                    self.switch_focus();
-                    return;
+    /// Show the loaded doc on a new Page, along with docs of its dependencies.
+    fn open_page(&mut self, pkg_key: PkgKey) {
+        let ui = self.dash_board.ui();
+        let Some((doc, others, missing)) = ui.get_loaded_doc(&pkg_key) else {
+            return;
+        };
+        let pending = ui.take_pending_jump(&pkg_key);
+        match Page::new(pkg_key, doc, ui.get_full_area()) {
+            Ok(mut page) => {
+                page.set_other_docs(others, missing);
+                match pending {
+                    Some(PendingJump::Item(path)) => page.jump_to_path(&path),
+                    Some(PendingJump::Session(session)) => page.restore_session(*session),
+                    None => (),
                 }
-                KeyCode::Char('q') => {
-                    self.quit();
-                    return;
+                self.page = page;
+                // e.g. problems in config are shown in Help on startup
+                if !matches!(self.focus, Focus::Help) {
+                    self.switch_to_page();
                 }
-                _ => (),
             }
+            Err(err) => error!("Failed to construct a Page:\n{err}"),
         }
+    }
 
-        if let KeyCode::F(1) = key_event.code {
-            if !matches!(self.focus, Focus::Help) {
-                self.get_help();
-            } else {
-                self.switch_focus();
+    fn update_for_key(&mut self, key_event: KeyEvent) {
+        let keymap = keymap();
+        match keymap.action(Scope::Global, &key_event) {
+            Some(Action::SwitchFocus) => return self.switch_focus(),
+            Some(Action::Quit) => return self.quit(),
+            Some(Action::ToggleHelp) => {
+                if !matches!(self.focus, Focus::Help) {
+                    self.get_help();
+                } else {
+                    self.switch_focus();
+                }
+                return;
             }
-            return;
+            _ => (),
         }
 
+        if let Focus::Page = self.focus {
+            self.page.clear_notice();
+        }
         match self.focus {
             Focus::DashBoard => update_dash_board(&mut self.dash_board, &key_event),
-            Focus::Page => update_page(&mut self.page, &key_event),
-            Focus::Help => update_help(self.get_help(), &key_event),
+            Focus::Page if self.page.is_searching() => {
+                update_page_search(&mut self.page, &key_event)
+            }
+            Focus::Page if self.page.is_listing_candidates() => {
+                if let Some(action) = keymap.action(Scope::PageCandidates, &key_event) {
+                    update_page_candidates(&mut self.page, action)
+                }
+            }
+            Focus::Page => match keymap.action(Scope::Page, &key_event) {
+                Some(Action::ToggleBookmark) => self.toggle_bookmark(),
+                Some(action) => update_page(&mut self.page, action),
+                None => (),
+            },
+            Focus::Help => {
+                if let Some(action) = keymap.action(Scope::Help, &key_event) {
+                    update_help(self.get_help(), action)
+                }
+            }
         };
     }
 
+    /// Bookmark the item under the outline cursor on the Page, or remove the bookmark.
+    fn toggle_bookmark(&mut self) {
+        if let Some((pkg, path)) = self.page.current_item() {
+            self.dash_board.ui().toggle_bookmark(pkg, path);
+        }
+    }
+
     fn update_for_mouse(&mut self, event: MouseEvent) {
         match self.focus {
             Focus::DashBoard => {
@@ -65,6 +110,38 @@ impl Frame {
                     self.switch_to_page();
                 }
             }
+            Focus::Page if self.page.is_searching() => {
+                let search = self.page.search();
+                match event.kind {
+                    MouseEventKind::ScrollDown => {
+                        search.results().scroll_down(ScrollOffset::Fixed(5))
+                    }
+                    MouseEventKind::ScrollUp => search.results().scroll_up(ScrollOffset::Fixed(5)),
+                    MouseEventKind::Down(MouseButton::Left)
+                        if !search.set_cursor_on_screen((event.column, event.row)) =>
+                    {
+                        search.close()
+                    }
+                    _ => (),
+                }
+            }
+            Focus::Page if self.page.is_listing_candidates() => {
+                let candidates = self.page.candidates();
+                match event.kind {
+                    MouseEventKind::ScrollDown => {
+                        candidates.found().scroll_down(ScrollOffset::Fixed(5))
+                    }
+                    MouseEventKind::ScrollUp => {
+                        candidates.found().scroll_up(ScrollOffset::Fixed(5))
+                    }
+                    MouseEventKind::Down(MouseButton::Left)
+                        if !candidates.set_cursor_on_screen((event.column, event.row)) =>
+                    {
+                        candidates.close()
+                    }
+                    _ => (),
+                }
+            }
             Focus::Page => match event.kind {
                 MouseEventKind::ScrollDown => {
                     self.page.scrolldown(ScrollOffset::Fixed(5));
@@ -112,64 +189,124 @@ impl Frame {
 
 fn update_dash_board(dash: &mut DashBoard, key_event: &KeyEvent) {
     let ui = dash.ui();
-    if key_event.modifiers == KeyModifiers::CONTROL {
+    let Some(action) = keymap().action(Scope::DashBoard, key_event) else {
+        // type in the input
         match key_event.code {
-            KeyCode::Char('c') => ui.clear_input(),
-            KeyCode::Char('s') => ui.switch_sort(),
-            KeyCode::Char('f') => ui.switch_search_source(),
+            KeyCode::Char(_) if key_event.modifiers.contains(KeyModifiers::CONTROL) => (),
+            KeyCode::Char(ch) => ui.respond_to_char(ch),
+            KeyCode::Backspace => ui.pop_char(),
             _ => (),
         }
         return;
-    }
-    match key_event.code {
-        KeyCode::Char(ch) => ui.respond_to_char(ch),
-        KeyCode::Backspace => ui.pop_char(),
-        KeyCode::Up => ui.move_backward_cursor(),
-        KeyCode::Down => ui.move_forward_cursor(),
-        KeyCode::Home => ui.scroll_home(),
-        KeyCode::End => ui.scroll_end(),
-        KeyCode::PageUp => ui.scroll_up(),
-        KeyCode::PageDown => ui.scroll_down(),
-        KeyCode::Enter => ui.compile_or_load_doc(None),
-        KeyCode::Tab => ui.switch_panel(),
-        KeyCode::Delete => ui.downgrade(None),
-        KeyCode::Esc => ui.close_ver_feat(),
+    };
+    match action {
+        Action::ClearInput => ui.clear_input(),
+        Action::SwitchSort => ui.switch_sort(),
+        Action::SwitchSearchSource => ui.switch_search_source(),
+        Action::TogglePathInput => ui.toggle_path_input(),
+        Action::TogglePrivateItems => ui.toggle_private_items(),
+        Action::ToggleNoteInput => ui.toggle_note_input(),
+        Action::RestoreSession => ui.restore_last_session(),
+        Action::ToggleSelect => ui.toggle_select(),
+        Action::CompileSelected => ui.compile_selected(),
+        Action::CompileDependencies => ui.compile_dependencies(),
+        Action::CursorUp => ui.move_backward_cursor(),
+        Action::CursorDown => ui.move_forward_cursor(),
+        Action::ScrollHome => ui.scroll_home(),
+        Action::ScrollEnd => ui.scroll_end(),
+        Action::PageUp => ui.scroll_up(),
+        Action::PageDown => ui.scroll_down(),
+        Action::Confirm => ui.compile_or_load_doc(None),
+        Action::SwitchPanel => ui.switch_panel(),
+        Action::Remove => ui.downgrade(None),
+        Action::Close => ui.close_popup(),
         _ => (),
     }
 }
 
-fn update_page(page: &mut Page, key_event: &KeyEvent) {
-    match key_event.code {
-        KeyCode::Down | KeyCode::Char('j') => page.move_forward_cursor(),
-        KeyCode::Up | KeyCode::Char('k') => page.move_backward_cursor(),
-        KeyCode::Right | KeyCode::Tab | KeyCode::Char('l') => page.set_next_action(),
-        KeyCode::Left | KeyCode::Char('h') => page.set_previous_action(),
-        KeyCode::Home => page.scroll_home(),
-        KeyCode::End => page.scroll_end(),
-        KeyCode::PageUp => page.scrollup(ScrollOffset::HalfScreen),
-        KeyCode::PageDown => page.scrolldown(ScrollOffset::HalfScreen),
-        KeyCode::Char('L') => page.move_bottom_cursor(),
-        KeyCode::Char('H') => page.move_top_cursor(),
-        KeyCode::Char('M') => page.move_middle_cursor(),
-        KeyCode::Char('m') => page.outline_fold_expand_current_module_only(),
-        KeyCode::Char('/') => page.outline_fold_expand_all(),
-        KeyCode::Char('0') => page.outline_fold_expand_zero_level(),
-        KeyCode::Char('1') => page.outline_fold_expand_to_first_level_modules(),
-        KeyCode::Enter => page.outline_fold_expand_toggle(),
-        KeyCode::Char('d') => page.toggle_sytect(),
+fn update_page(page: &mut Page, action: Action) {
+    match action {
+        Action::CursorDown => page.move_forward_cursor(),
+        Action::CursorUp => page.move_backward_cursor(),
+        Action::NextNavi => page.set_next_action(),
+        Action::PreviousNavi => page.set_previous_action(),
+        Action::ScrollHome => page.scroll_home(),
+        Action::ScrollEnd => page.scroll_end(),
+        Action::PageUp => page.scrollup(ScrollOffset::HalfScreen),
+        Action::PageDown => page.scrolldown(ScrollOffset::HalfScreen),
+        Action::CursorBottom => page.move_bottom_cursor(),
+        Action::CursorTop => page.move_top_cursor(),
+        Action::CursorMiddle => page.move_middle_cursor(),
+        Action::ExpandCurrentModule => page.outline_fold_expand_current_module_only(),
+        Action::ExpandAll => page.outline_fold_expand_all(),
+        Action::ExpandZeroLevel => page.outline_fold_expand_zero_level(),
+        Action::ExpandToFirstLevel => page.outline_fold_expand_to_first_level_modules(),
+        Action::Confirm => page.enter(),
+        Action::ToggleMarkdown => page.toggle_sytect(),
+        Action::OpenSearch => page.open_search(),
+        Action::HistoryBack => page.history_back(),
+        Action::HistoryForward => page.history_forward(),
+        Action::ShowCandidates => page.show_candidates(),
+        Action::NextLink => page.select_item_link(true),
+        Action::PreviousLink => page.select_item_link(false),
         _ => {}
     };
 }
 
-fn update_help(help: &mut Help, event: &KeyEvent) {
+fn update_page_search(page: &mut Page, key_event: &KeyEvent) {
+    let search = page.search();
+    let Some(action) = keymap().action(Scope::PageSearch, key_event) else {
+        // type in the input
+        match key_event.code {
+            KeyCode::Char(_) if key_event.modifiers.contains(KeyModifiers::CONTROL) => (),
+            KeyCode::Char(ch) => search.push_char(ch),
+            KeyCode::Backspace => search.pop_char(),
+            _ => (),
+        }
+        return;
+    };
+    match action {
+        Action::ClearInput => search.clear_input(),
+        Action::SwitchSearchSource => search.switch_source(),
+        Action::CursorUp => search.results().move_backward_cursor(),
+        Action::CursorDown => search.results().move_forward_cursor(),
+        Action::ScrollHome => search.results().scroll_home(),
+        Action::ScrollEnd => search.results().scroll_end(),
+        Action::PageUp => search.results().scroll_up(ScrollOffset::HalfScreen),
+        Action::PageDown => search.results().scroll_down(ScrollOffset::HalfScreen),
+        Action::Confirm => page.confirm_search(),
+        Action::Close => search.close(),
+        _ => (),
+    }
+}
+
+fn update_page_candidates(page: &mut Page, action: Action) {
+    let candidates = page.candidates();
+    match action {
+        Action::CursorUp => candidates.found().move_backward_cursor(),
+        Action::CursorDown => candidates.found().move_forward_cursor(),
+        Action::ScrollHome => candidates.found().scroll_home(),
+        Action::ScrollEnd => candidates.found().scroll_end(),
+        Action::PageUp => candidates.found().scroll_up(ScrollOffset::HalfScreen),
+        Action::PageDown => candidates.found().scroll_down(ScrollOffset::HalfScreen),
+        Action::Confirm => page.confirm_candidate(),
+        Action::Close => candidates.close(),
+        Action::SwitchPanel => page.next_candidate_param(),
+        Action::SwitchSearchSource => page.switch_candidate_source(),
+        Action::SwitchList => page.switch_candidate_list(),
+        _ => (),
+    }
+}
+
+fn update_help(help: &mut Help, action: Action) {
     let help = help.scroll_text();
-    match event.code {
-        KeyCode::Up => help.scroll_up(ScrollOffset::Fixed(1)),
-        KeyCode::Down => help.scroll_down(ScrollOffset::Fixed(1)),
-        KeyCode::Home => help.scroll_home(),
-        KeyCode::End => help.scroll_end(),
-        KeyCode::PageUp => help.scroll_up(ScrollOffset::Fixed(5)),
-        KeyCode::PageDown => help.scroll_down(ScrollOffset::Fixed(5)),
+    match action {
+        Action::CursorUp => help.scroll_up(ScrollOffset::Fixed(1)),
+        Action::CursorDown => help.scroll_down(ScrollOffset::Fixed(1)),
+        Action::ScrollHome => help.scroll_home(),
+        Action::ScrollEnd => help.scroll_end(),
+        Action::PageUp => help.scroll_up(ScrollOffset::Fixed(5)),
+        Action::PageDown => help.scroll_down(ScrollOffset::Fixed(5)),
         _ => (),
     }
 }
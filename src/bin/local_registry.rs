@@ -1,15 +1,13 @@
-use crate::{
-    color::{PKG_NAME, PKG_VERSION},
-    Result,
-};
+use crate::{color::theme, err, Result};
 use itertools::Itertools;
 use ratatui::prelude::Style;
 use regex::Regex;
-use semver::Version;
+use semver::{Comparator, Op, Version, VersionReq};
 use serde::{Deserialize, Serialize};
 use std::{
     fs,
     path::{Path, PathBuf},
+    sync::OnceLock,
     time::SystemTime,
 };
 use term_rustdoc::util::XString;
@@ -53,6 +51,198 @@ fn find_pkgs(registry_src: &Path) -> Vec<PkgInfo> {
     }
 }
 
+/// Crates shipped with the `rust-docs-json` rustup component.
+const SYSROOT_CRATES: [&str; 5] = ["std", "core", "alloc", "proc_macro", "test"];
+
+/// Run `rustc +nightly` with the args, and return the trimmed stdout.
+fn nightly_rustc(args: &[&str]) -> Option<String> {
+    match std::process::Command::new("rustc")
+        .arg("+nightly")
+        .args(args)
+        .output()
+    {
+        Ok(output) if output.status.success() => {
+            Some(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+        }
+        Ok(output) => {
+            let err = String::from_utf8_lossy(&output.stderr);
+            error!("Failed to run `rustc +nightly {}`:\n{err}", args.join(" "));
+            None
+        }
+        Err(err) => {
+            error!("Failed to run `rustc +nightly {}`:\n{err}", args.join(" "));
+            None
+        }
+    }
+}
+
+/// The sysroot and the version of the nightly toolchain.
+struct Nightly {
+    sysroot: PathBuf,
+    ver: XString,
+    version: Version,
+}
+
+/// Query the nightly toolchain once, since the result doesn't change while running.
+/// The two rustc processes run at the same time to wait less.
+fn nightly() -> Option<&'static Nightly> {
+    static NIGHTLY: OnceLock<Option<Nightly>> = OnceLock::new();
+    NIGHTLY
+        .get_or_init(|| {
+            let (sysroot, ver) = std::thread::scope(|s| {
+                let sysroot = s.spawn(|| nightly_rustc(&["--print", "sysroot"]));
+                let ver = nightly_rustc(&["-V"]);
+                (sysroot.join().ok().flatten(), ver)
+            });
+            // rustc 1.80.0-nightly (ada5e2c7b 2024-05-31)
+            let ver = XString::from(ver?.split_whitespace().nth(1)?);
+            let Ok(version) = ver.parse::<Version>() else {
+                error!("Failed to parse the nightly rustc version `{ver}`");
+                return None;
+            };
+            Some(Nightly {
+                sysroot: sysroot?.into(),
+                ver,
+                version,
+            })
+        })
+        .as_ref()
+}
+
+/// Find json docs under `share/doc/rust/json/` in the nightly sysroot, which are
+/// installed by `rustup component add --toolchain nightly rust-docs-json`.
+///
+/// The version of these pkgs is the nightly rustc version like `1.80.0-nightly`.
+fn sysroot_pkgs() -> Vec<PkgInfo> {
+    let Some(Nightly {
+        sysroot,
+        ver,
+        version,
+    }) = nightly()
+    else {
+        return Vec::new();
+    };
+    let mut json_dir = sysroot.clone();
+    json_dir.extend(["share", "doc", "rust", "json"]);
+    if !json_dir.exists() {
+        info!(
+            "{} doesn't exist, thus no std docs available: install the rust-docs-json component",
+            json_dir.display()
+        );
+        return Vec::new();
+    }
+    SYSROOT_CRATES
+        .iter()
+        .filter_map(|name| {
+            let json = json_dir.join(format!("{name}.json"));
+            let modified = json.metadata().ok()?.modified().ok()?;
+            Some(PkgInfo {
+                name: (*name).into(),
+                ver_str: ver.clone(),
+                version: version.clone(),
+                path: json,
+                modified,
+                source: PkgSource::Sysroot,
+            })
+        })
+        .collect()
+}
+
+/// Find pkgs from a local Cargo.toml or the dir containing it via `cargo metadata`.
+/// All members are returned for a workspace root, while only the pkg itself is
+/// returned for a workspace member.
+pub fn local_pkgs(path: &Path) -> Result<Vec<PkgInfo>> {
+    #[derive(Deserialize)]
+    struct Metadata {
+        packages: Vec<Package>,
+        workspace_root: PathBuf,
+    }
+    #[derive(Deserialize)]
+    struct Package {
+        name: XString,
+        version: Version,
+        manifest_path: PathBuf,
+    }
+
+    let manifest = if path.is_dir() {
+        path.join("Cargo.toml")
+    } else {
+        path.to_owned()
+    };
+    let output = std::process::Command::new("cargo")
+        .args([
+            "metadata",
+            "--no-deps",
+            "--format-version",
+            "1",
+            "--manifest-path",
+        ])
+        .arg(&manifest)
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(err!(
+            "Failed to run `cargo metadata` on {}:\n{stderr}",
+            manifest.display()
+        ));
+    }
+    let metadata: Metadata = serde_json::from_slice(&output.stdout)?;
+    let manifest = manifest.canonicalize()?;
+    let is_root = metadata.workspace_root.join("Cargo.toml") == manifest;
+    let mut pkgs: Vec<_> = metadata
+        .packages
+        .into_iter()
+        .filter(|pkg| is_root || pkg.manifest_path == manifest)
+        .filter_map(|pkg| {
+            let path = pkg.manifest_path.parent()?.to_owned();
+            let modified = path.metadata().ok()?.modified().ok()?;
+            Some(PkgInfo {
+                name: pkg.name,
+                ver_str: pkg.version.to_string().into(),
+                version: pkg.version,
+                path,
+                modified,
+                source: PkgSource::Local,
+            })
+        })
+        .collect();
+    pkgs.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    Ok(pkgs)
+}
+
+/// Names and versions of pkgs from registries in Cargo.lock of the workspace that the
+/// local pkg dir belongs to. Local and git pkgs are not included.
+pub fn lock_dependencies(pkg_dir: &Path) -> Result<Vec<(XString, XString)>> {
+    #[derive(Deserialize)]
+    struct CargoLock {
+        #[serde(default)]
+        package: Vec<Package>,
+    }
+    #[derive(Deserialize)]
+    struct Package {
+        name: XString,
+        version: XString,
+        source: Option<String>,
+    }
+
+    let lock = pkg_dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.exists())
+        .ok_or_else(|| err!("Cargo.lock is not found for {}", pkg_dir.display()))?;
+    let content = fs::read_to_string(&lock)?;
+    let lock: CargoLock =
+        toml::from_str(&content).map_err(|e| err!("Failed to parse {}:\n{e}", lock.display()))?;
+    let is_registry =
+        |source: &str| source.starts_with("registry+") || source.starts_with("sparse+");
+    Ok(lock
+        .package
+        .into_iter()
+        .filter(|pkg| pkg.source.as_deref().is_some_and(is_registry))
+        .map(|pkg| (pkg.name, pkg.version))
+        .collect())
+}
+
 pub fn all_pkgs_in_latest_registry(registry_src: &Path) -> Vec<PkgInfo> {
     let mut pkgs = find_pkgs(registry_src);
     pkgs.sort_unstable_by(|a, b| (&*a.name, &a.version).cmp(&(&*b.name, &b.version)));
@@ -91,10 +281,13 @@ impl LocalRegistry {
         Ok(LocalRegistry { pkgs, path })
     }
 
+    /// The latest pkgs are prepended with sysroot pkgs like std if available.
     pub fn all_pkgs_with_latest_and_all_versions() -> Result<[Self; 2]> {
         let all = Self::all_pkgs_in_latest_registry()?;
+        let mut pkgs = sysroot_pkgs();
+        pkgs.extend(all_versions_to_latest_version(&all.pkgs));
         let latest = LocalRegistry {
-            pkgs: all_versions_to_latest_version(&all.pkgs),
+            pkgs,
             path: all.path.clone(),
         };
         Ok([latest, all])
@@ -104,6 +297,13 @@ impl LocalRegistry {
         self.pkgs.len()
     }
 
+    /// Put local pkgs on top. Pkgs already in the list are moved instead of being duplicated.
+    pub fn insert_local_pkgs(&mut self, local: Vec<PkgInfo>) {
+        self.pkgs
+            .retain(|pkg| local.iter().all(|l| l.path != pkg.path));
+        self.pkgs.splice(0..0, local);
+    }
+
     pub fn registry_src_path(&self) -> &Path {
         &self.path
     }
@@ -141,6 +341,18 @@ impl PkgNameVersion {
     }
 }
 
+/// Where a pkg comes from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PkgSource {
+    /// Downloaded into the local registry.
+    #[default]
+    Registry,
+    /// Added from a local path.
+    Local,
+    /// Shipped with the rust-docs-json component, whose json doc needs no compilation.
+    Sysroot,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PkgInfo {
     /// Pkg name from dir name.
@@ -150,9 +362,50 @@ pub struct PkgInfo {
     /// Pkg version parsed from ver_str.
     version: Version,
     /// The full pkg dir path not including Cargo.toml but including registry src path.
+    ///
+    /// For sysroot pkgs like std, this is the path to the json doc file.
     path: PathBuf,
     /// The last modified time for pkg dir.
     modified: SystemTime,
+    source: PkgSource,
+}
+
+/// PkgInfo saved in db files before the source is recorded.
+#[derive(Deserialize)]
+pub struct LegacyPkgInfo {
+    name: XString,
+    ver_str: XString,
+    version: Version,
+    path: PathBuf,
+    modified: SystemTime,
+}
+
+/// The source is inferred from the path.
+impl From<LegacyPkgInfo> for PkgInfo {
+    fn from(legacy: LegacyPkgInfo) -> Self {
+        let LegacyPkgInfo {
+            name,
+            ver_str,
+            version,
+            path,
+            modified,
+        } = legacy;
+        let source = if path.extension().is_some_and(|ext| ext == "json") {
+            PkgSource::Sysroot
+        } else if home::cargo_home().is_ok_and(|home| !path.starts_with(home)) {
+            PkgSource::Local
+        } else {
+            PkgSource::Registry
+        };
+        PkgInfo {
+            name,
+            ver_str,
+            version,
+            path,
+            modified,
+            source,
+        }
+    }
 }
 
 impl Default for PkgInfo {
@@ -164,6 +417,7 @@ impl Default for PkgInfo {
             version: Version::new(0, 0, 0),
             path,
             modified: SystemTime::now(),
+            source: PkgSource::Registry,
         }
     }
 }
@@ -178,6 +432,7 @@ impl PkgInfo {
             version,
             path: pkg_path,
             modified,
+            source: PkgSource::Registry,
         })
     }
 
@@ -194,7 +449,10 @@ impl PkgInfo {
     }
 
     pub fn styled_name_ver(&self) -> [(&str, Style); 2] {
-        [(self.name(), PKG_NAME), (self.ver(), PKG_VERSION)]
+        [
+            (self.name(), theme().pkg_name),
+            (self.ver(), theme().pkg_version),
+        ]
     }
 
     pub fn path(&self) -> &Path {
@@ -208,6 +466,77 @@ impl PkgInfo {
         }
     }
 
+    /// Sysroot pkgs from the rust-docs-json component have json docs already,
+    /// thus no need to compile them.
+    pub fn is_sysroot(&self) -> bool {
+        self.source == PkgSource::Sysroot
+    }
+
+    /// Pkgs added from local paths instead of registries.
+    pub fn is_local(&self) -> bool {
+        self.source == PkgSource::Local
+    }
+
+    /// Crate names of direct dependencies with `-` replaced by `_` as in json docs,
+    /// and versions of them that the pkg uses: the exact versions resolved in Cargo.lock
+    /// for local pkgs, otherwise the version requirements in Cargo.toml.
+    /// Dev-dependencies and build-dependencies are excluded.
+    pub fn dependencies(&self) -> Vec<(XString, VersionReq)> {
+        if self.is_sysroot() {
+            let deps: &[&str] = match &*self.name {
+                "std" => &["alloc", "core"],
+                "alloc" => &["core"],
+                "proc_macro" | "test" => &["core", "std"],
+                _ => &[],
+            };
+            // sysroot crates come from the same toolchain
+            let req = exact_version(&self.version);
+            return deps
+                .iter()
+                .map(|&name| (name.into(), req.clone()))
+                .collect();
+        }
+        let manifest = match cargo_toml::Manifest::from_path(self.path.join("Cargo.toml")) {
+            Ok(manifest) => manifest,
+            Err(err) => {
+                error!("Failed to read dependencies of {}:\n{err}", self.name);
+                return Vec::new();
+            }
+        };
+        let locked = if self.is_local() {
+            lock_dependencies(&self.path).unwrap_or_else(|err| {
+                error!(
+                    "Failed to read locked dependencies of {}:\n{err}",
+                    self.name
+                );
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+        let targets = manifest.target.values().map(|target| &target.dependencies);
+        let mut deps: Vec<(XString, VersionReq)> = [&manifest.dependencies]
+            .into_iter()
+            .chain(targets)
+            .flatten()
+            .map(|(key, dep)| {
+                let pkg = dep.package().unwrap_or(key);
+                let req = VersionReq::parse(dep.req()).unwrap_or(VersionReq::STAR);
+                // Cargo.lock can have several versions of a pkg
+                let locked = locked
+                    .iter()
+                    .filter(|(name, _)| name == pkg)
+                    .filter_map(|(_, ver)| Version::parse(ver).ok())
+                    .find(|ver| req.matches(ver));
+                let req = locked.map_or(req, |ver| exact_version(&ver));
+                (pkg.replace('-', "_").into(), req)
+            })
+            .collect();
+        deps.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        deps.dedup_by(|a, b| a.0 == b.0);
+        deps
+    }
+
     /// This is not the same as PartialEq/Eq (`==`), because this method only
     /// compares with name and version.
     pub fn is_same_pkg(&self, pkg: &Self) -> bool {
@@ -215,6 +544,19 @@ impl PkgInfo {
     }
 }
 
+/// `=ver` as a version requirement.
+fn exact_version(ver: &Version) -> VersionReq {
+    VersionReq {
+        comparators: vec![Comparator {
+            op: Op::Exact,
+            major: ver.major,
+            minor: Some(ver.minor),
+            patch: Some(ver.patch),
+            pre: ver.pre.clone(),
+        }],
+    }
+}
+
 thread_local! {
     static RE: Regex = Regex::new(r"-\d+\.\d+\.\d+.*?$").unwrap();
 }
@@ -272,3 +614,26 @@ fn local_registry_pkgs() -> Result<()> {
     dbg!(pkgs.iter().map(|p| &p.path).collect::<Vec<_>>());
     Ok(())
 }
+
+#[test]
+fn local_workspace_pkgs() -> Result<()> {
+    let pkgs = local_pkgs(Path::new(env!("CARGO_MANIFEST_DIR")))?;
+    let names: Vec<_> = pkgs.iter().map(|p| p.name()).collect();
+    assert_eq!(names, ["integration", "reexport", "term-rustdoc"]);
+
+    let member = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/integration/Cargo.toml");
+    let pkgs = local_pkgs(&member)?;
+    assert_eq!(pkgs.len(), 1);
+    assert_eq!(pkgs[0].name(), "integration");
+    Ok(())
+}
+
+#[test]
+fn workspace_lock_dependencies() -> Result<()> {
+    let member = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/integration");
+    let deps = lock_dependencies(&member)?;
+    assert!(deps.iter().any(|(name, _)| name == "ratatui"));
+    // workspace members are local pkgs
+    assert!(deps.iter().all(|(name, _)| name != "integration"));
+    Ok(())
+}
@@ -1,4 +1,6 @@
+mod cli;
 mod color;
+mod config;
 mod dashboard;
 mod database;
 mod event;
@@ -14,18 +16,27 @@ mod ui;
 extern crate tracing;
 
 use self::frame::Frame;
+use clap::Parser;
 use color_eyre::eyre::{eyre as err, Result, WrapErr};
 
 fn main() -> Result<()> {
-    tui::install_hooks()?;
+    let mut args = cli::Args::parse();
     logger::init()?;
+    // only `open` subcommand runs in TUI
+    if let Some(cmd) = args
+        .command
+        .take_if(|cmd| !matches!(cmd, cli::Command::Open(_)))
+    {
+        return cli::run(cmd);
+    }
+    tui::install_hooks()?;
 
     let mut tui = tui::Tui::new(1000)?;
     let fuzz = fuzzy::Fuzzy::new();
 
     let full = tui.size()?;
     let sender = tui.events.get_sender();
-    let dash_board = dashboard::DashBoard::new(full, fuzz, sender)?;
+    let dash_board = dashboard::DashBoard::new(full, fuzz, sender, &args)?;
     let mut frame = Frame::new(dash_board);
 
     // Start the main loop.
@@ -0,0 +1,423 @@
+//! A popup on the Page to list concrete types that meet the trait bounds on a generic
+//! type parameter of the item in declaration, or functions that return or accept such
+//! types and generic types with equivalent or stronger bounds.
+
+use super::Page;
+use crate::{
+    color::theme,
+    config::{keymap, Action, Scope},
+    database::PkgKey,
+    frame::centered_rect,
+    ui::{render_line, LineState, Scroll, Surround},
+};
+use ratatui::{
+    layout::Alignment,
+    prelude::{Buffer, Line, Rect, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+use rustdoc_types::Id;
+use term_rustdoc::{
+    tree::{CrateDoc, ItemPath},
+    type_name::{generic_bounds, BoundedFns, Candidate, GenericBounds, TraitImplementors},
+    util::{xformat, XString},
+};
+
+#[derive(Default)]
+pub struct CandidateTypes {
+    /// Show the popup or not.
+    show: bool,
+    /// The item in declaration, which is not listed as a function for itself.
+    item: Option<Id>,
+    /// Generic type parameters on the item.
+    params: Vec<GenericBounds>,
+    /// Index of the parameter whose candidates are listed.
+    current: usize,
+    /// List candidates in other loaded docs as well as in current doc.
+    all_docs: bool,
+    list: List,
+    /// Implementors in current doc at first, followed by those in other docs.
+    /// They're collected the first time they're needed, so are bounded functions.
+    implementors: Vec<TraitImplementors>,
+    bounded: Vec<BoundedFns>,
+    found: Scroll<Listed>,
+    border: Surround,
+    full: Rect,
+}
+
+impl std::fmt::Debug for CandidateTypes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CandidateTypes")
+            .field("show", &self.show)
+            .field("params", &self.params)
+            .field("current", &self.current)
+            .field("all_docs", &self.all_docs)
+            .field("list", &self.list)
+            .finish()
+    }
+}
+
+/// What to list for the generic type parameter.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum List {
+    /// Concrete types that meet the bounds.
+    #[default]
+    Types,
+    /// Functions that return such concrete types or generic types.
+    Returning,
+    /// Functions that accept such concrete types or generic types.
+    Accepting,
+}
+
+impl List {
+    fn next(self) -> List {
+        match self {
+            List::Types => List::Returning,
+            List::Returning => List::Accepting,
+            List::Accepting => List::Types,
+        }
+    }
+
+    fn text(self) -> &'static str {
+        match self {
+            List::Types => "Types",
+            List::Returning => "Functions Returning",
+            List::Accepting => "Functions Accepting",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Listed {
+    found: Vec<Found>,
+}
+
+impl std::ops::Deref for Listed {
+    type Target = [Found];
+
+    fn deref(&self) -> &Self::Target {
+        &self.found
+    }
+}
+
+pub struct Found {
+    /// 0 for current doc, or the index plus one in other docs.
+    doc: usize,
+    /// A type path, or a function path.
+    path: XString,
+    /// The item to jump to if it's defined in the doc.
+    id: Option<Id>,
+    outer: Option<Id>,
+    /// The generic type or concrete type in the signature of a function.
+    note: Option<XString>,
+}
+
+impl Found {
+    fn candidate(doc: usize, candidate: Candidate) -> Found {
+        Found {
+            doc,
+            path: candidate.ty,
+            id: candidate.id,
+            outer: None,
+            note: None,
+        }
+    }
+
+    fn function(doc: usize, item: &ItemPath, note: XString) -> Found {
+        Found {
+            doc,
+            path: item.path.clone(),
+            id: Some(item.id),
+            outer: item.outer,
+            note: Some(note),
+        }
+    }
+}
+
+impl LineState for Found {
+    type State = (usize, XString, Option<XString>);
+
+    fn state(&self) -> Self::State {
+        (self.doc, self.path.clone(), self.note.clone())
+    }
+
+    fn is_identical(&self, state: &Self::State) -> bool {
+        self.doc == state.0 && self.path == state.1 && self.note == state.2
+    }
+}
+
+impl CandidateTypes {
+    pub fn is_shown(&self) -> bool {
+        self.show
+    }
+
+    pub fn close(&mut self) {
+        self.show = false;
+    }
+
+    /// Drop what's collected from other docs, since the indices of them are changed.
+    pub fn forget_other_docs(&mut self) {
+        self.implementors.truncate(1);
+        self.bounded.truncate(1);
+    }
+
+    fn param(&self) -> Option<&GenericBounds> {
+        self.params.get(self.current)
+    }
+
+    /// List candidates or functions for the parameter in docs, where current doc comes first.
+    fn update(&mut self, docs: &[&CrateDoc]) {
+        let len = if self.all_docs { docs.len() } else { 1 };
+        for doc in docs.iter().take(len).skip(self.implementors.len()) {
+            self.implementors.push(TraitImplementors::new(doc));
+        }
+        if self.list != List::Types {
+            for doc in docs.iter().take(len).skip(self.bounded.len()) {
+                self.bounded.push(BoundedFns::new(doc));
+            }
+        }
+        let mut found = Vec::new();
+        if let Some(param) = self.params.get(self.current) {
+            for (doc, implementors) in self.implementors.iter().take(len).enumerate() {
+                let candidates = implementors.candidates(&param.traits);
+                if self.list == List::Types {
+                    found.extend(candidates.into_iter().map(|c| Found::candidate(doc, c)));
+                    continue;
+                }
+                let returning = self.list == List::Returning;
+                let bounded = &self.bounded[doc];
+                let (generic, verb) = if returning {
+                    (bounded.returning(param), "returns")
+                } else {
+                    (bounded.accepting(param), "accepts")
+                };
+                let itself = |id: &Id| doc == 0 && self.item == Some(*id);
+                found.extend(
+                    generic
+                        .iter()
+                        .filter(|f| !itself(&f.item.id))
+                        .map(|f| Found::function(doc, &f.item, xformat!("{verb} {}", f.param))),
+                );
+                let usages = docs[doc].type_usages();
+                let mut concrete = Vec::new();
+                for candidate in &candidates {
+                    let Some(fns) = candidate.id.and_then(|id| usages.get(&id)) else {
+                        continue;
+                    };
+                    let fns = if returning {
+                        &fns.returns
+                    } else {
+                        &fns.arguments
+                    };
+                    concrete.extend(
+                        fns.iter()
+                            .filter(|f| !itself(&f.id))
+                            .map(|f| Found::function(doc, f, xformat!("{verb} {}", candidate.ty))),
+                    );
+                }
+                concrete.sort_by(|a, b| a.path.cmp(&b.path));
+                found.extend(concrete);
+            }
+        }
+        info!(
+            param = %self.param().map(|p| p.to_string()).unwrap_or_default(),
+            list = self.list.text(),
+            "found {} candidates",
+            found.len()
+        );
+        self.found.lines = Listed { found };
+        self.found.start = 0;
+        self.found.cursor.y = 0;
+    }
+
+    pub fn update_area(&mut self, full: Rect) {
+        if self.full == full {
+            return;
+        }
+        self.full = full;
+        let outer = centered_rect(full, 60, 60);
+        let keymap = keymap();
+        let title = Line::from(format!(
+            " {} to jump / {} to close ",
+            keymap.hint(Scope::PageCandidates, Action::Confirm),
+            keymap.hint(Scope::PageCandidates, Action::Close)
+        ))
+        .alignment(Alignment::Right);
+        self.border = Surround::new(
+            Block::new().title_bottom(title).borders(Borders::ALL),
+            outer,
+        );
+        self.found.area = self.border.inner();
+    }
+
+    pub fn render(&self, buf: &mut Buffer, others: &[(PkgKey, CrateDoc)]) {
+        Clear.render(self.border.area(), buf);
+        self.border.render(buf);
+        let keymap = keymap();
+        let Some(param) = self.param() else {
+            return;
+        };
+        let source = if self.all_docs {
+            "All Loaded Docs"
+        } else {
+            "Current Crate"
+        };
+        let key = keymap.hint(Scope::PageCandidates, Action::SwitchSearchSource);
+        let list = self.list.text();
+        let text = xformat!(" {list} `{param}` In {source} ({key} to switch) ");
+        self.border.render_only_top_left_text(buf, &text, 0);
+
+        let found = &self.found;
+        let Rect {
+            x, mut y, width, ..
+        } = found.area;
+        let width = width as usize;
+        let gray = theme().pkg_version;
+        if let Some(lines) = found.visible_lines() {
+            for line in lines {
+                let note = line.note.as_deref().map(|note| xformat!("  {note}"));
+                let key = others
+                    .get(line.doc.wrapping_sub(1))
+                    .map(|(key, _)| xformat!("  in {key}"));
+                let texts = [(&*line.path, Style::new())]
+                    .into_iter()
+                    .chain(note.as_deref().map(|note| (note, gray)))
+                    .chain(key.as_deref().map(|key| (key, gray)));
+                render_line(texts, buf, x, y, width);
+                y += 1;
+            }
+            found.highlight_current_line(buf, |cell| {
+                cell.bg = theme().cursor_line_bg();
+            });
+        } else {
+            let text = match self.list {
+                _ if param.traits.is_empty() => "No trait bound on the parameter.",
+                List::Types => "No type meets the trait bounds.",
+                List::Returning => "No function returns a type that meets the trait bounds.",
+                List::Accepting => "No function accepts a type that meets the trait bounds.",
+            };
+            render_line([(text, gray)], buf, x, y, width);
+        }
+
+        let param_key = keymap.hint(Scope::PageCandidates, Action::SwitchPanel);
+        let list_key = keymap.hint(Scope::PageCandidates, Action::SwitchList);
+        let text = xformat!(
+            " Parameter {} / {} ({param_key} for next) / {list_key} for next list / Got {} ",
+            self.current + 1,
+            self.params.len(),
+            found.total_len()
+        );
+        self.border.render_only_bottom_left_text(buf, &text, 0);
+    }
+
+    pub fn found(&mut self) -> &mut Scroll<Listed> {
+        &mut self.found
+    }
+
+    /// Set the cursor on the line by a left click.
+    /// Returns false if the position is out of the popup.
+    pub fn set_cursor_on_screen(&mut self, position: (u16, u16)) -> bool {
+        if !self.border.area().contains(position.into()) {
+            return false;
+        }
+        self.found.force_line_on_screen(position.1);
+        true
+    }
+}
+
+/// List candidate types for generic parameters in the Page.
+impl Page {
+    pub fn is_listing_candidates(&self) -> bool {
+        self.candidates.is_shown()
+    }
+
+    pub fn candidates(&mut self) -> &mut CandidateTypes {
+        &mut self.candidates
+    }
+
+    /// Show candidates for the first parameter with trait bounds on the item in declaration.
+    pub fn show_candidates(&mut self) {
+        if let Some(id) = self.content.inner.decl_id() {
+            self.open_candidates(&id, None);
+        }
+    }
+
+    /// Show candidates for the parameter of the item. The popup is not shown if the
+    /// item has no generic type parameter, or doesn't have the named one.
+    pub(super) fn open_candidates(&mut self, id: &Id, name: Option<&str>) {
+        let doc = self.outline.display_ref().lines.doc();
+        let params = generic_bounds(id, &doc);
+        let current = match name {
+            Some(name) => params.iter().position(|p| p.name == name),
+            None => (!params.is_empty()).then(|| {
+                params
+                    .iter()
+                    .position(|p| !p.traits.is_empty())
+                    .unwrap_or(0)
+            }),
+        };
+        let Some(current) = current else {
+            info!(path = %doc.path(id), name, "no generic type parameter to list candidates for");
+            return;
+        };
+        let candidates = &mut self.candidates;
+        candidates.item = Some(*id);
+        candidates.params = params;
+        candidates.current = current;
+        candidates.show = true;
+        self.update_candidates();
+    }
+
+    fn update_candidates(&mut self) {
+        let doc = self.outline.display_ref().lines.doc();
+        let mut docs = Vec::with_capacity(self.others.len() + 1);
+        docs.push(&doc);
+        docs.extend(self.others.iter().map(|(_, other)| other));
+        self.candidates.update(&docs);
+    }
+
+    /// List candidates for the next generic parameter.
+    pub fn next_candidate_param(&mut self) {
+        let candidates = &mut self.candidates;
+        if candidates.params.len() > 1 {
+            candidates.current = (candidates.current + 1) % candidates.params.len();
+            self.update_candidates();
+        }
+    }
+
+    /// Switch between listing candidates in current doc and in all loaded docs.
+    pub fn switch_candidate_source(&mut self) {
+        self.candidates.all_docs = !self.candidates.all_docs;
+        self.update_candidates();
+    }
+
+    /// Switch among listing types, functions returning them and functions accepting them.
+    pub fn switch_candidate_list(&mut self) {
+        self.candidates.list = self.candidates.list.next();
+        self.update_candidates();
+    }
+
+    /// Close the popup and jump to the type or function under the cursor. For an item in
+    /// another doc, the Page switches to that doc first.
+    pub fn confirm_candidate(&mut self) {
+        self.candidates.close();
+        let Some(found) = self.candidates.found.get_line_of_current_cursor() else {
+            return;
+        };
+        let Some(id) = found.id else {
+            info!(path = %found.path, "the type is not an item to jump to");
+            return;
+        };
+        let item = ItemPath {
+            id,
+            path: found.path.clone(),
+            outer: found.outer,
+        };
+        let other = found.doc.checked_sub(1);
+        self.record_jump(|page| {
+            if let Some(idx) = other {
+                page.switch_to_other_doc(idx);
+            }
+            page.jump_to_item(&item);
+        });
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    color::{DECLARATION_BORDER, JUMP, NEW},
+    color::{theme, NEW},
     ui::{
         render_line,
         scrollable::{Headings, ScrollText},
@@ -15,8 +15,9 @@ use std::ops::Range;
 use term_rustdoc::{
     tree::{CrateDoc, IDMap},
     type_name::{DeclarationLine, DeclarationLines},
+    util::XString,
 };
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 #[derive(Default)]
 pub(super) struct ContentInner {
@@ -61,6 +62,7 @@ impl ContentInner {
             self.decl.display.render(buf);
         }
         self.md.render(buf);
+        self.md.highlight_selected_link(buf);
     }
 
     pub fn content(&mut self) -> &mut ScrollText {
@@ -80,8 +82,31 @@ impl ContentInner {
         self.md.lines.reset_doc();
     }
 
+    /// Ids in the declaration or intra-doc links in the markdown.
     pub fn jumpable_id(&self, x: u16, y: u16) -> Option<Id> {
-        self.decl.display.jumpable_id(x, y)
+        self.decl
+            .display
+            .jumpable_id(x, y)
+            .or_else(|| self.md.jumpable_id(x, y))
+    }
+
+    pub fn visible_item_link(&self) -> Option<Id> {
+        self.md.visible_item_link()
+    }
+
+    pub fn select_item_link(&mut self, forward: bool) {
+        self.md.select_item_link(forward);
+    }
+
+    /// The item of the declaration.
+    pub fn decl_id(&self) -> Option<Id> {
+        self.decl.display.id
+    }
+
+    /// The word in the declaration that can be a generic parameter, with the item id.
+    pub fn decl_word(&self, x: u16, y: u16) -> Option<(Id, XString)> {
+        let word = self.decl.display.word(x, y)?;
+        Some((self.decl.display.id?, word))
     }
 }
 
@@ -98,7 +123,7 @@ impl Default for Declaration {
                 Block::new()
                     .borders(Borders::ALL)
                     .border_type(BorderType::QuadrantOutside)
-                    .border_style(DECLARATION_BORDER),
+                    .border_style(theme().declaration_border),
                 Rect::default(),
             ),
         }
@@ -109,6 +134,8 @@ impl Default for Declaration {
 struct DeclarationInner {
     inner: Scroll<DeclarationLines>,
     jumpable_ids: Vec<JumpableId>,
+    /// The item being declared.
+    id: Option<Id>,
 }
 
 #[derive(Debug)]
@@ -133,9 +160,12 @@ impl DeclarationInner {
         } = self.inner.area;
         let width = width as usize;
         for line in self.inner.all_lines() {
-            let line = line
-                .iter()
-                .map(|tt| (tt.text.as_str(), if tt.id.is_some() { JUMP } else { NEW }));
+            let line = line.iter().map(|tt| {
+                (
+                    tt.text.as_str(),
+                    if tt.id.is_some() { theme().jump } else { NEW },
+                )
+            });
             render_line(line, buf, x, y, width);
             y += 1;
         }
@@ -174,6 +204,48 @@ impl DeclarationInner {
             .iter()
             .find_map(|jump| (jump.y == y && jump.x.contains(&x)).then_some(jump.id))
     }
+
+    /// The identifier under the position in plain text, i.e. not a jumpable path.
+    fn word(&self, x: u16, y: u16) -> Option<XString> {
+        let area = self.inner.area;
+        let x = x.checked_sub(area.x)? as usize;
+        let y = y.checked_sub(area.y)? as usize;
+        let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+        let mut col = 0;
+        for tt in self.inner.all_lines().get(y)?.iter() {
+            let width = tt.text.width();
+            if x >= col + width {
+                col += width;
+                continue;
+            }
+            if tt.id.is_some() {
+                return None;
+            }
+            let chars: Vec<char> = tt.text.chars().collect();
+            let mut pos = 0;
+            for c in &chars {
+                let w = c.width().unwrap_or(0);
+                if col + w > x {
+                    break;
+                }
+                col += w;
+                pos += 1;
+            }
+            if !chars.get(pos).copied().is_some_and(is_ident) {
+                return None;
+            }
+            let start = chars[..pos]
+                .iter()
+                .rposition(|&c| !is_ident(c))
+                .map_or(0, |p| p + 1);
+            let end = chars[pos..]
+                .iter()
+                .position(|&c| !is_ident(c))
+                .map_or(chars.len(), |p| pos + p);
+            return Some(chars[start..end].iter().collect());
+        }
+        None
+    }
 }
 
 /// No need to query state for previous line.
@@ -186,14 +258,15 @@ impl LineState for DeclarationLine {
 }
 
 impl Declaration {
-    fn update_decl(&mut self, id: &Id, map: &IDMap, width: u16) {
+    fn update_decl(&mut self, id: &Id, map: &IDMap, _width: u16) {
         let lines = DeclarationLines::new(id, map);
+        self.display.id = Some(*id);
         if lines.is_empty() {
             self.display.scroll_text().lines = Default::default();
             self.display.jumpable_ids = Vec::new();
         } else {
             self.display.update_decl(lines);
-            // self.display.rust_code(&code, width);
+            // self.display.rust_code(&code, _width);
         }
     }
 
@@ -0,0 +1,105 @@
+use super::Page;
+use crate::database::{CrateReq, PkgKey};
+use rustdoc_types::Id;
+use std::mem;
+use term_rustdoc::tree::{CrateDoc, Tag};
+
+/// Jump between the doc shown on the Page and other loaded docs.
+impl Page {
+    /// Docs of external crates that items in current doc can jump into, and
+    /// dependencies that can't be jumped into because their docs are not cached.
+    pub fn set_other_docs(&mut self, others: Vec<(PkgKey, CrateDoc)>, missing: Vec<CrateReq>) {
+        info!(
+            others = ?others.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+            ?missing,
+            "Page can jump into other docs"
+        );
+        if !missing.is_empty() {
+            let deps: Vec<_> = missing
+                .iter()
+                .map(|(name, req)| format!("{name} {req}"))
+                .collect();
+            self.notice = Some(format!("Docs not cached for {}", deps.join(", ")));
+        }
+        self.others = others;
+        self.missing = missing;
+        self.candidates.forget_other_docs();
+    }
+
+    /// Jump to the item in current doc, or switch to the loaded doc of the external
+    /// crate in which the item is defined and jump to it.
+    pub fn jump_to_id_across_crates(&mut self, id: &Id) {
+        let doc = self.outline.display_ref().lines.doc();
+        let Some((name, summary)) = doc.external_item(id) else {
+            self.jump_to_id(id);
+            return;
+        };
+        let path = summary.path.join("::");
+        let found = self
+            .others
+            .iter()
+            .enumerate()
+            .find_map(|(idx, (_, other))| {
+                if other.crate_name() != name {
+                    return None;
+                }
+                Some((idx, other.find_local_item(&summary.path, summary.kind)?))
+            });
+        let Some((idx, target)) = found else {
+            info!(
+                krate = name,
+                path, "the external item is not found in loaded docs"
+            );
+            let missing = self.missing.iter().find(|(dep, _)| dep == name);
+            self.notice = Some(match missing {
+                Some((dep, req)) => format!("Can't jump to {path}: doc of {dep} {req} not cached"),
+                None => format!("Can't jump to {path}: doc of {name} not loaded"),
+            });
+            return;
+        };
+        self.switch_to_other_doc(idx);
+        // items may be folded in the fresh outline
+        self.outline_fold_expand_all();
+        self.jump_to_id(&target);
+        info!(krate = name, path, "jumped into the external crate");
+    }
+
+    /// Show another loaded doc on the Page, and current doc becomes one of other docs.
+    /// The history is kept to go back to current doc.
+    pub(super) fn switch_to_other_doc(&mut self, idx: usize) {
+        let (key, doc) = self.others.swap_remove(idx);
+        let mut others = mem::take(&mut self.others);
+        if let Some(current) = self.pkg_key.take() {
+            others.push((current, self.outline.display_ref().lines.doc()));
+        }
+        match Page::new(key, doc, self.area) {
+            Ok(page) => {
+                let history = mem::take(&mut self.history);
+                *self = page;
+                self.others = others;
+                self.history = history;
+            }
+            Err(err) => error!("Failed to switch to another doc:\n{err}"),
+        }
+    }
+
+    /// Jump to the supertrait under the outline cursor in the tree of trait items,
+    /// which may be defined in another loaded doc.
+    /// Returns false if the cursor is not on a supertrait.
+    pub(super) fn follow_supertrait(&mut self) -> bool {
+        let outline = self.outline.display_ref();
+        let Some(line) = outline.get_line_of_current_cursor() else {
+            return false;
+        };
+        let (Tag::Supertrait, Some(id)) = (line.tag, line.id) else {
+            return false;
+        };
+        self.record_jump(|page| {
+            page.navi.reset_navi_outline();
+            page.outline.reset_to_module_tree();
+            page.outline_fold_expand_all();
+            page.jump_to_id_across_crates(&id);
+        });
+        true
+    }
+}
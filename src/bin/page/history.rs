@@ -0,0 +1,123 @@
+use super::{navi::NaviAction, Page};
+use crate::database::PkgKey;
+use rustdoc_types::Id;
+
+/// At most this number of positions are remembered for going back.
+const MAX_HISTORY: usize = 64;
+
+/// Where the Page was: the doc, the outline and scroll offsets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pkg_key: Option<PkgKey>,
+    /// The item under the outline cursor.
+    id: Option<Id>,
+    /// The outer item and the action for inner item outline, or None for module tree.
+    setu: Option<(Id, NaviAction)>,
+    outline_start: usize,
+    outline_cursor: u16,
+    content_start: usize,
+}
+
+impl Position {
+    /// Positions are regarded as the same place if they show the same item.
+    fn is_same_place(&self, other: &Position) -> bool {
+        self.pkg_key == other.pkg_key && self.id == other.id && self.setu == other.setu
+    }
+}
+
+/// Back/forward stacks of positions.
+#[derive(Debug, Default)]
+pub struct History {
+    back: Vec<Position>,
+    forward: Vec<Position>,
+}
+
+impl Page {
+    fn position(&self) -> Position {
+        let outline = self.outline.display_ref();
+        Position {
+            pkg_key: self.pkg_key.clone(),
+            id: outline.get_id(),
+            setu: self.outline.inner.setu(),
+            outline_start: outline.start,
+            outline_cursor: outline.cursor.y,
+            content_start: self.content.inner.md_ref().start,
+        }
+    }
+
+    /// Run a jump and remember the position before it if the Page moves to another item.
+    pub(super) fn record_jump(&mut self, jump: impl FnOnce(&mut Page)) {
+        let before = self.position();
+        jump(self);
+        if before.is_same_place(&self.position()) {
+            return;
+        }
+        let history = &mut self.history;
+        if history.back.len() == MAX_HISTORY {
+            history.back.remove(0);
+        }
+        history.back.push(before);
+        history.forward.clear();
+    }
+
+    /// Go back to the position before last jump.
+    pub fn history_back(&mut self) {
+        if let Some(pos) = self.history.back.pop() {
+            let current = self.position();
+            self.restore_position(pos);
+            self.history.forward.push(current);
+        }
+    }
+
+    /// Go forward to the position that was left by going back.
+    pub fn history_forward(&mut self) {
+        if let Some(pos) = self.history.forward.pop() {
+            let current = self.position();
+            self.restore_position(pos);
+            self.history.back.push(current);
+        }
+    }
+
+    fn restore_position(&mut self, pos: Position) {
+        if pos.pkg_key != self.pkg_key {
+            let found = self
+                .others
+                .iter()
+                .position(|(key, _)| Some(key) == pos.pkg_key.as_ref());
+            let Some(idx) = found else {
+                error!(pkg_key = ?pos.pkg_key, "the doc in history is no longer loaded");
+                return;
+            };
+            self.switch_to_other_doc(idx);
+        }
+
+        self.navi.reset_navi_outline();
+        self.outline.reset_to_module_tree();
+        if let Some((outer, action)) = pos.setu {
+            self.outline.set_setu_id(outer);
+            self.outline.action(action);
+            if let Some(doc) = self.content.inner.md_ref().doc_ref() {
+                self.navi.set_item_inner(Some(outer), doc);
+            }
+            self.navi.set_outline_action(action);
+        }
+        self.update_area_inner(self.area);
+
+        let outline = self.outline();
+        outline.start = pos.outline_start;
+        outline.set_cursor(pos.outline_cursor);
+        if outline.get_id() == pos.id {
+            self.update_content();
+        } else if let Some(id) = pos.id {
+            // the outline has been folded or expanded since then
+            if self.outline.is_module_tree() {
+                self.outline_fold_expand_all();
+            }
+            self.jump_to_id(&id);
+        }
+
+        let content = self.content();
+        content.start = pos.content_start.min(content.total_len().saturating_sub(1));
+        info!(?pos, "restored the Page position from history");
+    }
+}
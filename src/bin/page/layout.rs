@@ -1,5 +1,5 @@
 use super::{navi::navi_outline_width, Page, Panel, Surround};
-use crate::color::SET;
+use crate::color::theme;
 use ratatui::{
     prelude::{Constraint, Direction, Layout, Rect},
     widgets::{Block, BorderType, Borders},
@@ -48,7 +48,7 @@ impl Page {
             .border_type(BorderType::Thick);
         let outline_border = Surround::new(
             if matches!(self.current, None | Some(Panel::Outline)) {
-                outline_border.style(SET)
+                outline_border.style(theme().focused_panel)
             } else {
                 outline_border
             },
@@ -3,15 +3,25 @@ use self::{
     panel::Panel,
 };
 use crate::{
-    database::PkgKey,
-    ui::{scrollable::ScrollTreeLines, Surround},
+    color::theme,
+    database::{CrateReq, PkgKey},
+    ui::{render_line, scrollable::ScrollTreeLines, Surround},
     Result,
 };
-use ratatui::prelude::{Buffer, Rect, Widget};
+use ratatui::{
+    prelude::{Buffer, Rect, Widget},
+    widgets::Clear,
+};
 use rustdoc_types::Id;
-use term_rustdoc::tree::CrateDoc;
+use term_rustdoc::{tree::CrateDoc, util::XString};
 
+/// concrete types that meet the trait bounds on generic parameters
+mod candidates;
 mod content;
+/// jump into other loaded crates
+mod cross_crate;
+/// back/forward navigation between jumps
+mod history;
 mod layout;
 mod navi;
 mod outline;
@@ -20,6 +30,10 @@ mod page_fold;
 /// scroll up/down behavior and with what offset
 mod page_scroll;
 mod panel;
+/// fuzzy search items in the whole crate
+mod search;
+/// save and restore the Page state across sessions
+mod session;
 
 #[derive(Default, Debug)]
 pub struct Page {
@@ -29,6 +43,15 @@ pub struct Page {
     current: Option<Panel>,
     pkg_key: Option<PkgKey>,
     area: Rect,
+    search: search::ItemSearch,
+    candidates: candidates::CandidateTypes,
+    /// Loaded docs of external crates, usually direct dependencies.
+    others: Vec<(PkgKey, CrateDoc)>,
+    /// External crates whose docs in the versions used are not cached.
+    missing: Vec<CrateReq>,
+    /// A message shown at the bottom of the content until the next key press.
+    notice: Option<String>,
+    history: history::History,
 }
 
 impl Page {
@@ -44,17 +67,47 @@ impl Page {
             area,
             pkg_key: Some(pkg_key),
             navi: Default::default(),
+            search: Default::default(),
+            candidates: Default::default(),
+            others: Vec::new(),
+            missing: Vec::new(),
+            notice: None,
+            history: Default::default(),
         };
         page.update_area_inner(area);
         info!(?area, "Page ready");
         Ok(page)
     }
 
-    #[allow(clippy::single_match)]
     pub fn double_click(&mut self) {
-        match self.current {
-            Some(Panel::Outline) => self.outline_fold_expand_toggle(),
-            _ => {}
+        if self.is_searching() {
+            self.confirm_search();
+            return;
+        }
+        if self.is_listing_candidates() {
+            self.confirm_candidate();
+            return;
+        }
+        if matches!(self.current, Some(Panel::Outline)) && !self.follow_supertrait() {
+            self.outline_fold_expand_toggle();
+        }
+    }
+
+    /// The pkg and the path of the item under the outline cursor.
+    pub fn current_item(&self) -> Option<(PkgKey, XString)> {
+        let outline = self.outline.display_ref();
+        let id = outline.get_id()?;
+        let path = outline.lines.doc_ref().path(&id);
+        Some((self.pkg_key.clone()?, path))
+    }
+
+    /// Jump to the item by its full path, e.g. for a bookmark.
+    pub fn jump_to_path(&mut self, path: &str) {
+        let doc = self.outline.display_ref().lines.doc();
+        let items = doc.dmodule().item_paths(&doc);
+        match items.iter().find(|item| item.path == path) {
+            Some(item) => self.record_jump(|page| page.jump_to_item(item)),
+            None => error!(path, "the item is not found in the doc"),
         }
     }
 
@@ -62,7 +115,32 @@ impl Page {
         self.area.height == 0 || self.area.width == 0
     }
 
-    /// Drop the data when PkgKey matches.
+    pub fn clear_notice(&mut self) {
+        self.notice = None;
+    }
+
+    /// The notice covers the last line of the content.
+    fn render_notice(&self, buf: &mut Buffer) {
+        let (Some(notice), area) = (&self.notice, self.content.border.area()) else {
+            return;
+        };
+        if area.height == 0 {
+            return;
+        }
+        let y = area.bottom() - 1;
+        Clear.render(
+            Rect {
+                y,
+                height: 1,
+                ..area
+            },
+            buf,
+        );
+        let line = Some((notice.as_str(), theme().failed));
+        render_line(line, buf, area.x, y, area.width as usize);
+    }
+
+    /// Drop the data when PkgKey matches, or forget the matched doc in other docs.
     pub fn drop(&mut self, pkg_key: &PkgKey) {
         if self
             .pkg_key
@@ -71,6 +149,9 @@ impl Page {
             .unwrap_or(false)
         {
             *self = Page::default();
+        } else {
+            self.others.retain(|(key, _)| key != pkg_key);
+            self.candidates.forget_other_docs();
         }
     }
 }
@@ -83,6 +164,15 @@ impl Widget for &mut Page {
         self.content.border.render(buf);
         self.content.inner.render(buf);
         self.navi.render(buf, self.content.inner.md_ref());
+        self.render_notice(buf);
+        // popups are drawn last over the page
+        if self.search.is_shown() {
+            self.search.update_area(area);
+            self.search.render(buf);
+        } else if self.candidates.is_shown() {
+            self.candidates.update_area(area);
+            self.candidates.render(buf, &self.others);
+        }
         debug!("Page rendered");
     }
 }
@@ -167,4 +257,12 @@ impl Content {
     fn jumpable_id(&self, x: u16, y: u16) -> Option<Id> {
         self.inner.jumpable_id(x, y)
     }
+
+    fn visible_item_link(&self) -> Option<Id> {
+        self.inner.visible_item_link()
+    }
+
+    fn select_item_link(&mut self, forward: bool) {
+        self.inner.select_item_link(forward);
+    }
 }
@@ -48,6 +48,10 @@ impl Navigation {
         self.display.outline.set_cursor_back_to_home();
     }
 
+    pub fn set_outline_action(&mut self, action: NaviAction) {
+        self.display.outline.set_action(action);
+    }
+
     pub fn update_area(&mut self, border: Surround) {
         let inner = border.inner();
         let [heading, outline] = split(inner);
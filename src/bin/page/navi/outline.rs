@@ -1,6 +1,6 @@
 #![allow(clippy::redundant_static_lifetimes)]
 use crate::{
-    color::{BG_CURSOR_LINE, FG_CURSOR_LINE, NEW},
+    color::{theme, NEW},
     ui::{render_line, LineState, Scroll, Surround},
 };
 use ratatui::{
@@ -78,12 +78,14 @@ const STRUCT: &'static [NaviAction] = &[
     NaviAction::Item,
     NaviAction::StructInner,
     NaviAction::ITABImpls,
+    NaviAction::TypeUsages,
     NaviAction::BackToHome,
 ];
 const ENUM: &'static [NaviAction] = &[
     NaviAction::Item,
     NaviAction::EnumInner,
     NaviAction::ITABImpls,
+    NaviAction::TypeUsages,
     NaviAction::BackToHome,
 ];
 const TRAIT: &'static [NaviAction] = &[
@@ -100,6 +102,7 @@ pub enum NaviAction {
     TraitAssociated,
     TraitImplementors,
     ITABImpls,
+    TypeUsages,
     Item,
     #[default]
     BackToHome,
@@ -113,6 +116,7 @@ impl NaviAction {
             NaviAction::TraitAssociated => "Associated",
             NaviAction::TraitImplementors => "Implementors",
             NaviAction::ITABImpls => "Impls",
+            NaviAction::TypeUsages => "Usages",
             NaviAction::Item => "Current Item",
             NaviAction::BackToHome => "Back To Home",
         }
@@ -168,6 +172,13 @@ impl NaviOutline {
         self.display.move_bottom_cursor();
     }
 
+    /// Move the cursor to the given action if it's available for the selected item.
+    pub fn set_action(&mut self, action: NaviAction) {
+        if let Some(pos) = self.inner_ref().iter().position(|&a| a == action) {
+            self.display.set_cursor(pos as u16);
+        }
+    }
+
     pub fn update_area(&mut self, area: Rect) {
         if let Some(inner) = self.border.update_area(area) {
             self.display.area = inner;
@@ -197,9 +208,10 @@ impl NaviOutline {
                 render_line(line, buf, x, y, width);
                 y += 1;
             }
+            let theme = theme();
             self.display.highlight_current_line(buf, |cell| {
-                cell.bg = BG_CURSOR_LINE;
-                cell.fg = FG_CURSOR_LINE;
+                cell.bg = theme.cursor_line_bg();
+                cell.fg = theme.cursor_line_fg();
             });
         }
     }
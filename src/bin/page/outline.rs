@@ -52,6 +52,11 @@ impl OutlineInner {
         }
     }
 
+    /// The module tree, even if an inner item tree is displayed.
+    pub fn modules_ref(&self) -> &ScrollTreeLines {
+        &self.modules
+    }
+
     pub fn update_area(&mut self, area: Rect) {
         self.modules.area = area;
         self.setu.update_area(area);
@@ -71,6 +76,15 @@ impl OutlineInner {
         self.setu.outer_item = id;
     }
 
+    /// The outer item and the action that generate current inner item tree.
+    /// None for module tree.
+    pub fn setu(&self) -> Option<(Id, NaviAction)> {
+        match self.kind {
+            OutlineKind::Modules => None,
+            OutlineKind::InnerItem => Some((self.setu.outer_item, self.setu.action)),
+        }
+    }
+
     pub fn action(&mut self, action: NaviAction) {
         match action {
             NaviAction::BackToHome => self.back_to_home(),
@@ -107,6 +121,7 @@ pub enum OutlineKind {
 /// This also supports focus on a module, but not very much designed.
 pub struct Setu {
     outer_item: Id,
+    action: NaviAction,
     display: ScrollTreeLines,
 }
 
@@ -114,6 +129,7 @@ impl Default for Setu {
     fn default() -> Self {
         Setu {
             outer_item: Id(0),
+            action: NaviAction::Item,
             display: Default::default(),
         }
     }
@@ -132,6 +148,7 @@ impl Setu {
             let dmod = map.dmodule();
             match action {
                 NaviAction::ITABImpls => dmod.impl_tree(id, map),
+                NaviAction::TypeUsages => dmod.usage_tree(id, map),
                 NaviAction::Item => dmod.item_inner_tree(id, map),
                 NaviAction::TraitAssociated => dmod.associated_item_tree(id, map),
                 NaviAction::TraitImplementors => dmod.implementor_tree(id, map),
@@ -144,6 +161,7 @@ impl Setu {
             error!("{path} generated unexpected empty TreeLines");
         }
         // self.update_area(modules.area);
+        self.action = action;
         self.display.start = 0;
         self.display.cursor.y = 0;
         Some(())
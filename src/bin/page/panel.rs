@@ -1,4 +1,4 @@
-use crate::color::{NEW, SET};
+use crate::color::{theme, NEW};
 
 #[derive(Debug)]
 pub enum Panel {
@@ -20,7 +20,7 @@ impl super::Page {
                     self.content.border.block_mut(),
                     self.navi.border().block_mut(),
                 );
-                *block.$a = block.$a.clone().style(SET);
+                *block.$a = block.$a.clone().style(theme().focused_panel);
                 *block.$b = block.$b.clone().style(NEW);
                 *block.$c = block.$c.clone().style(NEW);
                 Some(Panel::$var)
@@ -29,12 +29,16 @@ impl super::Page {
         let position = (x, y).into();
         // Block area covers border and its inner
         self.current = if self.outline.border.area().contains(position) {
-            self.outline().set_cursor(y);
-            self.update_content();
+            self.record_jump(|page| {
+                page.outline().set_cursor(y);
+                page.update_content();
+            });
             set!(outline)
         } else if self.content.border.area().contains(position) {
             if let Some(id) = self.content.jumpable_id(x, y) {
-                self.jump_to_id(&id);
+                self.record_jump(|page| page.jump_to_id_across_crates(&id));
+            } else if let Some((id, word)) = self.content.inner.decl_word(x, y) {
+                self.open_candidates(&id, Some(&word));
             }
             set!(content)
         } else if self.navi.contains(position) {
@@ -54,6 +58,25 @@ impl super::Page {
         info!(?self.current);
     }
 
+    /// Follow the selected intra-doc link on screen, or the first one if none is selected,
+    /// when the content panel is focused, otherwise follow the supertrait or fold or expand
+    /// the module under the outline cursor.
+    pub fn enter(&mut self) {
+        if matches!(self.current, Some(Panel::Content)) {
+            if let Some(id) = self.content.visible_item_link() {
+                self.record_jump(|page| page.jump_to_id_across_crates(&id));
+            }
+        } else if !self.follow_supertrait() {
+            self.outline_fold_expand_toggle();
+        }
+    }
+
+    /// Select the next or previous intra-doc link on screen, and focus on the content panel.
+    pub fn select_item_link(&mut self, forward: bool) {
+        self.current = Some(Panel::Content);
+        self.content.select_item_link(forward);
+    }
+
     pub fn set_next_action(&mut self) {
         let next_action = self.navi.next_action();
         debug!(?next_action);
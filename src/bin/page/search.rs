@@ -0,0 +1,507 @@
+//! A popup on the Page to fuzzy search items in the whole crate by their paths,
+//! by the contents of their documentation, or search functions by their signatures.
+
+use super::navi::NaviAction;
+use crate::{
+    color::theme,
+    config::{keymap, Action, Scope},
+    frame::centered_rect,
+    fuzzy::Fuzzy,
+    ui::{render_line, tail_within_width, LineState, Scroll, Surround},
+};
+use ratatui::{
+    layout::Alignment,
+    prelude::{Buffer, Constraint, Layout, Line, Rect, Style},
+    widgets::{Block, Borders, Clear, Widget},
+};
+use rustdoc_types::ItemEnum;
+use term_rustdoc::{
+    tree::{CrateDoc, ItemPath},
+    type_name::{fn_signature, SignatureIndex, SignatureQuery},
+    util::{xformat, XString},
+};
+
+#[derive(Default)]
+pub struct ItemSearch {
+    input: String,
+    results: Scroll<Candidates>,
+    /// Show the popup or not.
+    show: bool,
+    input_area: Rect,
+    border: Surround,
+    full: Rect,
+}
+
+impl std::fmt::Debug for ItemSearch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ItemSearch")
+            .field("input", &self.input)
+            .field("show", &self.show)
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug)]
+enum Source {
+    #[default]
+    ItemPath,
+    Documentation,
+    Signature,
+}
+
+#[derive(Default)]
+pub struct Candidates {
+    /// All the items as candidates, initialized the first time the popup shows up.
+    items: Vec<ItemPath>,
+    /// Non-empty lines in the documentation of all the items.
+    doc_lines: Vec<DocLine>,
+    /// Indices of functions and methods in items.
+    fns: Vec<usize>,
+    doc: CrateDoc,
+    /// Built the first time functions are searched by signature.
+    signature: Option<SignatureIndex>,
+    source: Source,
+    /// Matched candidates.
+    filter: Vec<Hit>,
+    fuzzy: Option<Fuzzy>,
+}
+
+/// A line in the raw markdown documentation on an item.
+struct DocLine {
+    /// Index of the item in candidates.
+    item: usize,
+    text: XString,
+}
+
+impl Candidates {
+    fn new(doc: &CrateDoc) -> Self {
+        let items = doc.dmodule().item_paths(doc);
+        let doc_lines: Vec<_> = items
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, item)| Some((idx, doc.get_doc(&item.id)?)))
+            .flat_map(|(item, md)| {
+                md.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(move |line| DocLine {
+                        item,
+                        text: line.into(),
+                    })
+            })
+            .collect();
+        let fns: Vec<_> = items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                doc.get_item(&item.id)
+                    .is_some_and(|item| matches!(item.inner, ItemEnum::Function(_)))
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        info!(
+            "Collected {} items, {} functions and {} lines of documentation as search candidates",
+            items.len(),
+            fns.len(),
+            doc_lines.len()
+        );
+        let mut candidates = Candidates {
+            items,
+            doc_lines,
+            fns,
+            doc: doc.clone(),
+            fuzzy: Some(Fuzzy::new()),
+            ..Default::default()
+        };
+        candidates.force_all();
+        candidates
+    }
+
+    fn len(&self) -> usize {
+        match self.source {
+            Source::ItemPath => self.items.len(),
+            Source::Documentation => self.doc_lines.len(),
+            Source::Signature => self.fns.len(),
+        }
+    }
+
+    /// Switch to the next source. The signature index is built the first time
+    /// functions are searched by signature.
+    fn switch_source(&mut self) {
+        self.source = match self.source {
+            Source::ItemPath => Source::Documentation,
+            Source::Documentation => Source::Signature,
+            Source::Signature => Source::ItemPath,
+        };
+        if let (Source::Signature, None) = (self.source, &self.signature) {
+            info!("Build the signature index to search functions");
+            self.signature = Some(SignatureIndex::new(&self.doc));
+        }
+    }
+
+    fn force_all(&mut self) {
+        self.filter.clear();
+        match self.source {
+            Source::Signature => self.filter.extend(self.fns.iter().map(|&idx| Hit(idx))),
+            _ => self.filter.extend((0..self.len()).map(Hit)),
+        }
+    }
+
+    /// Functions are sorted by the number of extra arguments and then by paths.
+    /// An invalid query matches nothing.
+    fn search_signature(&mut self, pattern: &str) {
+        self.filter.clear();
+        let (Some(index), Some(query)) = (&self.signature, SignatureQuery::parse(pattern)) else {
+            return;
+        };
+        let doc = &self.doc;
+        let mut matched: Vec<_> = self
+            .fns
+            .iter()
+            .filter_map(|&idx| {
+                let extra = query.matches(&self.items[idx].id, doc, index)?;
+                Some((extra, idx))
+            })
+            .collect();
+        // items are already sorted by path, so the index breaks ties in path order
+        matched.sort_unstable();
+        self.filter
+            .extend(matched.into_iter().map(|(_, idx)| Hit(idx)));
+    }
+
+    fn update_search(&mut self, pattern: &str) {
+        struct Ele<'s>(&'s str, Hit);
+        impl AsRef<str> for Ele<'_> {
+            fn as_ref(&self) -> &str {
+                self.0
+            }
+        }
+        impl From<Ele<'_>> for Hit {
+            fn from(value: Ele<'_>) -> Self {
+                value.1
+            }
+        }
+
+        if pattern.is_empty() {
+            self.force_all();
+            return;
+        }
+        if let Source::Signature = self.source {
+            self.search_signature(pattern);
+            return;
+        }
+        if let Some(fuzzy) = &self.fuzzy {
+            fuzzy.parse(pattern);
+            match self.source {
+                Source::ItemPath => {
+                    let iter = self.items.iter().enumerate();
+                    let iter = iter.map(|(idx, item)| Ele(&item.path, Hit(idx)));
+                    fuzzy.match_list(iter, &mut self.filter);
+                }
+                Source::Documentation => {
+                    let iter = self.doc_lines.iter().enumerate();
+                    let iter = iter.map(|(idx, line)| Ele(&line.text, Hit(idx)));
+                    fuzzy.match_list(iter, &mut self.filter);
+                }
+                Source::Signature => (),
+            }
+        }
+    }
+
+    /// Returns the matched item and the matched documentation line if searching in docs.
+    fn get(&self, hit: Hit) -> Option<(&ItemPath, Option<&str>)> {
+        match self.source {
+            Source::ItemPath | Source::Signature => self.items.get(hit.0).map(|item| (item, None)),
+            Source::Documentation => {
+                let line = self.doc_lines.get(hit.0)?;
+                let item = self.items.get(line.item)?;
+                Some((item, Some(&*line.text)))
+            }
+        }
+    }
+
+    /// The signature of a matched function when searching by signature.
+    fn signature(&self, item: &ItemPath) -> Option<XString> {
+        match self.source {
+            Source::Signature => fn_signature(&item.id, &self.doc),
+            _ => None,
+        }
+    }
+
+    /// Find the row with the highest score for the current pattern.
+    fn best_matched_row(&self, rows: impl IntoIterator<Item = XString>) -> Option<usize> {
+        let fuzzy = self.fuzzy.as_ref()?;
+        rows.into_iter()
+            .enumerate()
+            .filter_map(|(row, text)| fuzzy.score(&text).filter(|x| *x > 0).map(|x| (x, row)))
+            // the first row wins in ties
+            .max_by_key(|(score, row)| (*score, std::cmp::Reverse(*row)))
+            .map(|(_, row)| row)
+    }
+}
+
+impl std::ops::Deref for Candidates {
+    type Target = [Hit];
+
+    fn deref(&self) -> &Self::Target {
+        &self.filter
+    }
+}
+
+/// Index of a matched candidate in items or documentation lines depending on the source.
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct Hit(usize);
+
+impl LineState for Hit {
+    type State = usize;
+
+    fn state(&self) -> Self::State {
+        self.0
+    }
+
+    fn is_identical(&self, state: &Self::State) -> bool {
+        self.0 == *state
+    }
+}
+
+impl ItemSearch {
+    pub fn is_shown(&self) -> bool {
+        self.show
+    }
+
+    /// Show the popup and collect the candidates if they haven't been collected.
+    pub fn open(&mut self, doc: &CrateDoc) {
+        if self.results.lines.items.is_empty() {
+            self.results.lines = Candidates::new(doc);
+        }
+        self.show = true;
+    }
+
+    pub fn close(&mut self) {
+        self.show = false;
+    }
+
+    pub fn update_area(&mut self, full: Rect) {
+        if self.full == full {
+            return;
+        }
+        self.full = full;
+        let outer = centered_rect(full, 80, 80);
+        let keymap = keymap();
+        let title = Line::from(format!(
+            " {} to jump / {} to close ",
+            keymap.hint(Scope::PageSearch, Action::Confirm),
+            keymap.hint(Scope::PageSearch, Action::Close)
+        ))
+        .alignment(Alignment::Right);
+        self.border = Surround::new(
+            Block::new().title_bottom(title).borders(Borders::ALL),
+            outer,
+        );
+        let [input, _, results] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .areas(self.border.inner());
+        self.input_area = input;
+        self.results.area = results;
+        self.set_cursor();
+    }
+
+    pub fn render(&self, buf: &mut Buffer) {
+        Clear.render(self.border.area(), buf);
+        self.border.render(buf);
+        let text = match self.results.lines.source {
+            Source::ItemPath => "Search Items By Path",
+            Source::Documentation => "Search Items By Documentation",
+            Source::Signature => "Search Functions By Signature",
+        };
+        let key = keymap().hint(Scope::PageSearch, Action::SwitchSearchSource);
+        let text = xformat!(" {text} ({key} to switch) ");
+        self.border.render_only_top_left_text(buf, &text, 0);
+
+        // input line
+        let Rect { x, y, width, .. } = self.input_area;
+        let width = width.saturating_sub(1) as usize;
+        // show end half if the input exceeds the width
+        let text = tail_within_width(&self.input, width);
+        let (x, _) = buf.set_stringn(x, y, text, width, Style::new());
+        let cursor = theme().cursor;
+        buf.set_stringn(x, y, " ", 1, cursor);
+
+        // matched items
+        let results = &self.results;
+        if let Some(lines) = results.visible_lines() {
+            let Rect {
+                x, mut y, width, ..
+            } = results.area;
+            let width = width as usize;
+            for &hit in lines {
+                match results.lines.get(hit) {
+                    Some((item, None)) => match results.lines.signature(item) {
+                        Some(sig) => {
+                            let line = [
+                                (&*item.path, Style::new()),
+                                ("  ", theme().pkg_version),
+                                (&*sig, theme().pkg_version),
+                            ];
+                            render_line(line, buf, x, y, width);
+                        }
+                        None => {
+                            render_line([(&*item.path, Style::new())], buf, x, y, width);
+                        }
+                    },
+                    Some((item, Some(text))) => {
+                        let line = [
+                            (&*item.path, theme().pkg_version),
+                            ("  ", theme().pkg_version),
+                        ];
+                        render_line(
+                            line.into_iter().chain([(text, Style::new())]),
+                            buf,
+                            x,
+                            y,
+                            width,
+                        );
+                    }
+                    None => (),
+                }
+                y += 1;
+            }
+            results.highlight_current_line(buf, |cell| {
+                cell.bg = theme().cursor_line_bg();
+            });
+        }
+
+        let text = xformat!(
+            " Got {} / Total {} ",
+            results.total_len(),
+            results.lines.len()
+        );
+        self.border.render_only_bottom_left_text(buf, &text, 0);
+    }
+
+    pub fn push_char(&mut self, ch: char) {
+        self.input.push(ch);
+        self.update_search();
+    }
+
+    pub fn pop_char(&mut self) {
+        self.input.pop();
+        self.update_search();
+    }
+
+    pub fn clear_input(&mut self) {
+        self.input.clear();
+        self.update_search();
+    }
+
+    pub fn switch_source(&mut self) {
+        self.results.lines.switch_source();
+        self.update_search();
+    }
+
+    fn update_search(&mut self) {
+        self.results.lines.update_search(&self.input);
+        self.results.start = 0;
+        self.set_cursor();
+    }
+
+    fn set_cursor(&mut self) {
+        if !self.results.check_if_can_return_to_previous_cursor() {
+            self.results.cursor.y = 0;
+        }
+    }
+
+    pub fn results(&mut self) -> &mut Scroll<Candidates> {
+        &mut self.results
+    }
+
+    /// Set the cursor on the line by a left click.
+    /// Returns false if the position is out of the popup.
+    pub fn set_cursor_on_screen(&mut self, position: (u16, u16)) -> bool {
+        if !self.border.area().contains(position.into()) {
+            return false;
+        }
+        self.results.force_line_on_screen(position.1);
+        true
+    }
+
+    /// The item the cursor points to, as well as the matched documentation line
+    /// if searching in documentation.
+    pub fn get_current_hit(&self) -> Option<(&ItemPath, Option<&str>)> {
+        let hit = self.results.get_line_of_current_cursor()?;
+        self.results.lines.get(*hit)
+    }
+}
+
+/// Search items in the Page.
+impl super::Page {
+    pub fn is_searching(&self) -> bool {
+        self.search.is_shown()
+    }
+
+    pub fn open_search(&mut self) {
+        let doc = self.outline.display_ref().lines.doc();
+        self.search.open(&doc);
+    }
+
+    pub fn search(&mut self) -> &mut ItemSearch {
+        &mut self.search
+    }
+
+    /// Close the popup and jump to the item under the cursor.
+    ///
+    /// When searching in documentation, the content panel is scrolled to
+    /// the line that matches the pattern best.
+    pub fn confirm_search(&mut self) {
+        const MARGIN: usize = 3;
+        self.search.close();
+        let Some((item, doc_line)) = self.search.get_current_hit() else {
+            return;
+        };
+        let (item, in_doc) = (item.clone(), doc_line.is_some());
+        self.record_jump(|page| page.jump_to_item(&item));
+        if !in_doc {
+            return;
+        }
+        // Lines in content panel are wrapped, so we can't use the raw line number in markdown.
+        let rows = self.content.inner.md_ref().all_lines().iter().map(|line| {
+            line.iter_text_style()
+                .map(|(text, _)| text)
+                .collect::<XString>()
+        });
+        if let Some(row) = self.search.results.lines.best_matched_row(rows) {
+            let limit = self
+                .content
+                .inner
+                .md_ref()
+                .total_len()
+                .saturating_sub(MARGIN);
+            self.content().start = row.saturating_sub(MARGIN).min(limit);
+            info!(row, path = %item.path, "scroll content to the matched line");
+        }
+    }
+
+    /// Jump to an item in module tree, or in the inner tree of its outer item.
+    pub(super) fn jump_to_item(&mut self, item: &ItemPath) {
+        // The item may be folded or invisible in current tree, so
+        // go back to the module tree with all nodes expanded.
+        self.navi.reset_navi_outline();
+        self.outline.reset_to_module_tree();
+        self.outline_fold_expand_all();
+        self.current = Some(super::Panel::Outline);
+
+        let Some(outer) = item.outer else {
+            self.jump_to_id(&item.id);
+            return;
+        };
+        self.jump_to_id(&outer);
+        self.outline.action(NaviAction::Item);
+        self.navi.set_outline_action(NaviAction::Item);
+        self.update_area_inner(self.area);
+        self.jump_to_id(&item.id);
+    }
+}
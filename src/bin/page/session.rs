@@ -0,0 +1,59 @@
+use super::Page;
+use crate::database::Session;
+
+impl Page {
+    /// The state to be restored on next start. None if no doc is shown.
+    ///
+    /// The module tree is saved even if an inner item tree is displayed, in which case
+    /// the outer item is under the cursor.
+    pub fn session(&self) -> Option<Session> {
+        let pkg = self.pkg_key.clone()?;
+        let modules = self.outline.inner.modules_ref();
+        let path = modules.get_id().map(|id| modules.lines.doc_ref().path(&id));
+        let content_start = if self.outline.is_module_tree() {
+            self.content.inner.md_ref().start
+        } else {
+            0
+        };
+        Some(Session {
+            pkg,
+            path,
+            fold: modules.lines.fold().clone(),
+            outline_start: modules.start,
+            outline_cursor: modules.cursor.y,
+            content_start,
+        })
+    }
+
+    /// Restore the state from last session on the newly created Page.
+    pub fn restore_session(&mut self, session: Session) {
+        if self.pkg_key.as_ref() != Some(&session.pkg) {
+            error!(?session.pkg, pkg_key = ?self.pkg_key, "the session doesn't belong to the doc");
+            return;
+        }
+        self.outline.reset_to_module_tree();
+        self.outline().lines.restore_fold(session.fold);
+        self.update_area_inner(self.area);
+
+        let outline = self.outline();
+        outline.start = session
+            .outline_start
+            .min(outline.total_len().saturating_sub(1));
+        outline.set_cursor(session.outline_cursor);
+        let current = outline.get_id().map(|id| outline.lines.doc_ref().path(&id));
+        match session.path {
+            Some(path) if current.as_ref() != Some(&path) => {
+                // the doc may be recompiled and the item moves
+                self.jump_to_path(&path);
+                return;
+            }
+            _ => self.update_content(),
+        }
+
+        let content = self.content();
+        content.start = session
+            .content_start
+            .min(content.total_len().saturating_sub(1));
+        info!(?session.pkg, "restored the last session");
+    }
+}
@@ -4,6 +4,7 @@ pub mod scrollable;
 mod surround;
 
 pub use scrollable::{
-    render_line, LineState, MarkdownAndHeading, Scroll, ScrollMarkdown, ScrollOffset, Scrollable,
+    render_line, tail_within_width, LineState, MarkdownAndHeading, Scroll, ScrollMarkdown,
+    ScrollOffset, Scrollable,
 };
 pub use surround::Surround;
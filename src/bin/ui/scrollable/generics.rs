@@ -2,7 +2,7 @@ use ratatui::{buffer::Buffer, style::Style};
 use rustdoc_types::Id;
 use std::ops::Deref;
 use term_rustdoc::{tree::TreeLine, util::XString};
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub trait Lines: Deref<Target = [Self::Line]> {
     type Line: LineState;
@@ -65,6 +65,19 @@ where
     used_width
 }
 
+/// The end part of the text that fits in the width, cut on a char boundary.
+/// This is used to show the latest input when the input exceeds the width.
+pub fn tail_within_width(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (idx, ch) in text.char_indices().rev() {
+        used += ch.width().unwrap_or(0);
+        if used > width {
+            return &text[idx + ch.len_utf8()..];
+        }
+    }
+    text
+}
+
 pub fn render_line_fill_gap<'t, I>(
     line: I,
     style: Style,
@@ -107,3 +120,13 @@ pub fn render_line_fill_gap<'t, I>(
         buf.set_stringn(x, y, gap_str, gap, style);
     }
 }
+
+#[test]
+fn tail_of_wide_chars() {
+    assert_eq!(tail_within_width("abc", 5), "abc");
+    assert_eq!(tail_within_width("abc", 2), "bc");
+    // each CJK char takes 2 columns and 3 bytes
+    assert_eq!(tail_within_width("a中文", 4), "中文");
+    assert_eq!(tail_within_width("a中文", 3), "文");
+    assert_eq!(tail_within_width("中文", 1), "");
+}
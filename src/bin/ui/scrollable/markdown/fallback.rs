@@ -3,13 +3,17 @@ use super::{
     parse::{self, Blocks},
     StyledText,
 };
+use crate::color::theme;
 use crate::{
     ui::scrollable::{generics::LineState, Scroll},
     Result,
 };
-use ratatui::style::Style;
+use ratatui::prelude::{Buffer, Rect, Style};
 use rustdoc_types::Id;
-use std::{fmt, ops::Deref};
+use std::{
+    fmt,
+    ops::{Deref, Range},
+};
 use term_rustdoc::{tree::CrateDoc, util::XString};
 use unicode_width::UnicodeWidthStr;
 
@@ -102,6 +106,8 @@ pub struct StyledLines {
     syntect: bool,
     lines: Vec<StyledLine>,
     blocks: Blocks,
+    /// The order of the selected intra-doc link, which is followed by Enter.
+    selected_link: Option<usize>,
     doc: Option<CrateDoc>,
 }
 
@@ -133,18 +139,24 @@ impl StyledLines {
     ///
     /// The Headings can still be empty because heading jumping isn't supported in syntect case.
     pub fn update_doc(&mut self, id: &Id, width: Option<f64>) -> Option<Headings> {
-        if let Some(doc) = &self.doc {
-            if let Some(doc) = doc.get_doc(id) {
-                return if let Some(width) = width {
-                    let (lines, blocks, headings) = parse::parse_doc(doc, width);
-                    self.lines = lines;
-                    self.blocks = blocks;
-                    Some(headings)
-                } else {
-                    warn!("no wrapping for markdown content");
-                    self.lines = parse::md(doc);
-                    Some(Headings::default())
-                };
+        if let Some(map) = &self.doc {
+            if let Some(item) = map.get_doc_item(id) {
+                if let Some(doc) = item.docs.as_deref() {
+                    return if let Some(width) = width {
+                        let links = item.links.iter().map(|(k, v)| (k.clone(), *v)).collect();
+                        let (lines, blocks, headings) = parse::parse_doc(doc, &links, width);
+                        self.lines = lines;
+                        self.blocks = blocks;
+                        self.selected_link = None;
+                        Some(headings)
+                    } else {
+                        warn!("no wrapping for markdown content");
+                        self.lines = parse::md(doc);
+                        self.blocks = Blocks::default();
+                        self.selected_link = None;
+                        Some(Headings::default())
+                    };
+                }
             }
         }
         self.reset_doc();
@@ -154,6 +166,13 @@ impl StyledLines {
     /// FIXME: cache queried doc to save parsing
     pub fn reset_doc(&mut self) {
         self.lines = Vec::new();
+        self.blocks = Blocks::default();
+        self.selected_link = None;
+    }
+
+    /// The item that the intra-doc link at the position points to.
+    pub fn item_link_at(&self, row: usize, col: usize) -> Option<Id> {
+        self.blocks.links.item_link_at(row, col)
     }
 
     pub fn toggle_sytect(&mut self) {
@@ -187,4 +206,68 @@ impl ScrollText {
     pub fn doc_ref(&self) -> Option<&CrateDoc> {
         self.lines.doc.as_ref()
     }
+
+    /// The item that the intra-doc link under the position on screen points to.
+    pub fn jumpable_id(&self, x: u16, y: u16) -> Option<Id> {
+        let col = x.checked_sub(self.area.x)? as usize;
+        let row = y.checked_sub(self.area.y)?;
+        if row >= self.area.height {
+            return None;
+        }
+        self.lines.item_link_at(self.start + row as usize, col)
+    }
+
+    fn visible_rows(&self) -> Range<usize> {
+        self.start..self.start + self.area.height as usize
+    }
+
+    /// The selected intra-doc link if it's visible, otherwise the first one in visible lines.
+    pub fn visible_item_link(&self) -> Option<Id> {
+        let links = &self.lines.blocks.links;
+        let rows = self.visible_rows();
+        self.lines
+            .selected_link
+            .and_then(|link| links.item_link_in(link, &rows))
+            .or_else(|| links.first_item_link_in(rows))
+    }
+
+    /// Select the next or previous intra-doc link in visible lines, which wraps around.
+    pub fn select_item_link(&mut self, forward: bool) {
+        let rows = self.visible_rows();
+        let visible = self.lines.blocks.links.item_links_in(&rows);
+        let current = self
+            .lines
+            .selected_link
+            .and_then(|link| visible.iter().position(|&l| l == link));
+        let len = visible.len();
+        let next = match (current, forward) {
+            _ if len == 0 => None,
+            (Some(pos), true) => Some((pos + 1) % len),
+            (Some(pos), false) => Some((pos + len - 1) % len),
+            (None, true) => Some(0),
+            (None, false) => Some(len - 1),
+        };
+        self.lines.selected_link = next.map(|pos| visible[pos]);
+    }
+
+    /// Set the bg of the selected intra-doc link in visible lines.
+    pub fn highlight_selected_link(&self, buf: &mut Buffer) {
+        let Some(link) = self.lines.selected_link else {
+            return;
+        };
+        let Rect { x, y, width, .. } = self.area;
+        let rows = self.visible_rows();
+        let bg = theme().cursor_line_bg();
+        for region in self.lines.blocks.links.item_link_regions(link) {
+            let row = region.row_start();
+            if !rows.contains(&row) {
+                continue;
+            }
+            let [start, end] = region.col_span();
+            let row = y + (row - self.start) as u16;
+            for col in start..end.min(width as usize) {
+                buf[(x + col as u16, row)].set_bg(bg);
+            }
+        }
+    }
 }
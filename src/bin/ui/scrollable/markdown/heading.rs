@@ -1,6 +1,6 @@
 use super::region::SelectedRegion;
 use crate::{
-    color::HEAD,
+    color::theme,
     ui::scrollable::{
         generics::{render_line, render_line_fill_gap, LineState},
         Scroll,
@@ -100,7 +100,15 @@ impl ScrollHeading {
                         .map(|l| content_end < l.jump.row_start())
                         .unwrap_or(true))
             {
-                render_line_fill_gap(Some(text), HEAD, buf, x, y, width, &mut gap_str);
+                render_line_fill_gap(
+                    Some(text),
+                    theme().navi_heading,
+                    buf,
+                    x,
+                    y,
+                    width,
+                    &mut gap_str,
+                );
             } else {
                 let style = line.line.style;
                 render_line(Some((text, style)), buf, x, y, width);
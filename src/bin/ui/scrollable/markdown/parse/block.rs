@@ -1,6 +1,7 @@
 use super::{line::Line, word::Word};
 use super::{segment_words, MetaTag};
-use ratatui::style::{Color, Modifier, Style};
+use crate::color::theme;
+use ratatui::style::Style;
 use std::fmt;
 use term_rustdoc::util::XString;
 
@@ -120,8 +121,7 @@ impl Block {
         for line in &mut self.lines {
             for word in &mut line.words {
                 word.tag = MetaTag::Heading(id);
-                word.style.fg = Some(Color::LightCyan);
-                word.style.add_modifier = Modifier::BOLD;
+                word.style = theme().md_heading;
             }
         }
     }
@@ -132,7 +132,7 @@ impl Block {
         for line in &mut self.lines {
             for word in &mut line.words {
                 word.tag = MetaTag::QuoteBlock;
-                word.style.fg = Some(Color::Rgb(186, 85, 211)); // #BA55D3
+                word.style = theme().quote_block;
             }
         }
     }
@@ -142,7 +142,7 @@ impl Block {
         for line in &mut self.lines {
             for word in &mut line.words {
                 word.tag = MetaTag::FootnoteSource;
-                word.style.fg = Some(Color::LightMagenta);
+                word.style = theme().footnote;
             }
         }
     }
@@ -1,11 +1,14 @@
-use super::{element::LINK, Block, Line, LinkTag, MetaTag, Word};
-use crate::ui::scrollable::markdown::{
-    fallback::StyledLine,
-    heading::Headings,
-    region::{LinkedRegions, SelectedRegion},
+use super::{Block, ItemLinks, Line, LinkTag, MetaTag, Word};
+use crate::{
+    color::theme,
+    ui::scrollable::markdown::{
+        fallback::StyledLine,
+        heading::Headings,
+        region::{ItemLinkRegion, LinkedRegions, SelectedRegion},
+    },
 };
-use ratatui::style::{Color, Style};
-use std::fmt;
+use rustdoc_types::Id;
+use std::{fmt, ops::Range};
 use term_rustdoc::util::{hashmap, xformat, HashMap, XString};
 use textwrap::wrap_algorithms::{wrap_optimal_fit, Penalties};
 
@@ -45,6 +48,7 @@ impl Blocks {
                 heading: Vec::new(),
                 links: Vec::with_capacity(8),
                 footnotes: hashmap(1),
+                ..Default::default()
             },
         }
     }
@@ -67,7 +71,7 @@ impl Blocks {
                     if let Some(word) = self.links.get_link(idx) {
                         let anchor = Word {
                             word: xformat!("[{idx}]:"),
-                            style: LINK,
+                            style: theme().link,
                             tag: MetaTag::Link(LinkTag::ReferenceLink(idx)),
                             trailling_whitespace: true,
                         };
@@ -80,7 +84,7 @@ impl Blocks {
                                 let end = width.min(link.len());
                                 let line = Word {
                                     word: link[..end].into(),
-                                    style: LINK,
+                                    style: theme().link,
                                     tag: MetaTag::Link(LinkTag::ReferenceLink(idx)),
                                     trailling_whitespace: false,
                                 };
@@ -150,6 +154,7 @@ impl WriteLines {
 
     fn split(mut self, links: &mut Links) -> Vec<StyledLine> {
         links.set_heading_regions(self.regions.take_headings());
+        links.item_regions = self.regions.take_item_links();
         self.lines
     }
 }
@@ -162,6 +167,12 @@ pub struct Links {
     // FIXME: replace this HashMap with Vec<(XString, Block)>,
     // and use the index as key/id like push_link returns.
     footnotes: HashMap<XString, Block>,
+    /// Intra-doc links used to resolve link destinations when parsing.
+    item_links: ItemLinks,
+    /// The number of intra-doc links parsed so far.
+    item_link_count: usize,
+    /// Where resolved intra-doc links are in the lines.
+    item_regions: Vec<ItemLinkRegion>,
 }
 
 impl Links {
@@ -180,15 +191,69 @@ impl Links {
     pub fn get_link(&self, idx: usize) -> Option<Word> {
         self.links.get(idx).map(|link| Word {
             word: link.clone(),
-            style: Style {
-                fg: Some(Color::Rgb(30, 144, 255)), // #1E90FF
-                ..Default::default()
-            },
+            style: theme().link,
             tag: MetaTag::Link(LinkTag::ReferenceLink(idx)),
             trailling_whitespace: false,
         })
     }
 
+    pub fn set_item_links(&mut self, item_links: &ItemLinks) {
+        self.item_links = item_links.clone();
+    }
+
+    /// Resolve the link destination to an item if it's an intra-doc link.
+    pub fn item_link(&self, link: &str) -> Option<Id> {
+        self.item_links.get(link).copied()
+    }
+
+    /// The order of a new intra-doc link, which tells links to the same item apart.
+    pub fn next_item_link(&mut self) -> usize {
+        let link = self.item_link_count;
+        self.item_link_count += 1;
+        link
+    }
+
+    /// The item that the intra-doc link at the position points to.
+    pub fn item_link_at(&self, row: usize, col: usize) -> Option<Id> {
+        self.item_regions
+            .iter()
+            .find_map(|r| r.region.contains(row, col).then_some(r.id))
+    }
+
+    /// The first intra-doc link in the rows.
+    pub fn first_item_link_in(&self, rows: Range<usize>) -> Option<Id> {
+        self.item_regions
+            .iter()
+            .find_map(|r| rows.contains(&r.region.row_start()).then_some(r.id))
+    }
+
+    /// The item of the intra-doc link if any part of the link is in the rows.
+    pub fn item_link_in(&self, link: usize, rows: &Range<usize>) -> Option<Id> {
+        self.item_regions
+            .iter()
+            .find_map(|r| (r.link == link && rows.contains(&r.region.row_start())).then_some(r.id))
+    }
+
+    /// Orders of intra-doc links in the rows.
+    pub fn item_links_in(&self, rows: &Range<usize>) -> Vec<usize> {
+        let mut links: Vec<_> = self
+            .item_regions
+            .iter()
+            .filter(|r| rows.contains(&r.region.row_start()))
+            .map(|r| r.link)
+            .collect();
+        links.dedup();
+        links
+    }
+
+    /// Regions of the intra-doc link.
+    pub fn item_link_regions(&self, link: usize) -> impl Iterator<Item = &SelectedRegion> {
+        self.item_regions
+            .iter()
+            .filter(move |r| r.link == link)
+            .map(|r| &r.region)
+    }
+
     pub fn push_footnote(&mut self, key: &str, value: Block) {
         if let Some(old) = self.footnotes.insert(key.into(), value) {
             error!("Footnote definition `{key}` existed with the value {old:?}, but now covered.");
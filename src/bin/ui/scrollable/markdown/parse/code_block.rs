@@ -4,7 +4,7 @@
 // * code snippet beginning with `# ` is hidden as default
 
 use super::{convert_style, Block, Line, MetaTag, Word, SYNTHEME};
-use ratatui::style::{Color, Style};
+use crate::color::theme;
 use syntect::{easy::HighlightLines, util::LinesWithEndings};
 
 pub fn parse(fence: &mut str, code: &str) -> Block {
@@ -30,10 +30,7 @@ fn fallback(code: &str) -> Block {
     code.lines()
         .map(|line| Word {
             word: line.into(),
-            style: Style {
-                fg: Some(Color::LightRed),
-                ..Default::default()
-            },
+            style: theme().code_fallback,
             tag: MetaTag::CodeBlock("Unknown".into()),
             trailling_whitespace: false,
         })
@@ -45,7 +42,7 @@ pub fn rust(code: &str) -> Block {
         let Some(syntax) = ps.find_syntax_by_name("Rust") else {
             return fallback(code);
         };
-        let mut h = HighlightLines::new(syntax, &ts.themes["base16-ocean.dark"]);
+        let mut h = HighlightLines::new(syntax, ts);
         let mut lines = Vec::with_capacity(8);
         // filter out the lines starting `# ` used for hidden lines
         for line in code.lines().filter(|l| !{
@@ -77,7 +74,7 @@ macro_rules! gen_parse_code {
         }
         )+ };
     (#inner $code:ident $ps:ident $ts:ident $syntax:ident) => {
-        let mut h = HighlightLines::new($syntax, &$ts.themes["base16-ocean.dark"]);
+        let mut h = HighlightLines::new($syntax, $ts);
         let mut lines = Vec::with_capacity(8);
         for line in LinesWithEndings::from($code) {
             let mut words = Vec::with_capacity(8);
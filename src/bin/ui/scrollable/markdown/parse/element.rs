@@ -1,5 +1,7 @@
-use super::{segment_str, Block, Color, LinkTag, Links, MetaTag, Modifier, Style, Word};
+use super::{segment_str, Block, LinkTag, Links, MetaTag, Modifier, Style, Word};
+use crate::color::theme;
 use pulldown_cmark::{Event, Tag};
+use rustdoc_types::Id;
 use std::ops::Range;
 use term_rustdoc::util::{ToCompactString, XString};
 
@@ -114,8 +116,12 @@ where
         // }
     }
 
-    /// TODO: support local/external crate item links
+    /// Intra-doc links resolved by rustdoc are parsed as jumpable items;
+    /// other links are referenced links listed below the block.
     pub fn parse_link(self, link: &str) {
+        if let Some(id) = self.links.item_link(link) {
+            return self.parse_item_link(id);
+        }
         let Element {
             mut iter,
             block,
@@ -125,7 +131,7 @@ where
         let idx = links.push_link(link.into());
         block.push_link(idx);
         let tag = MetaTag::Link(LinkTag::ReferenceLink(idx));
-        let style = LINK;
+        let style = theme().link;
         let alink = |word| Word {
             word,
             style,
@@ -175,15 +181,36 @@ where
         block.push_a_word(alink(XString::const_new("]")));
     }
 
+    /// An intra-doc link is rendered as its text without the link destination,
+    /// and can be jumped to like ids in declarations.
+    fn parse_item_link(self, id: Id) {
+        let Element {
+            iter, block, links, ..
+        } = self;
+        let tag = MetaTag::Link(LinkTag::ItemLink(id, links.next_item_link()));
+        let style = theme().jump;
+        for (event, _) in iter {
+            match event {
+                Event::Text(words) => block.push_words(&words, style, tag.clone()),
+                Event::Code(code) => segment_str(&code, |s| {
+                    block.push_a_word(Word {
+                        word: s.into(),
+                        style,
+                        tag: tag.clone(),
+                        trailling_whitespace: false,
+                    })
+                }),
+                _ => (),
+            }
+        }
+    }
+
     /// Images are like links, e.g. `![ref]` are valid syntax, or `![styled text](...)`.
     /// But when parsing them, don't show further styles and just truncate the img line if too long.
     pub fn parse_image(self, link: &str) {
         let Element { iter, block, .. } = self;
         let tag = MetaTag::Image;
-        let style = Style {
-            fg: Some(Color::Rgb(192, 192, 192)), // #C0C0C0
-            ..Default::default()
-        };
+        let style = theme().image;
         let mut img = String::with_capacity(32);
         img.push_str("![");
         for (event, _) in iter {
@@ -262,35 +289,11 @@ where
     }
 }
 
-pub const LINK: Style = Style {
-    fg: Some(Color::Rgb(30, 144, 255)), // #1E90FF
-    add_modifier: Modifier::empty(),
-    bg: None,
-    underline_color: None,
-    sub_modifier: Modifier::empty(),
-};
-
-const INTRA_CODE: Style = Style {
-    fg: Some(Color::Rgb(255, 184, 162)), // #FFB8A2
-    bg: None,
-    underline_color: None,
-    add_modifier: Modifier::empty(),
-    sub_modifier: Modifier::empty(),
-};
-
-pub const FOOTNOTE: Style = Style {
-    fg: Some(Color::LightMagenta),
-    bg: None,
-    underline_color: None,
-    add_modifier: Modifier::empty(),
-    sub_modifier: Modifier::empty(),
-};
-
 pub fn parse_intra_code(code: &str, block: &mut Block) {
     fn word(s: &str) -> Word {
         Word {
             word: s.into(),
-            style: INTRA_CODE,
+            style: theme().intra_code,
             tag: MetaTag::InlineCode,
             trailling_whitespace: false,
         }
@@ -312,10 +315,10 @@ pub fn parse_intra_code_in_link(code: &str, block: &mut Block) {
             trailling_whitespace: false,
         }
     }
-    let tick = word("`", INTRA_CODE);
+    let tick = word("`", theme().intra_code);
     block.push_a_word(tick.clone());
     segment_str(code, |s| {
-        let word = word(s, LINK);
+        let word = word(s, theme().link);
         block.push_a_word(word);
     });
     block.push_a_word(tick);
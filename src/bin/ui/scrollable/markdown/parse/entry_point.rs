@@ -1,18 +1,22 @@
 use super::{
     code_block,
-    element::{Element, FOOTNOTE},
+    element::Element,
     list::{self, parse_codeblock},
-    Block, Blocks, MetaTag, Word,
+    Block, Blocks, ItemLinks, MetaTag, Word,
 };
-use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag};
+use crate::color::theme;
+use pulldown_cmark::{BrokenLink, CodeBlockKind, CowStr, Event, Options, Parser, Tag};
 use term_rustdoc::util::{xformat, XString};
 
-pub fn parse(doc: &str) -> Blocks {
+/// Parse the doc of an item. `item_links` is the `links` field of the item
+/// used to resolve intra-doc links.
+pub fn parse(doc: &str, item_links: &ItemLinks) -> Blocks {
     if doc.is_empty() {
         return Blocks::default();
     }
     let mut blocks = Blocks::new();
-    let mut iter = markdown_iter(doc);
+    blocks.links().set_item_links(item_links);
+    let mut iter = markdown_iter(doc, item_links);
     while let Some((event, range)) = iter.by_ref().next() {
         match event {
             Event::Start(Tag::Paragraph) => {
@@ -74,7 +78,7 @@ pub fn parse(doc: &str) -> Blocks {
                     let mut block = Block::default();
                     block.push_a_word(Word {
                         word: xformat!("[^{key}]: "),
-                        style: FOOTNOTE,
+                        style: theme().footnote,
                         tag: MetaTag::FootnoteSource,
                         trailling_whitespace: false,
                     });
@@ -97,15 +101,24 @@ pub fn parse(doc: &str) -> Blocks {
     blocks
 }
 
-fn markdown_iter(
-    doc: &str,
-) -> pulldown_cmark::OffsetIter<'_, pulldown_cmark::DefaultBrokenLinkCallback> {
-    Parser::new_ext(
+fn markdown_iter<'doc>(
+    doc: &'doc str,
+    item_links: &'doc ItemLinks,
+) -> pulldown_cmark::OffsetIter<'doc, impl pulldown_cmark::BrokenLinkCallback<'doc>> {
+    // Intra-doc links like [`Foo`] or [text][`Foo`] have no link definitions in markdown,
+    // so keep the reference as the link destination if rustdoc resolves it.
+    let intra_doc_link = |link: BrokenLink<'doc>| {
+        item_links
+            .contains_key(&*link.reference)
+            .then_some((link.reference, CowStr::Borrowed("")))
+    };
+    Parser::new_with_broken_link_callback(
         doc,
         Options::ENABLE_FOOTNOTES
             | Options::ENABLE_STRIKETHROUGH
             | Options::ENABLE_TABLES
             | Options::ENABLE_TASKLISTS,
+        Some(intra_doc_link),
     )
     .into_offset_iter()
 }
@@ -1,5 +1,6 @@
 use super::{markdown_iter, parse};
 use insta::{assert_debug_snapshot as snap, assert_snapshot as shot};
+use rustdoc_types::Id;
 
 #[test]
 fn parse_markdown() {
@@ -23,8 +24,8 @@ let a = 1;
     1. *a*
     2. `b`
 "#;
-    snap!(markdown_iter(doc).collect::<Vec<_>>());
-    let mut blocks = parse(doc);
+    snap!(markdown_iter(doc, &Default::default()).collect::<Vec<_>>());
+    let mut blocks = parse(doc, &Default::default());
     shot!(blocks, @r###"
     # h1 `code`
 
@@ -57,7 +58,7 @@ fn parse_markdown_dbg() {
     let doc = r#"
 "#;
     const WIDTH: f64 = 70.0;
-    let lines = parse(doc).write_styled_lines(WIDTH);
+    let lines = parse(doc, &Default::default()).write_styled_lines(WIDTH);
     dbg!(lines);
 }
 
@@ -78,8 +79,8 @@ m[^n].
 
 [^n]: blah
 ";
-    snap!(markdown_iter(doc).collect::<Vec<_>>());
-    let mut blocks = parse(doc);
+    snap!(markdown_iter(doc, &Default::default()).collect::<Vec<_>>());
+    let mut blocks = parse(doc, &Default::default());
     shot!(blocks, @r###"
     [a][0], [c][1], [e][1]. [long][2]
 
@@ -96,8 +97,45 @@ m[^n].
     snap!("parse_markdown_links-parsed", blocks);
 }
 
+#[test]
+fn parse_markdown_item_links() {
+    let doc = "
+See [`Foo`], [Foo::f], [the fn][`bar`], [baz] and [unresolved].
+
+[baz]: crate::bar
+";
+    let item_links = [("`Foo`", 0), ("Foo::f", 1), ("`bar`", 2), ("crate::bar", 2)]
+        .into_iter()
+        .map(|(link, id)| (link.to_owned(), Id(id)))
+        .collect();
+    let mut blocks = parse(doc, &item_links);
+    shot!(blocks, @"See Foo, Foo::f, the fn, baz and [unresolved].");
+
+    let lines = blocks.write_styled_lines(20.0);
+    let links = blocks.links();
+    let ids: Vec<_> = (0..lines.len())
+        .flat_map(|row| (0..20).map(move |col| (row, col)))
+        .filter_map(|(row, col)| links.item_link_at(row, col))
+        .collect();
+    shot!(format!("{lines:?}\n{ids:?}"), @r#"
+    ["See ""Foo"", ""Foo::f"",", "the ""fn"", ""baz"" ""and ""[", "unresolved""]"".", ]
+    [Id(0), Id(0), Id(0), Id(1), Id(1), Id(1), Id(1), Id(1), Id(1), Id(2), Id(2), Id(2), Id(2), Id(2), Id(2), Id(2), Id(2), Id(2)]
+    "#);
+
+    // `the fn` and `baz` are adjacent links to the same item, but still two links
+    let regions: Vec<Vec<_>> = links
+        .item_links_in(&(0..lines.len()))
+        .into_iter()
+        .map(|link| links.item_link_regions(link).collect())
+        .collect();
+    shot!(format!("{regions:?}"), @"[[SelectedRegion { row_start: 0, row_end: 0, col_start: 4, col_end: 7 }], [SelectedRegion { row_start: 0, row_end: 0, col_start: 9, col_end: 15 }], [SelectedRegion { row_start: 1, row_end: 1, col_start: 0, col_end: 4 }, SelectedRegion { row_start: 1, row_end: 1, col_start: 4, col_end: 6 }], [SelectedRegion { row_start: 1, row_end: 1, col_start: 8, col_end: 11 }]]");
+}
+
 #[test]
 fn parse_markdown_intra_code() {
     let doc = "A `code` in a line.";
-    dbg!(markdown_iter(doc).collect::<Vec<_>>(), parse(doc));
+    dbg!(
+        markdown_iter(doc, &Default::default()).collect::<Vec<_>>(),
+        parse(doc, &Default::default())
+    );
 }
@@ -1,5 +1,5 @@
 use super::{word::Word, MetaTag};
-use ratatui::prelude::{Color, Modifier, Style};
+use crate::color::theme;
 use std::{fmt, ops::Deref};
 use term_rustdoc::util::XString;
 
@@ -59,11 +59,7 @@ impl Line {
         }
         words.push(Word {
             word: text[start..].into(),
-            style: Style {
-                fg: Some(Color::Red),
-                add_modifier: Modifier::BOLD,
-                ..Style::new()
-            },
+            style: theme().code_fence,
             tag: MetaTag::CodeBlock(fence.clone()),
             trailling_whitespace: false,
         });
@@ -1,11 +1,11 @@
 use super::{
     code_block,
-    element::{parse_intra_code, Element, EventRange, FOOTNOTE},
+    element::{parse_intra_code, Element, EventRange},
     meta_tag::{LinkTag, MetaTag},
     Block, Line, Links, Word,
 };
+use crate::color::theme;
 use pulldown_cmark::{CodeBlockKind, Event, Tag};
-use ratatui::style::{Color, Style};
 use term_rustdoc::util::{xformat, XString};
 
 pub fn parse<'doc, I>(
@@ -46,7 +46,7 @@ pub fn parse<'doc, I>(
                 let tag = MetaTag::Link(LinkTag::Footnote(key.clone()));
                 let footnote = |word| Word {
                     word,
-                    style: FOOTNOTE,
+                    style: theme().footnote,
                     tag: tag.clone(),
                     trailling_whitespace: false,
                 };
@@ -124,10 +124,7 @@ fn task_maker(done: bool, block: &mut Block) {
     let task = if done {
         Word {
             word: "[x]".into(),
-            style: Style {
-                fg: Some(Color::LightYellow),
-                ..Default::default()
-            },
+            style: theme().task_done,
             ..Default::default()
         }
     } else {
@@ -165,10 +162,7 @@ fn item_prefix(level: &mut u8, kind: Option<&mut u64>) -> [Word; 2] {
         },
         Word {
             word,
-            style: Style {
-                fg: Some(Color::Green),
-                ..Default::default()
-            },
+            style: theme().list_marker,
             tag,
             ..Default::default()
         },
@@ -36,10 +36,11 @@ pub enum MetaTag {
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
 pub enum LinkTag {
-    /// local crate item can be referred by item ID
-    LocalItemLink(Id),
-    /// points to a external crate item path (may be supported once multi-crate docs are ready)
-    ExternalItemLink(usize),
+    /// An item referred by item ID, which may be defined in an external crate
+    /// and jumped to once the doc of that crate is loaded.
+    ///
+    /// The usize is the order of the link in the doc to tell links apart.
+    ItemLink(Id, usize),
     /// Reference link
     ReferenceLink(usize),
     /// a link to styled text
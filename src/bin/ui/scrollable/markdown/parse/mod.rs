@@ -2,12 +2,14 @@ use super::{fallback::StyledLine, heading::Headings};
 use icu_segmenter::LineSegmenter;
 use itertools::Itertools;
 use ratatui::style::{Color, Modifier, Style};
+use rustdoc_types::Id;
 use syntect::{
     easy::HighlightLines,
-    highlighting::{FontStyle, ThemeSet},
+    highlighting::{FontStyle, Theme},
     parsing::SyntaxSet,
     util::LinesWithEndings,
 };
+use term_rustdoc::util::HashMap;
 
 mod code_block;
 #[macro_use]
@@ -29,10 +31,13 @@ pub use self::{
     word::Word,
 };
 
+/// Intra-doc links of an item resolved by rustdoc, i.e. `Item::links`.
+pub type ItemLinks = HashMap<String, Id>;
+
 thread_local! {
-    static SYNTHEME: (SyntaxSet, ThemeSet) = (
+    static SYNTHEME: (SyntaxSet, &'static Theme) = (
         SyntaxSet::load_defaults_newlines(),
-        ThemeSet::load_defaults(),
+        &crate::config::config().syntax,
     );
     static SEGMENTER: LineSegmenter = LineSegmenter::new_auto();
 }
@@ -62,8 +67,12 @@ pub fn segment_words(text: &str, mut f: impl FnMut(&str, bool)) {
     });
 }
 
-pub fn parse_doc(doc: &str, width: f64) -> (Vec<StyledLine>, Blocks, Headings) {
-    let mut blocks = entry_point::parse(doc);
+pub fn parse_doc(
+    doc: &str,
+    item_links: &ItemLinks,
+    width: f64,
+) -> (Vec<StyledLine>, Blocks, Headings) {
+    let mut blocks = entry_point::parse(doc, item_links);
     let lines = blocks.write_styled_lines(width);
     let headings = blocks.links().to_heading();
     (lines, blocks, headings)
@@ -71,9 +80,9 @@ pub fn parse_doc(doc: &str, width: f64) -> (Vec<StyledLine>, Blocks, Headings) {
 
 pub fn md(doc: &str) -> Vec<StyledLine> {
     let mut lines = Vec::with_capacity(128);
-    SYNTHEME.with(|(ps, ts)| {
+    SYNTHEME.with(|(ps, theme)| {
         let syntax = ps.find_syntax_by_extension("md").unwrap();
-        let mut h = HighlightLines::new(syntax, &ts.themes["base16-ocean.dark"]);
+        let mut h = HighlightLines::new(syntax, theme);
         for line in LinesWithEndings::from(doc) {
             let mut styled_line = StyledLine::new();
             for (style, text) in h.highlight_line(line, ps).unwrap() {
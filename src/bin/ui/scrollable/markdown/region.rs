@@ -3,6 +3,7 @@ use super::{
     parse::{LinkTag, MetaTag},
     wrapped::ColumnSpan,
 };
+use rustdoc_types::Id;
 use smallvec::SmallVec;
 use std::cmp::Ordering;
 use term_rustdoc::util::{hashmap, HashMap, XString};
@@ -23,6 +24,18 @@ impl SelectedRegion {
         self.row_start
     }
 
+    /// The start and end columns, which are for a region in the same line.
+    pub fn col_span(&self) -> [usize; 2] {
+        [self.col_start, self.col_end]
+    }
+
+    /// Check if the position is in the region.
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        let after_start = (row, col) >= (self.row_start, self.col_start);
+        let before_end = (row, col) < (self.row_end, self.col_end);
+        after_start && before_end
+    }
+
     fn new_same_line(row: usize, col: ColumnSpan) -> Self {
         let [start, end] = col.span();
         SelectedRegion {
@@ -95,6 +108,16 @@ impl From<SelectedRegion> for TargetRegion {
 pub struct LinkedRegions {
     tag: HashMap<RegionTag, TargetRegion>,
     heading: Vec<(usize, SelectedRegion)>,
+    item_links: Vec<ItemLinkRegion>,
+}
+
+/// A region of an intra-doc link.
+#[derive(Debug)]
+pub struct ItemLinkRegion {
+    pub id: Id,
+    /// The order of the link in the doc.
+    pub link: usize,
+    pub region: SelectedRegion,
 }
 
 impl LinkedRegions {
@@ -102,6 +125,7 @@ impl LinkedRegions {
         LinkedRegions {
             tag: hashmap(8),
             heading: Vec::with_capacity(8),
+            item_links: Vec::new(),
         }
     }
 
@@ -117,6 +141,17 @@ impl LinkedRegions {
         self.heading.push((idx, region));
     }
 
+    /// Each word in an intra-doc link is pushed as a region, so clicking on
+    /// any word of a link that is wrapped to multiple lines works.
+    pub fn push_item_link(&mut self, id: Id, link: usize, row: usize, col: ColumnSpan) {
+        let region = SelectedRegion::new_same_line(row, col);
+        self.item_links.push(ItemLinkRegion { id, link, region });
+    }
+
+    pub fn take_item_links(&mut self) -> Vec<ItemLinkRegion> {
+        std::mem::take(&mut self.item_links)
+    }
+
     pub fn take_headings(&mut self) -> Vec<(usize, SelectedRegion)> {
         std::mem::take(&mut self.heading)
     }
@@ -10,7 +10,7 @@ mod interaction;
 mod markdown;
 mod render;
 
-pub use self::generics::{render_line, LineState, Lines};
+pub use self::generics::{render_line, tail_within_width, LineState, Lines};
 pub use self::interaction::{ScrollOffset, Scrollable};
 pub use self::markdown::{Headings, MarkdownAndHeading, ScrollHeading, ScrollMarkdown, ScrollText};
 
@@ -1,4 +1,5 @@
 use super::ScrollTreeLines;
+use crate::color::theme;
 use ratatui::prelude::{Buffer, Color, Rect};
 use term_rustdoc::tree::TreeLine;
 
@@ -30,20 +31,26 @@ fn write_lines(lines: &[TreeLine], buf: &mut Buffer, x: u16, mut y: u16, width:
 }
 
 fn render_line(line: &TreeLine, buf: &mut Buffer, x: u16, y: u16, width: usize) {
-    let [(glyph, g_style), (name, n_style)] = line.glyph_name();
-    let (x_name, _) = buf.set_stringn(x, y, glyph, width, g_style);
+    let theme = theme();
+    let [(glyph, _), (name, _)] = line.glyph_name();
+    let (x_name, _) = buf.set_stringn(x, y, glyph, width, theme.tree_glyph);
     if let Some(remain) = width.checked_sub((x_name - x) as usize) {
-        buf.set_stringn(x_name, y, name, remain, n_style);
+        buf.set_stringn(x_name, y, name, remain, theme.tag(line.tag));
     }
 }
 
-// Usually the line doesn't contain bg, thus highlight it by adding DarkGray bg on glyph
-// and inversing bg the name with Black fg.
+// Usually the line doesn't contain bg, thus highlight it by adding a bg on glyph
+// and inversing bg the name with a contrasting fg.
 fn render_current_line(line: &TreeLine, buf: &mut Buffer, x: u16, y: u16, width: usize) {
-    let [(glyph, g_style), (name, mut n_style)] = line.glyph_name();
-    let (x_name, _) = buf.set_stringn(x, y, glyph, width, g_style.bg(Color::DarkGray));
+    let theme = theme();
+    let [(glyph, _), (name, _)] = line.glyph_name();
+    let g_style = theme
+        .tree_glyph
+        .bg(theme.tree_cursor.bg.unwrap_or(Color::Reset));
+    let (x_name, _) = buf.set_stringn(x, y, glyph, width, g_style);
+    let mut n_style = theme.tag(line.tag);
     n_style.bg = n_style.fg;
-    n_style.fg = Some(Color::Black);
+    n_style.fg = theme.tree_cursor.fg;
     if let Some(remain) = width.checked_sub((x_name - x) as usize) {
         buf.set_stringn(x_name, y, name, remain, n_style);
     }
@@ -0,0 +1,361 @@
+//! Export the item outline, declarations and docs of a crate into a single Text,
+//! Markdown or HTML file.
+//!
+//! Each exported item is marked with an `#item-N` anchor where N is its id: a
+//! `<a id="item-N">` before the Markdown heading, the id of the HTML section, or the
+//! trailing `#item-N` of the item header in text. Intra-doc links resolved to exported
+//! items are rewritten to point to these anchors, and so are links in the HTML outline.
+
+use super::{CrateDoc, DocTree, Tag, TextTag};
+use crate::{type_name::DeclarationLines, util::XString};
+use pulldown_cmark::{BrokenLink, CowStr, Event, Options, Parser, TagEnd};
+use rustc_hash::FxHashSet;
+use rustdoc_types::Id;
+use std::{collections::HashMap, fmt::Write, ops::Range};
+use termtree::Tree;
+
+/// The file format to export a crate doc to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    Text,
+    #[default]
+    Markdown,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Text => "txt",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+/// An item in the outline with its declaration and docs.
+struct ExportItem<'doc> {
+    id: Id,
+    kind: &'static str,
+    path: XString,
+    decl: DeclarationLines,
+    docs: Option<&'doc str>,
+    /// Intra-doc links in docs resolved by rustdoc.
+    links: Option<&'doc HashMap<String, Id>>,
+}
+
+impl ExportItem<'_> {
+    /// Docs with intra-doc links pointing to anchors of exported items, and headings
+    /// shifted by `shift` levels to stay below the heading of the item.
+    fn docs(&self, exported: &FxHashSet<Id>, shift: usize) -> Option<String> {
+        let docs = self.docs?;
+        let docs = match self.links {
+            Some(links) if !links.is_empty() => resolve_links(docs, links, exported),
+            _ => docs.to_owned(),
+        };
+        Some(if shift == 0 {
+            docs
+        } else {
+            shift_headings(&docs, shift)
+        })
+    }
+}
+
+impl CrateDoc {
+    /// Export the item outline as well as declarations and docs of all items in it
+    /// into a self-contained file content.
+    pub fn export(&self, format: ExportFormat) -> String {
+        let outline = self.dmodule().item_tree(self);
+        let mut items = Vec::new();
+        self.collect_export_items(&outline.tree, &mut items);
+        let name = self.crate_name();
+        let exported: FxHashSet<Id> = items.iter().map(|item| item.id).collect();
+        match format {
+            ExportFormat::Text => export_text(name, &outline, &items, &exported),
+            ExportFormat::Markdown => export_markdown(name, &outline, &items, &exported),
+            ExportFormat::Html => export_html(name, &outline, &items, &exported),
+        }
+    }
+
+    fn collect_export_items<'doc>(
+        &'doc self,
+        tree: &Tree<TextTag>,
+        items: &mut Vec<ExportItem<'doc>>,
+    ) {
+        if let Some(id) = tree.root.id {
+            let doc_item = self.get_doc_item(&id);
+            items.push(ExportItem {
+                id,
+                kind: kind(tree.root.tag),
+                path: self.path(&id),
+                decl: DeclarationLines::new(&id, self),
+                docs: doc_item
+                    .and_then(|item| item.docs.as_deref())
+                    .filter(|docs| !docs.is_empty()),
+                links: doc_item.map(|item| &item.links),
+            });
+        }
+        for leaf in &tree.leaves {
+            self.collect_export_items(leaf, items);
+        }
+    }
+}
+
+fn kind(tag: Tag) -> &'static str {
+    match tag {
+        Tag::Module | Tag::ModuleFolded => "mod",
+        Tag::Struct => "struct",
+        Tag::Union => "union",
+        Tag::Enum => "enum",
+        Tag::Trait => "trait",
+        Tag::Function => "fn",
+        Tag::Constant => "const",
+        Tag::Static => "static",
+        Tag::TypeAlias => "type",
+        Tag::MacroDecl => "macro",
+        Tag::MacroFunc => "proc macro",
+        Tag::MacroAttr => "attribute macro",
+        Tag::MacroDerv => "derive macro",
+        _ => "item",
+    }
+}
+
+/// Concatenate declaration lines into plain code.
+fn decl_code(decl: &DeclarationLines) -> String {
+    let mut code = String::new();
+    for line in decl.iter() {
+        line.iter().for_each(|tt| code.push_str(&tt.text));
+        code.push('\n');
+    }
+    code
+}
+
+/// Items are marked with `#item-N` anchors that intra-doc links refer to.
+fn export_text(
+    name: &str,
+    outline: &DocTree,
+    items: &[ExportItem],
+    exported: &FxHashSet<Id>,
+) -> String {
+    let mut text = String::with_capacity(1024 * 8);
+    _ = writeln!(text, "Crate {name}\n\n{outline}");
+    for item in items {
+        let (kind, path, anchor) = (item.kind, &item.path, anchor(&item.id));
+        _ = writeln!(text, "{}\n{kind} {path} #{anchor}\n", "=".repeat(80));
+        if !item.decl.is_empty() {
+            _ = writeln!(text, "{}", decl_code(&item.decl));
+        }
+        if let Some(docs) = item.docs(exported, 0) {
+            _ = writeln!(text, "{docs}\n");
+        }
+    }
+    text
+}
+
+fn export_markdown(
+    name: &str,
+    outline: &DocTree,
+    items: &[ExportItem],
+    exported: &FxHashSet<Id>,
+) -> String {
+    let mut md = String::with_capacity(1024 * 8);
+    _ = writeln!(
+        md,
+        "# Crate `{name}`\n\n## Outline\n\n```text\n{outline}```\n\n## Items\n"
+    );
+    for item in items {
+        let (kind, path, anchor) = (item.kind, &item.path, anchor(&item.id));
+        _ = writeln!(md, "<a id=\"{anchor}\"></a>\n\n### {kind} `{path}`\n");
+        if !item.decl.is_empty() {
+            _ = writeln!(md, "```rust\n{}```\n", decl_code(&item.decl));
+        }
+        if let Some(docs) = item.docs(exported, 3) {
+            _ = writeln!(md, "{docs}\n");
+        }
+    }
+    md
+}
+
+fn export_html(
+    name: &str,
+    outline: &DocTree,
+    items: &[ExportItem],
+    exported: &FxHashSet<Id>,
+) -> String {
+    let mut html = String::with_capacity(1024 * 16);
+    let name = escape(name);
+    _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{name}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <nav>\n<h1>Crate <code>{name}</code></h1>\n"
+    );
+    html_outline(&outline.tree, &mut html);
+    html.push_str("</nav>\n<main>\n");
+    for item in items {
+        _ = write!(
+            html,
+            "<section id=\"{}\">\n<h2>{} <code>{}</code></h2>\n",
+            anchor(&item.id),
+            item.kind,
+            escape(&item.path)
+        );
+        if !item.decl.is_empty() {
+            html.push_str("<pre><code>");
+            for line in item.decl.iter() {
+                for tt in line.iter() {
+                    let text = escape(&tt.text);
+                    // link to the item if it's exported here
+                    match tt.id.filter(|id| exported.contains(id)) {
+                        Some(id) => _ = write!(html, "<a href=\"#{}\">{text}</a>", anchor(&id)),
+                        None => html.push_str(&text),
+                    }
+                }
+                html.push('\n');
+            }
+            html.push_str("</code></pre>\n");
+        }
+        if let Some(docs) = item.docs(exported, 2) {
+            pulldown_cmark::html::push_html(&mut html, Parser::new_ext(&docs, Options::all()));
+        }
+        html.push_str("</section>\n");
+    }
+    html.push_str("</main>\n</body>\n</html>\n");
+    html
+}
+
+/// Point intra-doc links resolved to exported items to their anchors.
+fn resolve_links<'doc>(
+    docs: &'doc str,
+    links: &HashMap<String, Id>,
+    exported: &FxHashSet<Id>,
+) -> String {
+    // Intra-doc links like [`Foo`] or [text][`Foo`] have no link definitions in markdown,
+    // so keep the reference as the link destination if rustdoc resolves it.
+    let intra_doc_link = |link: BrokenLink<'doc>| {
+        links
+            .contains_key(&*link.reference)
+            .then_some((link.reference, CowStr::Borrowed("")))
+    };
+    let parser = Parser::new_with_broken_link_callback(docs, Options::all(), Some(intra_doc_link));
+    let mut edits = Vec::new();
+    // the whole link, the item, and the link text
+    let mut link: Option<(Range<usize>, Id, Option<Range<usize>>)> = None;
+    for (event, range) in parser.into_offset_iter() {
+        match event {
+            Event::Start(pulldown_cmark::Tag::Link { dest_url, .. }) => {
+                link = links
+                    .get(&*dest_url)
+                    .filter(|id| exported.contains(id))
+                    .map(|id| (range, *id, None));
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some((whole, id, Some(text))) = link.take() {
+                    let text = &docs[text];
+                    edits.push((whole, format!("[{text}](#{})", anchor(&id))));
+                }
+            }
+            _ => {
+                if let Some((_, _, text)) = &mut link {
+                    extend_range(text, range);
+                }
+            }
+        }
+    }
+    apply_edits(docs, edits)
+}
+
+/// Rewrite headings in ATX style with levels shifted, which are capped at 6.
+fn shift_headings(docs: &str, shift: usize) -> String {
+    let mut edits = Vec::new();
+    // the whole heading, the shifted level, and the heading text
+    let mut heading: Option<(Range<usize>, usize, Option<Range<usize>>)> = None;
+    for (event, range) in Parser::new_ext(docs, Options::all()).into_offset_iter() {
+        match event {
+            Event::Start(pulldown_cmark::Tag::Heading { level, .. }) => {
+                heading = Some((range, (level as usize + shift).min(6), None));
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((whole, level, text)) = heading.take() {
+                    let text = text.map_or("", |text| docs[text].trim());
+                    let newline = if docs[whole.clone()].ends_with('\n') {
+                        "\n"
+                    } else {
+                        ""
+                    };
+                    let hashes = "#".repeat(level);
+                    edits.push((whole, format!("{hashes} {text}{newline}")));
+                }
+            }
+            _ => {
+                if let Some((_, _, text)) = &mut heading {
+                    extend_range(text, range);
+                }
+            }
+        }
+    }
+    apply_edits(docs, edits)
+}
+
+fn extend_range(range: &mut Option<Range<usize>>, other: Range<usize>) {
+    *range = Some(match range.take() {
+        Some(r) => r.start.min(other.start)..r.end.max(other.end),
+        None => other,
+    });
+}
+
+/// Replace non-overlapping ranges in ascending order.
+fn apply_edits(text: &str, edits: Vec<(Range<usize>, String)>) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut pos = 0;
+    for (range, replacement) in edits {
+        output.push_str(&text[pos..range.start]);
+        output.push_str(&replacement);
+        pos = range.end;
+    }
+    output.push_str(&text[pos..]);
+    output
+}
+
+/// Nested list with links to items.
+fn html_outline(tree: &Tree<TextTag>, html: &mut String) {
+    let root = &tree.root;
+    let text = escape(&root.text);
+    match root.id {
+        Some(id) => _ = write!(html, "<a href=\"#{}\">{text}</a>", anchor(&id)),
+        None => html.push_str(&text),
+    }
+    if !tree.leaves.is_empty() {
+        html.push_str("\n<ul>\n");
+        for leaf in &tree.leaves {
+            html.push_str("<li>");
+            html_outline(leaf, html);
+            html.push_str("</li>\n");
+        }
+        html.push_str("</ul>\n");
+    }
+}
+
+fn anchor(id: &Id) -> XString {
+    crate::util::xformat!("item-{}", id.0)
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const STYLE: &str = "body{display:flex;margin:0;font-family:sans-serif}\
+nav{width:20em;height:100vh;overflow:auto;position:sticky;top:0;padding:0 1em;\
+border-right:1px solid #ddd}nav ul{padding-left:1.2em}\
+main{flex:1;padding:0 2em;max-width:60em}section{border-bottom:1px solid #eee}\
+pre{background:#f6f6f6;padding:.6em;overflow:auto}";
@@ -1,9 +1,9 @@
 #![allow(non_snake_case)]
-use super::{DModule, DocTree, Show};
+use super::{DModule, DocTree, Show, TypeUsages};
 use crate::type_name::style::{long, long_path};
 use crate::util::{join_path, xformat, XString};
 use rustdoc_types::{Crate, Id, Item, ItemEnum, ItemKind, ItemSummary, Target};
-use std::{borrow::Borrow, collections::HashMap};
+use std::{borrow::Borrow, collections::HashMap, sync::OnceLock};
 
 pub type IDs = Box<[Id]>;
 
@@ -13,6 +13,9 @@ pub type IDs = Box<[Id]>;
 pub struct IDMap {
     krate: Crate,
     dmod: DModule,
+    /// Built along with DModule, or the first time it's needed for a cached doc.
+    #[serde(skip)]
+    usages: OnceLock<TypeUsages>,
 }
 
 impl IDMap {
@@ -21,8 +24,10 @@ impl IDMap {
             krate,
             // placeholder for DModule: we'll construct it at once
             dmod: DModule::default(),
+            usages: OnceLock::new(),
         };
         map.dmod = DModule::new(&map);
+        _ = map.type_usages();
         info!("IDMap and DModule ready");
         map
     }
@@ -34,6 +39,11 @@ impl IDMap {
     pub fn raw_crate_doc(&self) -> &Crate {
         &self.krate
     }
+
+    /// Functions and methods that use a type as receiver, argument or return type.
+    pub fn type_usages(&self) -> &TypeUsages {
+        self.usages.get_or_init(|| TypeUsages::new(self))
+    }
 }
 
 impl Default for IDMap {
@@ -57,6 +67,7 @@ impl Default for IDMap {
                 target,
             },
             dmod: DModule::default(),
+            usages: OnceLock::new(),
         }
     }
 }
@@ -92,6 +103,14 @@ impl IDMap {
 // Documentation on an item.
 impl IDMap {
     pub fn get_doc(&self, id: &Id) -> Option<&str> {
+        self.get_doc_item(id).and_then(|item| item.docs.as_deref())
+    }
+
+    /// The item where the documentation comes from, i.e. the reexported item
+    /// for a reexport and the item itself for others.
+    ///
+    /// This is useful when both docs and intra-doc links on an item are needed.
+    pub fn get_doc_item(&self, id: &Id) -> Option<&Item> {
         self.get_item(id).and_then(|item| match &item.inner {
             ItemEnum::Use(item) => {
                 if let Some(inner_id) = item.id.as_ref() {
@@ -102,13 +121,13 @@ impl IDMap {
                                  recursively contains another Import.\n{item:?} "
                             );
                         } else {
-                            return reexport_item.docs.as_deref();
+                            return Some(reexport_item);
                         }
                     }
                 }
                 None
             }
-            _ => item.docs.as_deref(),
+            _ => Some(item),
         })
     }
 }
@@ -249,3 +268,51 @@ impl IDMap {
             .ok_or_else(|| self.name(id))
     }
 }
+
+/// Cross-crate queries.
+impl IDMap {
+    /// The name of the documented crate.
+    pub fn crate_name(&self) -> &str {
+        let krate = &self.krate;
+        krate
+            .index
+            .get(&krate.root)
+            .and_then(|root| root.name.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Names of external crates that the documented crate refers to.
+    pub fn external_crate_names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self
+            .krate
+            .external_crates
+            .values()
+            .map(|krate| krate.name.as_str())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// The crate name and summary of an item defined in external crates.
+    pub fn external_item(&self, id: &Id) -> Option<(&str, &ItemSummary)> {
+        if self.get_item(id).is_some() {
+            return None;
+        }
+        let summary = self.get_path(id).filter(|summary| summary.crate_id != 0)?;
+        let krate = self.krate.external_crates.get(&summary.crate_id)?;
+        Some((&krate.name, summary))
+    }
+
+    /// Find the item defined in this crate by the full path and kind.
+    pub fn find_local_item(&self, path: &[String], kind: ItemKind) -> Option<Id> {
+        let krate = &self.krate;
+        krate.paths.iter().find_map(|(id, summary)| {
+            (summary.crate_id == 0
+                && summary.kind == kind
+                && summary.path == path
+                && krate.index.contains_key(id))
+            .then_some(*id)
+        })
+    }
+}
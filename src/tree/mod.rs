@@ -2,38 +2,45 @@
 mod impls;
 // The inner macro `icon!` can be used afterwards in submods
 
+mod export;
 mod id;
 mod nodes;
+mod reexport;
 mod stats;
 mod tag;
 mod textline;
+mod usage;
 
 use rustdoc_types::Crate;
-use std::{fmt, ops::Deref, rc::Rc};
+use std::{fmt, ops::Deref, sync::Arc};
 
+pub use export::ExportFormat;
 pub use id::{IDMap, IDs, IndexMap, PathMap};
 pub use impls::show::{DocTree, Show};
 pub use nodes::{
     DConstant, DEnum, DFunction, DImpl, DImplInner, DMacroAttr, DMacroDecl, DMacroDerv, DMacroFunc,
-    DModule, DStatic, DStruct, DTrait, DTypeAlias, DUnion, DataItemKind,
+    DModule, DStatic, DStruct, DTrait, DTypeAlias, DUnion, DataItemKind, ItemPath,
 };
 pub use stats::{ImplCount, ImplCounts, ImplKind, ItemCount};
-pub use tag::Tag;
-pub use textline::{Text, TextTag, TreeLine, TreeLines};
+pub use tag::{Tag, TagGroup};
+pub use textline::{Fold, Text, TextTag, TreeLine, TreeLines};
+pub(crate) use usage::FnOwners;
+pub use usage::{FnsUsingType, TypeUsages};
 
 /// This should be the main data structure to refer to documentation
 /// and the items tree structure in public modules.
 ///
 /// It's cheap to clone and use a ID buffer to avoid the cost of generating a new string in query.
+/// It can be sent to other threads, e.g. docs are loaded in background.
 #[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct CrateDoc {
-    inner: Rc<IDMap>,
+    inner: Arc<IDMap>,
 }
 
 impl CrateDoc {
     pub fn new(doc: Crate) -> CrateDoc {
         CrateDoc {
-            inner: Rc::new(IDMap::new(doc)),
+            inner: Arc::new(IDMap::new(doc)),
         }
     }
 }
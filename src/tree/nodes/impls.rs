@@ -25,41 +25,40 @@ impl DImpl {
         if ids.is_empty() {
             return Default::default();
         }
-        // let [mut inherent, mut trait_, mut auto, mut blanket]: [Vec<_>; 4] = Default::default();
-        todo!();
-        // for id in ids {
-        // FIXME:
-        // if id.starts_with("a:") {
-        //     auto.push(DImplInner::new_with_no_details(id));
-        // } else if id.starts_with("b:") {
-        //     blanket.push(DImplInner::new_with_no_details(id));
-        // } else if let Some(item) = map.get_item(id) {
-        //     if let ItemEnum::Impl(impl_) = &item.inner {
-        //         if impl_.trait_.is_none() {
-        //             inherent.push(DImplInner::new(id, impl_, map));
-        //         } else {
-        //             trait_.push(DImplInner::new(id, impl_, map));
-        //         }
-        //     } else {
-        //         warn!("{id:?} in Crate's index doesn't refer to an impl item");
-        //     }
-        // } else {
-        //     warn!("the impl with {id:?} not found in Crate's index");
-        // }
-        // }
-        // inherent.sort_unstable_by_key(|x| map.name(&x.id));
-        // trait_.sort_unstable_by_key(|x| map.name(&x.id));
-        // auto.sort_unstable_by_key(|x| map.name(&x.id));
-        // blanket.sort_unstable_by_key(|x| map.name(&x.id));
-        // let merged_inherent = DImplInner::merge_inherent_impls(&inherent, map);
-        // DImpl {
-        //     inherent: inherent.into(),
-        //     trait_: trait_.into(),
-        //     auto: auto.into(),
-        //     blanket: blanket.into(),
-        //     merged_inherent: Box::new(merged_inherent),
-        // }
+        let [mut inherent, mut trait_, mut auto, mut blanket]: [Vec<_>; 4] = Default::default();
+        for id in ids {
+            let Some(item) = map.get_item(id) else {
+                warn!("the impl with {id:?} not found in Crate's index");
+                continue;
+            };
+            let ItemEnum::Impl(impl_) = &item.inner else {
+                warn!("{id:?} in Crate's index doesn't refer to an impl item");
+                continue;
+            };
+            if impl_.is_synthetic {
+                auto.push(DImplInner::new_with_no_details(*id));
+            } else if impl_.blanket_impl.is_some() {
+                blanket.push(DImplInner::new_with_no_details(*id));
+            } else if impl_.trait_.is_none() {
+                inherent.push(DImplInner::new(id, impl_, map));
+            } else {
+                trait_.push(DImplInner::new(id, impl_, map));
+            }
+        }
+        inherent.sort_unstable_by_key(|x| map.name(&x.id));
+        trait_.sort_unstable_by_key(|x| map.name(&x.id));
+        auto.sort_unstable_by_key(|x| map.name(&x.id));
+        blanket.sort_unstable_by_key(|x| map.name(&x.id));
+        let merged_inherent = DImplInner::merge_inherent_impls(&inherent, map);
+        DImpl {
+            inherent: inherent.into(),
+            trait_: trait_.into(),
+            auto: auto.into(),
+            blanket: blanket.into(),
+            merged_inherent: Box::new(merged_inherent),
+        }
     }
+
     pub fn is_empty(&self) -> bool {
         self.auto.is_empty()
             && self.blanket.is_empty()
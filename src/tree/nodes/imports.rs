@@ -62,10 +62,10 @@ pub(super) fn parse_import(
         }
     } else if let Some(extern_item) = map.pathmap().get(&import_id) {
         let id = import_id;
-        // TODO: external items are in path map, which means no further information
+        // External items are in path map, which means no further information
         // except full path and item kind will be known.
-        // To get details of an external item, we need to compile the external crate,
-        // and search it with the full path and kind.
+        // To get details of an external item, the doc of the external crate is needed
+        // to inline the item via `CrateDoc::inline_external_reexports`.
         // A simple example of this is `nucleo` crate.
         match extern_item.kind {
             ItemKind::Module if !kin.contains(&id) => {
@@ -1,5 +1,5 @@
 use super::{DEnum, DModule, DStruct, DTrait, DUnion};
-use crate::tree::{DocTree, IDMap, Show};
+use crate::tree::{DocTree, FnsUsingType, IDMap, Show};
 use rustdoc_types::{Id, ItemEnum};
 
 /// Data-carrying items that provide extra tree layer on fields/variants/impls.
@@ -121,4 +121,22 @@ impl DModule {
             _ => None,
         }
     }
+
+    /// Functions and methods that use the struct, enum or union as receiver, argument
+    /// or return type.
+    pub fn usage_tree(&self, id: &Id, map: &IDMap) -> Option<DocTree> {
+        let root = match DataItemKind::new(id, map)? {
+            DataItemKind::Struct => node!(Struct: map, *id),
+            DataItemKind::Enum => node!(Enum: map, *id),
+            DataItemKind::Union => node!(Union: map, *id),
+            _ => return None,
+        };
+        // the usages are indexed by the defining item instead of a reexport
+        let ty = map.get_doc_item(id)?.id;
+        let usages = map.type_usages().get(&ty);
+        Some(match usages {
+            Some(fns) => fns.show_prettier(root),
+            None => FnsUsingType::default().show_prettier(root),
+        })
+    }
 }
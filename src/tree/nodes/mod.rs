@@ -18,6 +18,9 @@ mod imports;
 mod item_inner;
 pub use item_inner::DataItemKind;
 
+mod search;
+pub use search::ItemPath;
+
 use super::IDMap;
 use crate::tree::impls::show::{DocTree, Show};
 use rustdoc_types::{Id, Item, ItemEnum, MacroKind, Module};
@@ -0,0 +1,114 @@
+use super::{DImpl, DModule};
+use crate::{
+    tree::IDMap,
+    util::{xformat, XString},
+};
+use rustdoc_types::Id;
+
+/// An item with its full path, used as a candidate for searching items by name.
+#[derive(Debug, Clone)]
+pub struct ItemPath {
+    pub id: Id,
+    pub path: XString,
+    /// The data-carrying item (struct/enum/union/trait) if this item only shows up
+    /// in the inner tree of it, like fields, variants, associated items and impl items.
+    ///
+    /// None means the item can be found in the module tree.
+    pub outer: Option<Id>,
+}
+
+impl ItemPath {
+    fn new(id: Id, map: &IDMap) -> Self {
+        ItemPath {
+            id,
+            path: map.path(&id),
+            outer: None,
+        }
+    }
+
+    fn inner(id: Id, outer: &Self, map: &IDMap) -> Self {
+        ItemPath {
+            id,
+            path: xformat!("{}::{}", outer.path, map.name(&id)),
+            outer: Some(outer.id),
+        }
+    }
+}
+
+macro_rules! push {
+    ($v:ident $map:ident: $( $field:ident ),+ $(,)?) => {$(
+        $v.extend($field.iter().map(|item| ItemPath::new(item.id, $map)));
+    )+};
+    ($v:ident $map:ident $outer:ident $item:ident: $( $field:ident ),+ $(,)?) => {$(
+        $v.extend($item.$field.iter().map(|&id| ItemPath::inner(id, &$outer, $map)));
+    )+};
+}
+
+impl DModule {
+    /// All the items in the module tree as well as the inner items of data-carrying items,
+    /// with their paths in alphabetical order.
+    pub fn item_paths(&self, map: &IDMap) -> Vec<ItemPath> {
+        let mut v = Vec::with_capacity(1024);
+        self.collect_item_paths(map, &mut v);
+        v.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+        v
+    }
+
+    fn collect_item_paths(&self, map: &IDMap, v: &mut Vec<ItemPath>) {
+        let DModule {
+            id,
+            modules,
+            structs,
+            unions,
+            enums,
+            traits,
+            functions,
+            constants,
+            statics,
+            type_alias,
+            macros_decl,
+            macros_func,
+            macros_attr,
+            macros_derv,
+        } = self;
+        v.push(ItemPath::new(*id, map));
+        push!(v map: functions, constants, statics, type_alias,
+              macros_decl, macros_func, macros_attr, macros_derv);
+        for item in structs {
+            let outer = ItemPath::new(item.id, map);
+            push!(v map outer item: fields);
+            item.impls.collect_item_paths(&outer, map, v);
+            v.push(outer);
+        }
+        for item in unions {
+            let outer = ItemPath::new(item.id, map);
+            push!(v map outer item: fields);
+            item.impls.collect_item_paths(&outer, map, v);
+            v.push(outer);
+        }
+        for item in enums {
+            let outer = ItemPath::new(item.id, map);
+            push!(v map outer item: variants);
+            item.impls.collect_item_paths(&outer, map, v);
+            v.push(outer);
+        }
+        for item in traits {
+            let outer = ItemPath::new(item.id, map);
+            push!(v map outer item: constants, types, functions);
+            v.push(outer);
+        }
+        for m in modules {
+            m.collect_item_paths(map, v);
+        }
+    }
+}
+
+impl DImpl {
+    /// Only items from inherent and trait impls are collected, because
+    /// auto and blanket impls usually bring too much noise.
+    fn collect_item_paths(&self, outer: &ItemPath, map: &IDMap, v: &mut Vec<ItemPath>) {
+        for item in self.inherent.iter().chain(&*self.trait_) {
+            push!(v map outer item: constants, types, functions);
+        }
+    }
+}
@@ -1,8 +1,15 @@
-use crate::tree::{
-    impls::show::{show_ids, DocTree, Show},
-    IDMap, IDs, Tag,
+use crate::{
+    tree::{
+        impls::show::{show_ids, DocTree, Show},
+        IDMap, IDs, Tag,
+    },
+    type_name::style::{long, long_path},
+    util::xformat,
 };
-use rustdoc_types::{Id, ItemEnum, Trait};
+use rustdoc_types::{
+    GenericArg, GenericArgs, GenericBound, GenericParamDefKind, Id, Impl, ItemEnum, Trait, Type,
+};
+use std::fmt;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
 pub struct DTrait {
@@ -61,25 +68,284 @@ impl DTrait {
         }
     }
 
+    /// Traits properties and supertraits, followed by associated items that an impl
+    /// must define and those with defaults.
     pub fn associated_items(&self, map: &IDMap) -> DocTree {
         let mut root = node!(Trait: map, self.id);
-        names_node!(@iter self map root
-            constants AssocConst,
-            types     AssocType,
-            functions AssocFn,
-        );
+        let Some(ItemEnum::Trait(item)) = map.get_doc_item(&self.id).map(|item| &item.inner) else {
+            // external traits have no details
+            names_node!(@iter self map root
+                constants AssocConst,
+                types     AssocType,
+                functions AssocFn,
+            );
+            return root;
+        };
+
+        root.push(if item.is_dyn_compatible {
+            Tag::DynCompatible.show()
+        } else {
+            Tag::NotDynCompatible.show()
+        });
+        if item.is_auto {
+            root.push(Tag::AutoTrait.show());
+        }
+        if item.is_unsafe {
+            root.push(Tag::UnsafeTrait.show());
+        }
+
+        let supertraits: Vec<_> = item
+            .bounds
+            .iter()
+            .filter_map(|bound| match bound {
+                GenericBound::TraitBound { trait_, .. } => Some(DocTree::new(
+                    long_path(trait_).into(),
+                    Tag::Supertrait,
+                    Some(trait_.id),
+                )),
+                _ => None,
+            })
+            .collect();
+        if !supertraits.is_empty() {
+            root.push(Tag::Supertraits.show().with_leaves(supertraits));
+        }
+
+        let (mut required, mut provided) = (Vec::new(), Vec::new());
+        let ids = [
+            (&self.types, Tag::AssocType),
+            (&self.constants, Tag::AssocConst),
+            (&self.functions, Tag::AssocFn),
+        ];
+        for (ids, tag) in ids {
+            for &id in &**ids {
+                let (has_default, name) = match map.get_item(&id).map(|item| &item.inner) {
+                    Some(ItemEnum::AssocType {
+                        type_: Some(ty), ..
+                    }) => (true, xformat!("{} = {}", map.name(&id), long(ty))),
+                    Some(ItemEnum::AssocConst { value, .. }) => (value.is_some(), map.name(&id)),
+                    Some(ItemEnum::Function(f)) => (f.has_body, map.name(&id)),
+                    _ => (false, map.name(&id)),
+                };
+                let node = DocTree::new(name, tag, Some(id));
+                if has_default {
+                    provided.push(node);
+                } else {
+                    required.push(node);
+                }
+            }
+        }
+        if !required.is_empty() {
+            root.push(Tag::RequiredItems.show().with_leaves(required));
+        }
+        if !provided.is_empty() {
+            root.push(Tag::ProvidedItems.show().with_leaves(provided));
+        }
         root
     }
 
+    /// Implementors grouped by the form of the implementing type, by concrete vs generic
+    /// and by local vs foreign type, with the count of each group.
     pub fn implementors(&self, map: &IDMap) -> DocTree {
         let mut root = node!(Trait: map, self.id);
-        names_node!(@iter self map root
-            implementations Implementor,
+        if self.implementations.is_empty() {
+            root.push(Tag::NoImplementors.show());
+            return root;
+        }
+        let classes: Vec<_> = self
+            .implementations
+            .iter()
+            .filter_map(|id| match &map.get_item(id)?.inner {
+                ItemEnum::Impl(imp) => Some((*id, ImplementorClass::new(imp, map))),
+                _ => None,
+            })
+            .collect();
+        let groups = ImplementorGroups {
+            classes: &classes,
+            map,
+        };
+        root.push(
+            groups.by(Tag::ImplementorsByOwnership, Ownership::ALL, |c| {
+                c.ownership
+            }),
+        );
+        root.push(
+            groups.by(Tag::ImplementorsByGenericity, Genericity::ALL, |c| {
+                c.genericity
+            }),
         );
+        root.push(groups.by(Tag::ImplementorsByLocality, Locality::ALL, |c| c.locality));
         root
     }
 }
 
+struct ImplementorGroups<'a> {
+    classes: &'a [(Id, ImplementorClass)],
+    map: &'a IDMap,
+}
+
+impl ImplementorGroups<'_> {
+    /// A heading with non-empty groups in the order of `all`.
+    fn by<K, const N: usize>(
+        &self,
+        heading: Tag,
+        all: [K; N],
+        key: impl Fn(&ImplementorClass) -> K,
+    ) -> DocTree
+    where
+        K: Copy + PartialEq + fmt::Display,
+    {
+        let mut node = heading.show();
+        for group in all {
+            let leaves: Vec<_> = self
+                .classes
+                .iter()
+                .filter(|(_, class)| key(class) == group)
+                .map(|(id, _)| DocTree::new(self.map.name(id), Tag::Implementor, Some(*id)))
+                .collect();
+            if !leaves.is_empty() {
+                let text = xformat!("{group} ({})", leaves.len());
+                node.push(DocTree::new(text, Tag::ImplementorGroup, None).with_leaves(leaves));
+            }
+        }
+        node
+    }
+}
+
+struct ImplementorClass {
+    ownership: Ownership,
+    genericity: Genericity,
+    locality: Locality,
+}
+
+impl ImplementorClass {
+    fn new(imp: &Impl, map: &IDMap) -> Self {
+        let (ownership, base) = Ownership::new(&imp.for_, map);
+        // `impl<T: X> Trait for T` and `impl<T> Trait for Vec<T>`
+        let generic = imp
+            .generics
+            .params
+            .iter()
+            .any(|p| matches!(p.kind, GenericParamDefKind::Type { .. }));
+        let locality = match base {
+            Type::Generic(_) => Locality::TypeParam,
+            Type::ResolvedPath(p) if is_local(&p.id, map) => Locality::Local,
+            _ => Locality::Foreign,
+        };
+        ImplementorClass {
+            ownership,
+            genericity: if generic {
+                Genericity::Generic
+            } else {
+                Genericity::Concrete
+            },
+            locality,
+        }
+    }
+}
+
+/// The form of the implementing type.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Ownership {
+    Owned,
+    Ref,
+    RefMut,
+    Boxed,
+}
+
+impl Ownership {
+    const ALL: [Ownership; 4] = [
+        Ownership::Owned,
+        Ownership::Ref,
+        Ownership::RefMut,
+        Ownership::Boxed,
+    ];
+
+    /// The form and the type inside it.
+    fn new<'t>(ty: &'t Type, map: &IDMap) -> (Ownership, &'t Type) {
+        match ty {
+            Type::BorrowedRef {
+                is_mutable, type_, ..
+            } => {
+                let form = if *is_mutable {
+                    Ownership::RefMut
+                } else {
+                    Ownership::Ref
+                };
+                (form, type_)
+            }
+            Type::ResolvedPath(p) if map.path(&p.id) == "alloc::boxed::Box" => {
+                if let Some(GenericArgs::AngleBracketed { args, .. }) = p.args.as_deref() {
+                    if let Some(GenericArg::Type(inner)) = args.first() {
+                        return (Ownership::Boxed, inner);
+                    }
+                }
+                (Ownership::Owned, ty)
+            }
+            _ => (Ownership::Owned, ty),
+        }
+    }
+}
+
+impl fmt::Display for Ownership {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Ownership::Owned => "T",
+            Ownership::Ref => "&T",
+            Ownership::RefMut => "&mut T",
+            Ownership::Boxed => "Box<T>",
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Genericity {
+    Concrete,
+    Generic,
+}
+
+impl Genericity {
+    const ALL: [Genericity; 2] = [Genericity::Concrete, Genericity::Generic];
+}
+
+impl fmt::Display for Genericity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Genericity::Concrete => "Concrete",
+            Genericity::Generic => "Generic",
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Locality {
+    Local,
+    Foreign,
+    /// Blanket impls like `impl<T: X> Trait for T`.
+    TypeParam,
+}
+
+impl Locality {
+    const ALL: [Locality; 3] = [Locality::Local, Locality::Foreign, Locality::TypeParam];
+}
+
+impl fmt::Display for Locality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Locality::Local => "Local Types",
+            Locality::Foreign => "Foreign Types",
+            Locality::TypeParam => "Type Parameters",
+        })
+    }
+}
+
+/// Items from the current crate have crate id 0 in PathMap.
+fn is_local(id: &Id, map: &IDMap) -> bool {
+    match map.get_path(id) {
+        Some(summary) => summary.crate_id == 0,
+        None => map.get_item(id).is_some(),
+    }
+}
+
 impl Show for DTrait {
     fn show(&self) -> DocTree {
         format!("[trait] {:?}", self.id).show().with_leaves([
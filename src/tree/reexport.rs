@@ -0,0 +1,513 @@
+//! Inline items re-exported from external crates by consulting the docs of these crates.
+//!
+//! A re-exported external item only has an [`ItemSummary`] in PathMap, so the tree node
+//! for it is an empty shell. Given the doc of the external crate, the real item is found
+//! by matching the path and kind in its PathMap, then the item and the items it owns
+//! (fields, variants, impls, items in modules/traits/impls etc) are copied into IndexMap
+//! with fresh ids. Types and links in these copied items that refer to other items are
+//! mapped to the PathMap of the local crate.
+
+use super::CrateDoc;
+use crate::util::{hashmap, HashMap, XString};
+use rustc_hash::FxHashSet as HashSet;
+use rustdoc_types::{
+    AssocItemConstraintKind, Crate, ExternalCrate, GenericArg, GenericArgs, GenericBound,
+    GenericParamDef, GenericParamDefKind, Generics, Id, Item, ItemEnum, ItemKind, ItemSummary,
+    Path, StructKind, Term, Type, VariantKind, Visibility, WherePredicate,
+};
+use std::collections::hash_map::Entry;
+
+impl CrateDoc {
+    /// Names of external crates from which items are re-exported, but only the paths
+    /// of these items are known.
+    pub fn reexported_external_crates(&self) -> Vec<XString> {
+        let krate = self.raw_crate_doc();
+        let mut names: Vec<XString> = unresolved_reexports(krate)
+            .filter_map(|(_, summary)| krate.external_crates.get(&summary.crate_id))
+            .map(|ext| ext.name.as_str().into())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Inline the re-exported external items from the docs of external crates.
+    ///
+    /// Returns None if no item is inlined. Items re-exported by external docs from
+    /// yet other crates are not resolved.
+    pub fn inline_external_reexports(&self, externals: &[CrateDoc]) -> Option<CrateDoc> {
+        let local = self.raw_crate_doc();
+        // paths of items in an external doc are indexed once for all re-exports from it
+        let mut lookups = hashmap::<&str, (&CrateDoc, LocalPaths)>(externals.len());
+        let mut targets = Vec::new();
+        for (id, summary) in unresolved_reexports(local) {
+            let ext_crate = local.external_crates.get(&summary.crate_id);
+            let Some(name) = ext_crate.map(|ext| ext.name.as_str()) else {
+                continue;
+            };
+            let (ext, paths) = match lookups.entry(name) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let Some(ext) = externals.iter().find(|doc| doc.crate_name() == name) else {
+                        continue;
+                    };
+                    entry.insert((ext, local_paths(ext.raw_crate_doc())))
+                }
+            };
+            if let Some(&ext_id) = paths.get(&(summary.path.as_slice(), summary.kind)) {
+                targets.push((&summary.path, id, summary.crate_id, *ext, ext_id));
+            }
+        }
+        if targets.is_empty() {
+            return None;
+        }
+        // IndexMap is unordered, so inline items by paths to get the same ids every time
+        targets.sort_unstable_by_key(|target| target.0);
+        let mut krate = local.clone();
+        let mut inliner = Inliner::new(&mut krate);
+        for (_, id, crate_id, ext, ext_id) in targets {
+            inliner.inline(id, crate_id, ext.raw_crate_doc(), ext_id);
+        }
+        inliner.implementations();
+        info!(
+            inlined = inliner.inlined,
+            "Inlined external items into {}",
+            self.crate_name()
+        );
+        Some(CrateDoc::new(krate))
+    }
+}
+
+/// Items defined in the crate, indexed by paths and kinds.
+type LocalPaths<'a> = HashMap<(&'a [String], ItemKind), Id>;
+
+fn local_paths(krate: &Crate) -> LocalPaths<'_> {
+    krate
+        .paths
+        .iter()
+        .filter(|(id, summary)| summary.crate_id == 0 && krate.index.contains_key(id))
+        .map(|(id, summary)| ((summary.path.as_slice(), summary.kind), *id))
+        .collect()
+}
+
+/// Import targets that are only known in PathMap.
+fn unresolved_reexports(krate: &Crate) -> impl Iterator<Item = (Id, &ItemSummary)> {
+    krate.index.values().filter_map(|item| {
+        let ItemEnum::Use(import) = &item.inner else {
+            return None;
+        };
+        let id = import.id?;
+        if krate.index.contains_key(&id) {
+            return None;
+        }
+        let summary = krate.paths.get(&id)?;
+        (summary.crate_id != 0 && summary.kind != ItemKind::Primitive).then_some((id, summary))
+    })
+}
+
+struct Inliner<'a> {
+    local: &'a mut Crate,
+    /// Paths and kinds of items in local PathMap, used to reuse ids for the same item.
+    paths: HashMap<(Vec<String>, ItemKind), Id>,
+    /// The next fresh id which is not used in local crate.
+    next: u32,
+    inlined: usize,
+    /// (crate id, external id) => local id of copied items
+    copied: HashMap<(u32, Id), Id>,
+    /// Copied traits whose implementations still hold external ids.
+    traits: Vec<(u32, Id)>,
+}
+
+impl<'a> Inliner<'a> {
+    fn new(local: &'a mut Crate) -> Self {
+        let paths = local
+            .paths
+            .iter()
+            .map(|(id, summary)| ((summary.path.clone(), summary.kind), *id))
+            .collect();
+        let max = local.index.keys().chain(local.paths.keys()).map(|id| id.0);
+        let next = max.max().map_or(0, |id| id + 1);
+        Inliner {
+            local,
+            paths,
+            next,
+            inlined: 0,
+            copied: hashmap(64),
+            traits: Vec::new(),
+        }
+    }
+
+    /// Copy the item with `ext_id` from external doc and its inner items into local
+    /// IndexMap, and the item will use the `id` of the unresolved import target.
+    fn inline(&mut self, id: Id, crate_id: u32, ext: &Crate, ext_id: Id) {
+        let mut remap = Remap {
+            inliner: self,
+            ext,
+            crate_id,
+            ids: hashmap(64),
+            queued: HashSet::from_iter([ext_id]),
+            queue: vec![ext_id],
+        };
+        remap.ids.insert(ext_id, id);
+        while let Some(ext_id) = remap.queue.pop() {
+            let Some(item) = ext.index.get(&ext_id) else {
+                continue;
+            };
+            let item = remap.item(item);
+            let inliner = &mut *remap.inliner;
+            inliner.copied.insert((crate_id, ext_id), item.id);
+            if let ItemEnum::Trait(_) = item.inner {
+                inliner.traits.push((crate_id, item.id));
+            }
+            inliner.local.index.insert(item.id, item);
+            inliner.inlined += 1;
+        }
+    }
+
+    /// Map implementations of copied traits once all items are copied, because an
+    /// impl can be copied after the trait, even by inlining another item.
+    /// Implementations for types in other places are not copied, thus dropped.
+    fn implementations(&mut self) {
+        for (crate_id, id) in self.traits.drain(..) {
+            let Some(ItemEnum::Trait(t)) =
+                self.local.index.get_mut(&id).map(|item| &mut item.inner)
+            else {
+                continue;
+            };
+            let copied = &self.copied;
+            t.implementations = (t.implementations.iter())
+                .filter_map(|ext_id| copied.get(&(crate_id, *ext_id)).copied())
+                .collect();
+        }
+    }
+
+    fn fresh_id(&mut self) -> Id {
+        let id = Id(self.next);
+        self.next += 1;
+        id
+    }
+}
+
+/// Map ids from an external crate to local crate.
+struct Remap<'i, 'a, 'e> {
+    inliner: &'i mut Inliner<'a>,
+    ext: &'e Crate,
+    /// The local crate id for the external crate.
+    crate_id: u32,
+    /// External id => local id
+    ids: HashMap<Id, Id>,
+    /// External items that have been or will be copied.
+    queued: HashSet<Id>,
+    /// External items to be copied.
+    queue: Vec<Id>,
+}
+
+impl Remap<'_, '_, '_> {
+    /// An item owned by the copied item, which will be copied too.
+    fn child(&mut self, id: &Id) -> Id {
+        let local = self.refer(id);
+        if self.ext.index.contains_key(id) && self.queued.insert(*id) {
+            self.queue.push(*id);
+        }
+        local
+    }
+
+    /// An item referred to by the copied item, which only needs to be known by path.
+    fn refer(&mut self, id: &Id) -> Id {
+        match self.ids.get(id) {
+            Some(local) => *local,
+            None => self.new_id(id),
+        }
+    }
+
+    fn new_id(&mut self, id: &Id) -> Id {
+        let local = match self.ext.paths.get(id) {
+            Some(summary) => self.path_id(summary),
+            None => self.inliner.fresh_id(),
+        };
+        self.ids.insert(*id, local);
+        local
+    }
+
+    /// Reuse the id in local PathMap if the item is known, or add it to PathMap.
+    fn path_id(&mut self, summary: &ItemSummary) -> Id {
+        let key = (summary.path.clone(), summary.kind);
+        if let Some(id) = self.inliner.paths.get(&key) {
+            return *id;
+        }
+        let id = self.inliner.fresh_id();
+        let crate_id = self.crate_id(summary.crate_id);
+        self.inliner.local.paths.insert(
+            id,
+            ItemSummary {
+                crate_id,
+                path: summary.path.clone(),
+                kind: summary.kind,
+            },
+        );
+        self.inliner.paths.insert(key, id);
+        id
+    }
+
+    /// Map the crate id in external doc to the one in local doc by crate name.
+    fn crate_id(&mut self, ext_crate_id: u32) -> u32 {
+        if ext_crate_id == 0 {
+            return self.crate_id;
+        }
+        let Some(ext_crate) = self.ext.external_crates.get(&ext_crate_id) else {
+            return self.crate_id;
+        };
+        let externals = &mut self.inliner.local.external_crates;
+        if let Some((id, _)) = externals.iter().find(|(_, c)| c.name == ext_crate.name) {
+            return *id;
+        }
+        let id = externals.keys().max().map_or(1, |id| id + 1);
+        externals.insert(id, ExternalCrate::clone(ext_crate));
+        id
+    }
+
+    fn ids(&mut self, ids: &[Id]) -> Vec<Id> {
+        ids.iter().map(|id| self.child(id)).collect()
+    }
+
+    fn item(&mut self, item: &Item) -> Item {
+        let mut item = item.clone();
+        item.id = self.child(&item.id);
+        item.crate_id = self.crate_id;
+        for id in item.links.values_mut() {
+            *id = self.refer(id);
+        }
+        if let Visibility::Restricted { parent, .. } = &mut item.visibility {
+            *parent = self.refer(parent);
+        }
+        self.item_enum(&mut item.inner);
+        item
+    }
+
+    fn item_enum(&mut self, inner: &mut ItemEnum) {
+        match inner {
+            ItemEnum::Module(m) => m.items = self.ids(&m.items),
+            ItemEnum::Use(import) => import.id = import.id.map(|id| self.child(&id)),
+            ItemEnum::Union(u) => {
+                self.generics(&mut u.generics);
+                u.fields = self.ids(&u.fields);
+                u.impls = self.ids(&u.impls);
+            }
+            ItemEnum::Struct(s) => {
+                match &mut s.kind {
+                    StructKind::Unit => (),
+                    StructKind::Tuple(fields) => self.tuple_fields(fields),
+                    StructKind::Plain { fields, .. } => *fields = self.ids(fields),
+                }
+                self.generics(&mut s.generics);
+                s.impls = self.ids(&s.impls);
+            }
+            ItemEnum::StructField(ty) => self.ty(ty),
+            ItemEnum::Enum(e) => {
+                self.generics(&mut e.generics);
+                e.variants = self.ids(&e.variants);
+                e.impls = self.ids(&e.impls);
+            }
+            ItemEnum::Variant(v) => match &mut v.kind {
+                VariantKind::Plain => (),
+                VariantKind::Tuple(fields) => self.tuple_fields(fields),
+                VariantKind::Struct { fields, .. } => *fields = self.ids(fields),
+            },
+            ItemEnum::Function(f) => {
+                self.generics(&mut f.generics);
+                for (_, ty) in &mut f.sig.inputs {
+                    self.ty(ty);
+                }
+                if let Some(ty) = &mut f.sig.output {
+                    self.ty(ty);
+                }
+            }
+            ItemEnum::Trait(t) => {
+                t.items = self.ids(&t.items);
+                self.generics(&mut t.generics);
+                self.bounds(&mut t.bounds);
+                // implementations are mapped in Inliner::implementations
+            }
+            ItemEnum::TraitAlias(t) => {
+                self.generics(&mut t.generics);
+                self.bounds(&mut t.params);
+            }
+            ItemEnum::Impl(i) => {
+                self.generics(&mut i.generics);
+                if let Some(path) = &mut i.trait_ {
+                    self.path(path);
+                }
+                self.ty(&mut i.for_);
+                i.items = self.ids(&i.items);
+                if let Some(ty) = &mut i.blanket_impl {
+                    self.ty(ty);
+                }
+            }
+            ItemEnum::TypeAlias(t) => {
+                self.ty(&mut t.type_);
+                self.generics(&mut t.generics);
+            }
+            ItemEnum::Constant { type_, .. } | ItemEnum::AssocConst { type_, .. } => {
+                self.ty(type_);
+            }
+            ItemEnum::Static(s) => self.ty(&mut s.type_),
+            ItemEnum::Primitive(p) => p.impls = self.ids(&p.impls),
+            ItemEnum::AssocType {
+                generics,
+                bounds,
+                type_,
+            } => {
+                self.generics(generics);
+                self.bounds(bounds);
+                if let Some(ty) = type_ {
+                    self.ty(ty);
+                }
+            }
+            ItemEnum::ExternCrate { .. }
+            | ItemEnum::ExternType
+            | ItemEnum::Macro(_)
+            | ItemEnum::ProcMacro(_) => (),
+        }
+    }
+
+    fn tuple_fields(&mut self, fields: &mut [Option<Id>]) {
+        for id in fields.iter_mut().flatten() {
+            *id = self.child(id);
+        }
+    }
+
+    fn generics(&mut self, generics: &mut Generics) {
+        self.params(&mut generics.params);
+        for pred in &mut generics.where_predicates {
+            match pred {
+                WherePredicate::BoundPredicate {
+                    type_,
+                    bounds,
+                    generic_params,
+                } => {
+                    self.ty(type_);
+                    self.bounds(bounds);
+                    self.params(generic_params);
+                }
+                WherePredicate::LifetimePredicate { .. } => (),
+                WherePredicate::EqPredicate { lhs, rhs } => {
+                    self.ty(lhs);
+                    self.term(rhs);
+                }
+            }
+        }
+    }
+
+    fn params(&mut self, params: &mut [GenericParamDef]) {
+        for param in params {
+            match &mut param.kind {
+                GenericParamDefKind::Lifetime { .. } => (),
+                GenericParamDefKind::Type {
+                    bounds, default, ..
+                } => {
+                    self.bounds(bounds);
+                    if let Some(ty) = default {
+                        self.ty(ty);
+                    }
+                }
+                GenericParamDefKind::Const { type_, .. } => self.ty(type_),
+            }
+        }
+    }
+
+    fn bounds(&mut self, bounds: &mut [GenericBound]) {
+        for bound in bounds {
+            if let GenericBound::TraitBound {
+                trait_,
+                generic_params,
+                ..
+            } = bound
+            {
+                self.path(trait_);
+                self.params(generic_params);
+            }
+        }
+    }
+
+    fn term(&mut self, term: &mut Term) {
+        if let Term::Type(ty) = term {
+            self.ty(ty);
+        }
+    }
+
+    fn path(&mut self, path: &mut Path) {
+        path.id = self.refer(&path.id);
+        if let Some(args) = &mut path.args {
+            self.args(args);
+        }
+    }
+
+    fn args(&mut self, args: &mut GenericArgs) {
+        match args {
+            GenericArgs::AngleBracketed { args, constraints } => {
+                for arg in args {
+                    if let GenericArg::Type(ty) = arg {
+                        self.ty(ty);
+                    }
+                }
+                for constraint in constraints {
+                    self.args(&mut constraint.args);
+                    match &mut constraint.binding {
+                        AssocItemConstraintKind::Equality(term) => self.term(term),
+                        AssocItemConstraintKind::Constraint(bounds) => self.bounds(bounds),
+                    }
+                }
+            }
+            GenericArgs::Parenthesized { inputs, output } => {
+                for ty in inputs {
+                    self.ty(ty);
+                }
+                if let Some(ty) = output {
+                    self.ty(ty);
+                }
+            }
+            GenericArgs::ReturnTypeNotation => (),
+        }
+    }
+
+    fn ty(&mut self, ty: &mut Type) {
+        match ty {
+            Type::ResolvedPath(path) => self.path(path),
+            Type::DynTrait(dyn_) => {
+                for poly in &mut dyn_.traits {
+                    self.path(&mut poly.trait_);
+                    self.params(&mut poly.generic_params);
+                }
+            }
+            Type::FunctionPointer(f) => {
+                self.params(&mut f.generic_params);
+                for (_, ty) in &mut f.sig.inputs {
+                    self.ty(ty);
+                }
+                if let Some(ty) = &mut f.sig.output {
+                    self.ty(ty);
+                }
+            }
+            Type::Tuple(tys) => tys.iter_mut().for_each(|ty| self.ty(ty)),
+            Type::Slice(ty)
+            | Type::Array { type_: ty, .. }
+            | Type::Pat { type_: ty, .. }
+            | Type::RawPointer { type_: ty, .. }
+            | Type::BorrowedRef { type_: ty, .. } => self.ty(ty),
+            Type::ImplTrait(bounds) => self.bounds(bounds),
+            Type::QualifiedPath {
+                args,
+                self_type,
+                trait_,
+                ..
+            } => {
+                self.args(args);
+                self.ty(self_type);
+                if let Some(path) = trait_ {
+                    self.path(path);
+                }
+            }
+            Type::Generic(_) | Type::Primitive(_) | Type::Infer => (),
+        }
+    }
+}
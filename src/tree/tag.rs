@@ -1,5 +1,5 @@
 use super::DocTree;
-use ratatui::style::{Color::*, Modifier, Style};
+use ratatui::style::{Color, Color::*, Modifier, Style};
 use termtree::GlyphPalette;
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -47,6 +47,23 @@ pub enum Tag {
     AssocFn,
     Implementors,
     Implementor,
+    DynCompatible,
+    NotDynCompatible,
+    AutoTrait,
+    UnsafeTrait,
+    Supertraits,
+    Supertrait,
+    RequiredItems,
+    ProvidedItems,
+    NoImplementors,
+    ImplementorsByOwnership,
+    ImplementorsByGenericity,
+    ImplementorsByLocality,
+    ImplementorGroup,
+    NoUsages,
+    Receivers,
+    Arguments,
+    Returns,
     Structs,
     Unions,
     Enums,
@@ -61,81 +78,223 @@ pub enum Tag {
     MacroDervs,
 }
 
-// for each normal item/list element
-fn fg(r: u8, g: u8, b: u8) -> Style {
-    Style::default().fg(Rgb(r, g, b))
+/// Tags sharing the same color, e.g. the `Structs` heading and `Struct` items.
+///
+/// Items are shown in the group color, list headings are bold and data structure
+/// names are bold and underlined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TagGroup {
+    Module,
+    Struct,
+    Field,
+    Union,
+    Enum,
+    Variant,
+    Trait,
+    Function,
+    Constant,
+    Static,
+    TypeAlias,
+    MacroDecl,
+    MacroFunc,
+    MacroAttr,
+    MacroDerv,
+    InherentImpl,
+    TraitImpl,
+    AutoImpl,
+    BlanketImpl,
+    Implementor,
+    Implementations,
+    /// Unknown tags and private fields.
+    Private,
+    /// Other headings like associated items.
+    Other,
 }
 
-// for the title of a list of items
-fn bfg(r: u8, g: u8, b: u8) -> Style {
-    Style::default()
-        .fg(Rgb(r, g, b))
-        .add_modifier(Modifier::BOLD)
+impl TagGroup {
+    pub const ALL: [TagGroup; 23] = [
+        TagGroup::Module,
+        TagGroup::Struct,
+        TagGroup::Field,
+        TagGroup::Union,
+        TagGroup::Enum,
+        TagGroup::Variant,
+        TagGroup::Trait,
+        TagGroup::Function,
+        TagGroup::Constant,
+        TagGroup::Static,
+        TagGroup::TypeAlias,
+        TagGroup::MacroDecl,
+        TagGroup::MacroFunc,
+        TagGroup::MacroAttr,
+        TagGroup::MacroDerv,
+        TagGroup::InherentImpl,
+        TagGroup::TraitImpl,
+        TagGroup::AutoImpl,
+        TagGroup::BlanketImpl,
+        TagGroup::Implementor,
+        TagGroup::Implementations,
+        TagGroup::Private,
+        TagGroup::Other,
+    ];
+
+    /// Snake case name, e.g. `type_alias`.
+    pub fn name(self) -> &'static str {
+        match self {
+            TagGroup::Module => "module",
+            TagGroup::Struct => "struct",
+            TagGroup::Field => "field",
+            TagGroup::Union => "union",
+            TagGroup::Enum => "enum",
+            TagGroup::Variant => "variant",
+            TagGroup::Trait => "trait",
+            TagGroup::Function => "function",
+            TagGroup::Constant => "constant",
+            TagGroup::Static => "static",
+            TagGroup::TypeAlias => "type_alias",
+            TagGroup::MacroDecl => "macro_decl",
+            TagGroup::MacroFunc => "macro_func",
+            TagGroup::MacroAttr => "macro_attr",
+            TagGroup::MacroDerv => "macro_derive",
+            TagGroup::InherentImpl => "inherent_impl",
+            TagGroup::TraitImpl => "trait_impl",
+            TagGroup::AutoImpl => "auto_impl",
+            TagGroup::BlanketImpl => "blanket_impl",
+            TagGroup::Implementor => "implementor",
+            TagGroup::Implementations => "implementations",
+            TagGroup::Private => "private",
+            TagGroup::Other => "other",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<TagGroup> {
+        TagGroup::ALL.into_iter().find(|group| group.name() == name)
+    }
+
+    /// The default color, which is designed for dark backgrounds.
+    pub const fn color(self) -> Color {
+        // Rgb(159, 234, 115), // #9FEA73
+        // Rgb(177, 84, 5),     // #B15405
+        match self {
+            TagGroup::Module => Rgb(213, 245, 85),        // #D5F555
+            TagGroup::Struct => Rgb(60, 148, 165),        // #3C94A5
+            TagGroup::Field => Rgb(137, 199, 210),        // #89C7D2
+            TagGroup::Union => Rgb(141, 218, 187),        // #8DDABB
+            TagGroup::Enum => Rgb(212, 61, 141),          // #D43D8D
+            TagGroup::Variant => Rgb(183, 64, 168),       // #B740A8
+            TagGroup::Trait => Rgb(255, 140, 41),         // #FF8C29
+            TagGroup::Function => Rgb(214, 83, 76),       // #D6534C
+            TagGroup::Constant => Rgb(232, 218, 104),     // #E8DA68
+            TagGroup::Static => Rgb(43, 43, 175),         // #2B2BAF
+            TagGroup::TypeAlias => Rgb(144, 99, 200),     // #9063C8
+            TagGroup::MacroDecl => Rgb(15, 129, 29),      // #0F811D
+            TagGroup::MacroFunc => Rgb(96, 215, 117),     // #60D775
+            TagGroup::MacroAttr => Rgb(159, 233, 27),     // #9FE91B
+            TagGroup::MacroDerv => Rgb(98, 152, 0),       // #629800
+            TagGroup::InherentImpl => Rgb(243, 101, 134), // #F36586
+            TagGroup::TraitImpl => Rgb(255, 195, 144),    // #FFC390
+            TagGroup::AutoImpl => Rgb(255, 140, 41),      // #FF8C29
+            TagGroup::BlanketImpl => Rgb(222, 186, 0),    // #DEBA00
+            TagGroup::Implementor => Rgb(111, 162, 255),  // #6FA2FF
+            TagGroup::Implementations => White,
+            TagGroup::Private => DarkGray,
+            // black fg on gray bg in bg/fg inversion
+            // If not set, black fg on black bg.
+            TagGroup::Other => Gray,
+        }
+    }
 }
 
+// for each normal item/list element
+const ITEM: Modifier = Modifier::empty();
+// for the title of a list of items
+const HEADING: Modifier = Modifier::BOLD;
 // for data structure name
-fn bufg(r: u8, g: u8, b: u8) -> Style {
-    Style::default()
-        .fg(Rgb(r, g, b))
-        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
-}
+const NAME: Modifier = Modifier::BOLD.union(Modifier::UNDERLINED);
 
 impl Tag {
-    pub fn style(self) -> Style {
-        // fg(159, 234, 115), // #9FEA73
-        // fg(Rgb(177, 84, 5)),     // #B15405
+    pub fn group(self) -> TagGroup {
         match self {
-            Tag::ModuleFolded => bufg(213, 245, 85), // #D5F555
+            Tag::Module | Tag::ModuleFolded => TagGroup::Module,
+            Tag::Structs | Tag::Struct => TagGroup::Struct,
+            Tag::Fields | Tag::Field | Tag::NoFields => TagGroup::Field,
+            Tag::Unions | Tag::Union => TagGroup::Union,
+            Tag::Enums | Tag::Enum => TagGroup::Enum,
+            Tag::Variants | Tag::Variant | Tag::NoVariants => TagGroup::Variant,
+            Tag::Traits | Tag::Trait | Tag::Supertraits | Tag::Supertrait => TagGroup::Trait,
+            Tag::Functions | Tag::Function => TagGroup::Function,
+            Tag::Receivers | Tag::Arguments | Tag::Returns => TagGroup::Function,
+            Tag::Constants | Tag::Constant => TagGroup::Constant,
+            Tag::Statics | Tag::Static => TagGroup::Static,
+            Tag::TypeAliass | Tag::TypeAlias => TagGroup::TypeAlias,
+            Tag::MacroDecls | Tag::MacroDecl => TagGroup::MacroDecl,
+            Tag::MacroFuncs | Tag::MacroFunc => TagGroup::MacroFunc,
+            Tag::MacroAttrs | Tag::MacroAttr => TagGroup::MacroAttr,
+            Tag::MacroDervs | Tag::MacroDerv => TagGroup::MacroDerv,
+            Tag::InherentImpls | Tag::ImplInherent => TagGroup::InherentImpl,
+            Tag::TraitImpls | Tag::ImplTrait => TagGroup::TraitImpl,
+            Tag::AutoImpls | Tag::ImplAuto => TagGroup::AutoImpl,
+            Tag::BlanketImpls | Tag::ImplBlanket => TagGroup::BlanketImpl,
+            Tag::Implementors
+            | Tag::Implementor
+            | Tag::ImplementorsByOwnership
+            | Tag::ImplementorsByGenericity
+            | Tag::ImplementorsByLocality
+            | Tag::ImplementorGroup => TagGroup::Implementor,
+            Tag::Implementations => TagGroup::Implementations,
+            Tag::Unknown | Tag::FieldsPrivate => TagGroup::Private,
+            _ => TagGroup::Other,
+        }
+    }
 
-            Tag::Module => bfg(213, 245, 85),     // #D5F555
-            Tag::Structs => bfg(60, 148, 165),    // #3C94A5
-            Tag::Struct => bufg(60, 148, 165),    // #3C94A5
-            Tag::Fields => bfg(137, 199, 210),    // #89C7D2
-            Tag::Field => fg(137, 199, 210),      // #89C7D2
-            Tag::NoFields => fg(137, 199, 210),   // #89C7D2
-            Tag::Unions => bufg(141, 218, 187),   // #8DDABB
-            Tag::Union => bufg(141, 218, 187),    // #8DDABB
-            Tag::Enums => bfg(212, 61, 141),      // #D43D8D
-            Tag::Enum => bufg(212, 61, 141),      // #D43D8D
-            Tag::Variants => bfg(183, 64, 168),   // #B740A8
-            Tag::Variant => fg(183, 64, 168),     // #B740A8
-            Tag::NoVariants => fg(183, 64, 168),  // #B740A8
-            Tag::Traits => bfg(255, 140, 41),     // #FF8C29
-            Tag::Trait => bufg(255, 140, 41),     // #FF8C29
-            Tag::Functions => bfg(214, 83, 76),   // #D6534C
-            Tag::Function => fg(214, 83, 76),     // #D6534C
-            Tag::Constants => bfg(232, 218, 104), // #E8DA68
-            Tag::Constant => fg(232, 218, 104),   // #E8DA68
-            Tag::Statics => bfg(43, 43, 175),     // #2B2BAF
-            Tag::Static => bfg(43, 43, 175),      // #2B2BAF
-            Tag::TypeAliass => bfg(144, 99, 200), // #9063C8
-            Tag::TypeAlias => fg(144, 99, 200),   // #9063C8
-            Tag::MacroDecls => bfg(15, 129, 29),  // #0F811D
-            Tag::MacroDecl => fg(15, 129, 29),    // #0F811D
-            Tag::MacroFuncs => bfg(96, 215, 117), // #60D775
-            Tag::MacroFunc => fg(96, 215, 117),   // #60D775
-            Tag::MacroAttrs => bfg(159, 233, 27), // #9FE91B
-            Tag::MacroAttr => fg(159, 233, 27),   // #9FE91B
-            Tag::MacroDervs => bfg(98, 152, 0),   // #629800
-            Tag::MacroDerv => fg(98, 152, 0),     // #629800
-            Tag::Unknown | Tag::FieldsPrivate => Style::default().fg(DarkGray),
-            Tag::Implementations => Style::default().fg(White),
-            Tag::InherentImpls => bfg(243, 101, 134), // #F36586
-            Tag::ImplInherent => fg(243, 101, 134),   // #F36586
-            Tag::TraitImpls => bfg(255, 195, 144),    // #FFC390
-            Tag::ImplTrait => fg(255, 195, 144),      // #FFC390
-            Tag::AutoImpls => bfg(255, 140, 41),      // #FF8C29
-            Tag::ImplAuto => fg(255, 140, 41),        // #FF8C29
-            Tag::BlanketImpls => bfg(222, 186, 0),    // #DEBA00
-            Tag::ImplBlanket => fg(222, 186, 0),      // #DEBA00
-            Tag::Implementors => bfg(111, 162, 255),  // #6FA2FF
-            Tag::Implementor => fg(111, 162, 255),    // #6FA2FF
-            // black fg on gray bg in bg/fg inversion
-            // If not set, black fg on black bg.
-            _ => Style::default().fg(Gray),
+    fn modifier(self) -> Modifier {
+        match self {
+            Tag::ModuleFolded | Tag::Struct | Tag::Unions | Tag::Union | Tag::Enum | Tag::Trait => {
+                NAME
+            }
+            Tag::Module
+            | Tag::Structs
+            | Tag::Fields
+            | Tag::Enums
+            | Tag::Variants
+            | Tag::Traits
+            | Tag::Functions
+            | Tag::Constants
+            | Tag::Statics
+            | Tag::Static
+            | Tag::TypeAliass
+            | Tag::MacroDecls
+            | Tag::MacroFuncs
+            | Tag::MacroAttrs
+            | Tag::MacroDervs
+            | Tag::InherentImpls
+            | Tag::TraitImpls
+            | Tag::AutoImpls
+            | Tag::BlanketImpls
+            | Tag::Implementors
+            | Tag::Supertraits
+            | Tag::RequiredItems
+            | Tag::ProvidedItems
+            | Tag::ImplementorsByOwnership
+            | Tag::ImplementorsByGenericity
+            | Tag::ImplementorsByLocality
+            | Tag::ImplementorGroup
+            | Tag::Receivers
+            | Tag::Arguments
+            | Tag::Returns => HEADING,
+            _ => ITEM,
         }
     }
 
+    pub fn style(self) -> Style {
+        self.style_with(self.group().color())
+    }
+
+    /// The style in a custom color of [`TagGroup`].
+    pub fn style_with(self, color: Color) -> Style {
+        Style::default().fg(color).add_modifier(self.modifier())
+    }
+
     pub fn glyph(self) -> GlyphPalette {
         match self {
             Tag::ModuleFolded => icon!(@fold "[Mod]"),
@@ -143,7 +302,7 @@ impl Tag {
             Tag::Struct => icon!("[Struct]"),
             Tag::Union => icon!("[Union]"),
             Tag::Enum => icon!("[Enum]"),
-            Tag::Trait => icon!("[Trait]"),
+            Tag::Trait | Tag::Supertrait => icon!("[Trait]"),
             Tag::Function => icon!("[Fn]"),
             Tag::Constant => icon!("[Const]"),
             Tag::Static => icon!("[Static]"),
@@ -184,6 +343,21 @@ impl Tag {
             Tag::AssocConsts => "Associated Constants",
             Tag::AssocFns => "Associated Functions",
             Tag::Implementors => "Implementors",
+            Tag::DynCompatible => "Dyn Compatible",
+            Tag::NotDynCompatible => "Not Dyn Compatible",
+            Tag::AutoTrait => "Auto Trait",
+            Tag::UnsafeTrait => "Unsafe Trait",
+            Tag::Supertraits => "Supertraits",
+            Tag::RequiredItems => "Required Items",
+            Tag::ProvidedItems => "Provided Items",
+            Tag::NoImplementors => "No Implementors!",
+            Tag::ImplementorsByOwnership => "By Ownership",
+            Tag::ImplementorsByGenericity => "By Concrete Or Generic",
+            Tag::ImplementorsByLocality => "By Local Or Foreign",
+            Tag::NoUsages => "No Function Uses The Type!",
+            Tag::Receivers => "As Receiver",
+            Tag::Arguments => "As Argument",
+            Tag::Returns => "As Return Type",
             Tag::Structs => "Structs",
             Tag::Unions => "Unions",
             Tag::Enums => "Enums",
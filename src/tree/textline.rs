@@ -1,4 +1,3 @@
-use self::fold::Fold;
 use crate::{
     tree::{CrateDoc, DocTree, Tag},
     util::XString,
@@ -14,6 +13,8 @@ use unicode_width::UnicodeWidthStr;
 
 mod fold;
 
+pub use self::fold::Fold;
+
 /// Tagged text including headings and nodes.
 #[derive(Clone, Default)]
 pub struct TextTag {
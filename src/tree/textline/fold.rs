@@ -2,9 +2,10 @@ use super::TreeLines;
 use crate::tree::{DModule, DocTree, IDMap};
 use rustc_hash::FxHashSet as HashSet;
 use rustdoc_types::{Id, ItemEnum};
+use serde::{Deserialize, Serialize};
 
 /// how to fold the text tree
-#[derive(Default, PartialEq, Eq)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 enum Kind {
     /// Expand all public items in all modules.
     #[default]
@@ -28,7 +29,9 @@ enum Kind {
 }
 
 /// Fold based on module tree.
-#[derive(Default)]
+///
+/// It can be persisted and restored via [`TreeLines::fold`] and [`TreeLines::restore_fold`].
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Fold {
     kind: Kind,
     /// module IDs that should be expanded
@@ -113,24 +116,6 @@ impl TreeLines {
     ///
     /// This pushs a module ID to a without setting any fold kind.
     pub fn expand_toggle(&mut self, id: Id) {
-        fn modules_traversal(
-            dmod: &DModule,
-            map: &IDMap,
-            parent: &mut DocTree,
-            should_stop: &mut impl FnMut(&DModule) -> bool,
-        ) {
-            for m in &dmod.modules {
-                if should_stop(m) {
-                    let node = node!(ModuleFolded: map, Module, m.id);
-                    parent.push(node);
-                } else {
-                    let mut node = m.item_tree_only_in_one_specified_mod(map);
-                    modules_traversal(m, map, &mut node, should_stop);
-                    parent.push(node);
-                };
-            }
-        }
-
         if self.fold.kind == Kind::CurrentModule {
             // FIXME: poor interaction with CurrentModule bahavior
             //
@@ -151,12 +136,49 @@ impl TreeLines {
         } else {
             mods.insert(id);
         }
+        self.expand_modules_in_set();
+    }
+
+    /// Only expand the modules in the expand set with others folded.
+    fn expand_modules_in_set(&mut self) {
+        fn modules_traversal(
+            dmod: &DModule,
+            map: &IDMap,
+            parent: &mut DocTree,
+            should_stop: &mut impl FnMut(&DModule) -> bool,
+        ) {
+            for m in &dmod.modules {
+                if should_stop(m) {
+                    let node = node!(ModuleFolded: map, Module, m.id);
+                    parent.push(node);
+                } else {
+                    let mut node = m.item_tree_only_in_one_specified_mod(map);
+                    modules_traversal(m, map, &mut node, should_stop);
+                    parent.push(node);
+                };
+            }
+        }
+
         self.update_cached_lines(|dmod, map, mods| {
             let mut root = dmod.item_tree_only_in_one_specified_mod(map);
             modules_traversal(dmod, map, &mut root, &mut |m| !mods.contains(&m.id));
             root
         });
     }
+
+    pub fn fold(&self) -> &Fold {
+        &self.fold
+    }
+
+    /// Fold the tree as the state returned by [`TreeLines::fold`] from the same doc.
+    pub fn restore_fold(&mut self, fold: Fold) {
+        self.fold = fold;
+        if self.fold.kind == Kind::CurrentModule {
+            self._expand_current_module_only();
+        } else {
+            self.expand_modules_in_set();
+        }
+    }
 }
 
 impl TreeLines {
@@ -0,0 +1,219 @@
+//! An index from types to functions and methods whose signatures use them.
+
+use super::{DocTree, IDMap, ItemPath, Tag};
+use crate::{
+    type_name::style::long,
+    util::{hashmap, xformat, HashMap, XString},
+};
+use rustc_hash::FxHashSet as HashSet;
+use rustdoc_types::{GenericArg, GenericArgs, Id, ItemEnum, Type};
+
+/// Functions and methods that use a type, sorted by their paths.
+#[derive(Debug, Default)]
+pub struct FnsUsingType {
+    /// Methods whose `self` is the type.
+    pub receivers: Vec<ItemPath>,
+    /// Functions that accept the type as an argument other than `self`.
+    pub arguments: Vec<ItemPath>,
+    /// Functions that return the type.
+    pub returns: Vec<ItemPath>,
+}
+
+impl FnsUsingType {
+    pub fn is_empty(&self) -> bool {
+        self.receivers.is_empty() && self.arguments.is_empty() && self.returns.is_empty()
+    }
+
+    /// Three headings for the functions under the root, with empty ones omitted.
+    pub fn show_prettier(&self, mut root: DocTree) -> DocTree {
+        if self.is_empty() {
+            root.push(Tag::NoUsages.show());
+            return root;
+        }
+        let groups = [
+            (Tag::Receivers, &self.receivers),
+            (Tag::Arguments, &self.arguments),
+            (Tag::Returns, &self.returns),
+        ];
+        for (tag, fns) in groups {
+            if !fns.is_empty() {
+                let leaves = fns
+                    .iter()
+                    .map(|f| DocTree::new(f.path.clone(), Tag::Function, Some(f.id)));
+                root.push(tag.show().with_leaves(leaves));
+            }
+        }
+        root
+    }
+}
+
+/// Types to functions whose signatures use them.
+///
+/// `&T`, `&mut T`, `Box<T>`, `Option<T>` and `Result<T, _>` count as `T`, and
+/// `Self` in an impl block is the implementor type.
+#[derive(Default)]
+pub struct TypeUsages {
+    types: HashMap<Id, FnsUsingType>,
+}
+
+impl TypeUsages {
+    pub fn new(map: &IDMap) -> Self {
+        let owners = FnOwners::new(map);
+        let mut usages = TypeUsages { types: hashmap(64) };
+        for (id, item) in map.indexmap() {
+            let ItemEnum::Function(f) = &item.inner else {
+                continue;
+            };
+            if owners.is_blanket(id) {
+                continue;
+            }
+            let self_ty = owners.owner(id).and_then(|owner| owner.self_ty);
+            let path = owners.item_path(*id, map);
+            for (idx, (name, ty)) in f.sig.inputs.iter().enumerate() {
+                if idx == 0 && name == "self" {
+                    if let Some(ty) = self_ty {
+                        usages.entry(ty).receivers.push(path.clone());
+                    }
+                } else if let Some(ty) = peel(ty, self_ty, map) {
+                    usages.entry(ty).arguments.push(path.clone());
+                }
+            }
+            if let Some(ty) = f.sig.output.as_ref().and_then(|ty| peel(ty, self_ty, map)) {
+                usages.entry(ty).returns.push(path);
+            }
+        }
+        for fns in usages.types.values_mut() {
+            for v in [&mut fns.receivers, &mut fns.arguments, &mut fns.returns] {
+                v.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+                // a function may accept the type in several arguments
+                v.dedup_by_key(|f| f.id);
+            }
+        }
+        info!(
+            "indexed {} types used in function signatures",
+            usages.types.len()
+        );
+        usages
+    }
+
+    fn entry(&mut self, ty: Id) -> &mut FnsUsingType {
+        self.types.entry(ty).or_default()
+    }
+
+    pub fn get(&self, id: &Id) -> Option<&FnsUsingType> {
+        self.types.get(id)
+    }
+}
+
+/// Impl blocks and traits where methods are defined.
+pub(crate) struct FnOwners {
+    owners: Vec<Owner>,
+    methods: HashMap<Id, usize>,
+    blanket: HashSet<Id>,
+}
+
+pub(crate) struct Owner {
+    /// The impl block or trait.
+    pub id: Id,
+    /// The implementor type, or None in traits.
+    pub self_ty: Option<Id>,
+    path: XString,
+    outer: Option<Id>,
+}
+
+impl FnOwners {
+    pub fn new(map: &IDMap) -> Self {
+        let index = map.indexmap();
+        let mut fns = FnOwners {
+            owners: Vec::new(),
+            methods: hashmap(index.len() / 4),
+            blanket: HashSet::default(),
+        };
+        for (&id, item) in index {
+            let (items, owner) = match &item.inner {
+                // items in a blanket impl are shared by all implementors, thus skipped
+                ItemEnum::Impl(imp) if imp.blanket_impl.is_some() => {
+                    fns.blanket.extend(imp.items.iter().copied());
+                    continue;
+                }
+                ItemEnum::Impl(imp) => {
+                    let self_ty = peel(&imp.for_, None, map);
+                    let owner = Owner {
+                        id,
+                        self_ty,
+                        path: self_ty.map_or_else(|| long(&imp.for_).into(), |ty| map.path(&ty)),
+                        // only local types have the inner tree to jump to
+                        outer: self_ty.filter(|ty| map.get_item(ty).is_some()),
+                    };
+                    (&imp.items, owner)
+                }
+                // `Self` is generic in a trait
+                ItemEnum::Trait(t) => (
+                    &t.items,
+                    Owner {
+                        id,
+                        self_ty: None,
+                        path: map.path(&id),
+                        outer: Some(id),
+                    },
+                ),
+                _ => continue,
+            };
+            let idx = fns.owners.len();
+            fns.methods.extend(items.iter().map(|&assoc| (assoc, idx)));
+            fns.owners.push(owner);
+        }
+        fns
+    }
+
+    /// Whether the item is defined in a blanket impl.
+    pub fn is_blanket(&self, id: &Id) -> bool {
+        self.blanket.contains(id)
+    }
+
+    /// The impl block or trait of an associated item, or None for a free function.
+    pub fn owner(&self, id: &Id) -> Option<&Owner> {
+        self.methods.get(id).map(|&idx| &self.owners[idx])
+    }
+
+    /// The path of a free function, or `Type::method` and `Trait::method` for methods.
+    pub fn item_path(&self, id: Id, map: &IDMap) -> ItemPath {
+        match self.owner(&id) {
+            Some(owner) => ItemPath {
+                id,
+                path: xformat!("{}::{}", owner.path, map.name(&id)),
+                outer: owner.outer,
+            },
+            None => ItemPath {
+                id,
+                path: map.path(&id),
+                outer: None,
+            },
+        }
+    }
+}
+
+/// The type in `T`, `&T`, `&mut T`, `Box<T>`, `Option<T>` or `Result<T, _>`.
+fn peel(ty: &Type, self_ty: Option<Id>, map: &IDMap) -> Option<Id> {
+    match ty {
+        Type::BorrowedRef { type_, .. } => peel(type_, self_ty, map),
+        Type::Generic(name) if name == "Self" => self_ty,
+        Type::ResolvedPath(p) => {
+            let path = map.path(&p.id);
+            if !matches!(
+                &*path,
+                "alloc::boxed::Box" | "core::option::Option" | "core::result::Result"
+            ) {
+                return Some(p.id);
+            }
+            let Some(GenericArgs::AngleBracketed { args, .. }) = p.args.as_deref() else {
+                return None;
+            };
+            match args.first()? {
+                GenericArg::Type(inner) => peel(inner, self_ty, map),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,114 @@
+//! Functions that accept or return generic types with equivalent or stronger trait bounds
+//! than a generic type parameter.
+//!
+//! Type parameters in arguments and return types are collected for each function, as well
+//! as `impl Trait` in both positions. A reference to them counts, but a type holding them
+//! like `Vec<T>` doesn't.
+//!
+//! Bounds are compared in the normalised form of [`GenericBounds::bounds`], so `T: Read`
+//! in a where clause is the same as `impl Read` in an argument. Supertraits are not taken
+//! into account: `T: Ord` doesn't meet `PartialOrd`.
+
+use super::candidates::{generic_bounds_with_owner, generics_of, GenericBounds};
+use crate::tree::{FnOwners, IDMap, ItemPath};
+use rustdoc_types::{ItemEnum, Type};
+
+/// A function with a generic type in its arguments or return type.
+#[derive(Debug, Clone)]
+pub struct BoundedFn {
+    pub item: ItemPath,
+    /// The type parameter like `T: Read`, or `impl Read`.
+    pub param: GenericBounds,
+}
+
+/// Functions with generic types of trait bounds in a crate.
+#[derive(Default)]
+pub struct BoundedFns {
+    /// Sorted by paths.
+    accepting: Vec<BoundedFn>,
+    /// Sorted by paths.
+    returning: Vec<BoundedFn>,
+}
+
+impl BoundedFns {
+    pub fn new(map: &IDMap) -> Self {
+        let owners = FnOwners::new(map);
+        let mut fns = BoundedFns::default();
+        for (id, item) in map.indexmap() {
+            let ItemEnum::Function(f) = &item.inner else {
+                continue;
+            };
+            if owners.is_blanket(id) {
+                continue;
+            }
+            let owner = owners
+                .owner(id)
+                .and_then(|owner| map.get_item(&owner.id))
+                .and_then(|owner| generics_of(&owner.inner));
+            let params = generic_bounds_with_owner(&item.inner, owner, map);
+
+            let mut accepted = Vec::new();
+            for (_, ty) in &f.sig.inputs {
+                generic_type(ty, &params, map, &mut accepted);
+            }
+            let mut returned = Vec::new();
+            if let Some(ty) = &f.sig.output {
+                generic_type(ty, &params, map, &mut returned);
+            }
+            if accepted.is_empty() && returned.is_empty() {
+                continue;
+            }
+            let item = owners.item_path(*id, map);
+            let bounded = |param| BoundedFn {
+                item: item.clone(),
+                param,
+            };
+            fns.accepting.extend(accepted.into_iter().map(bounded));
+            fns.returning.extend(returned.into_iter().map(bounded));
+        }
+        fns.accepting.sort_by(|a, b| a.item.path.cmp(&b.item.path));
+        fns.returning.sort_by(|a, b| a.item.path.cmp(&b.item.path));
+        info!(
+            "collected {} functions accepting and {} returning generic types in {}",
+            fns.accepting.len(),
+            fns.returning.len(),
+            map.crate_name()
+        );
+        fns
+    }
+
+    /// Functions that accept a generic type with equivalent or stronger bounds.
+    pub fn accepting(&self, param: &GenericBounds) -> Vec<BoundedFn> {
+        matched(&self.accepting, param)
+    }
+
+    /// Functions that return a generic type with equivalent or stronger bounds.
+    pub fn returning(&self, param: &GenericBounds) -> Vec<BoundedFn> {
+        matched(&self.returning, param)
+    }
+}
+
+fn matched(fns: &[BoundedFn], param: &GenericBounds) -> Vec<BoundedFn> {
+    fns.iter()
+        .filter(|f| param.is_satisfied_by(&f.param))
+        .cloned()
+        .collect()
+}
+
+/// Push the type parameter or `impl Trait` if it's the type or behind a reference.
+/// Parameters without bounds are skipped, since no bound is met by them.
+fn generic_type(ty: &Type, params: &[GenericBounds], map: &IDMap, found: &mut Vec<GenericBounds>) {
+    let param = match ty {
+        Type::BorrowedRef { type_, .. } => return generic_type(type_, params, map, found),
+        Type::Generic(name) => match params.iter().find(|p| p.name == *name) {
+            Some(param) => param.clone(),
+            None => return,
+        },
+        Type::ImplTrait(bounds) => GenericBounds::impl_trait(ty, bounds, map),
+        _ => return,
+    };
+    // a function may accept the type in several arguments
+    if !param.bounds.is_empty() && !found.iter().any(|p| p.name == param.name) {
+        found.push(param);
+    }
+}
@@ -0,0 +1,294 @@
+//! Concrete types that meet the trait bounds on a generic type parameter.
+//!
+//! Implementors are collected from implementations of local traits and impls on local
+//! structs, enums and unions. Traits are keyed by their full paths instead of ids, so
+//! bounds from one crate can be checked against implementors in docs of other crates.
+//!
+//! Only the paths of traits are compared: generic arguments like `Iterator<Item = u8>`
+//! and the bounds on generic impl blocks are not checked.
+
+use super::style::{generic_args, long, long_path};
+use crate::{
+    tree::IDMap,
+    util::{hashmap, xformat, HashMap, XString},
+};
+use rustdoc_types::{
+    GenericBound, GenericParamDefKind, Generics, Id, ItemEnum, TraitBoundModifier, Type,
+    WherePredicate,
+};
+use std::fmt;
+
+/// `Sized` is implicit for all types, so there is no impl block for it in json docs.
+const SIZED: &str = "core::marker::Sized";
+
+/// A generic type parameter and the traits it's bound to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenericBounds {
+    /// The name like `T`, or `impl Trait` for an anonymous parameter.
+    pub name: XString,
+    /// Full paths of the bound traits like `std::io::Read`.
+    pub traits: Vec<XString>,
+    /// Full paths with generic args like `core::iter::Iterator<Item = u8>`, which
+    /// normalise bounds from parameter lists, where clauses and `impl Trait`.
+    pub bounds: Vec<XString>,
+    /// The bounds as written, like `Read` or `Iterator<Item = u8>`.
+    written: Vec<XString>,
+}
+
+impl GenericBounds {
+    fn new(name: &str) -> Self {
+        GenericBounds {
+            name: name.into(),
+            traits: Vec::new(),
+            bounds: Vec::new(),
+            written: Vec::new(),
+        }
+    }
+
+    /// Bounds in `impl Trait`, named as the type.
+    pub(crate) fn impl_trait(ty: &Type, bounds: &[GenericBound], map: &IDMap) -> Self {
+        let mut param = GenericBounds::new(&long(ty));
+        param.extend(bounds, map);
+        param
+    }
+
+    /// The other parameter carries equivalent or stronger bounds, i.e. all the bounds
+    /// here. No bound means nothing to compare with.
+    pub fn is_satisfied_by(&self, other: &GenericBounds) -> bool {
+        !self.bounds.is_empty() && self.bounds.iter().all(|b| other.bounds.contains(b))
+    }
+
+    fn extend(&mut self, bounds: &[GenericBound], map: &IDMap) {
+        for bound in bounds {
+            let GenericBound::TraitBound {
+                trait_, modifier, ..
+            } = bound
+            else {
+                continue;
+            };
+            // `?Sized` relaxes a bound instead of adding one
+            if !matches!(modifier, TraitBoundModifier::None) {
+                continue;
+            }
+            let path = map.path(&trait_.id);
+            if path == SIZED {
+                continue;
+            }
+            let bound = match trait_.args.as_deref() {
+                Some(args) => xformat!("{path}{}", generic_args(args)),
+                None => path.clone(),
+            };
+            if self.bounds.contains(&bound) {
+                continue;
+            }
+            self.bounds.push(bound);
+            if !self.traits.contains(&path) {
+                self.traits.push(path);
+            }
+            self.written.push(long_path(trait_).into());
+        }
+    }
+}
+
+/// `R: Read + Send`, or `R` without bounds. `impl Trait` has its bounds in the name.
+impl fmt::Display for GenericBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if !self.written.is_empty() && !self.name.starts_with("impl ") {
+            write!(f, ": {}", self.written.join(" + "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Type parameters of an item with their trait bounds from both the parameter list and
+/// where clauses. For an associated function, parameters on the impl block or trait
+/// come first.
+pub fn generic_bounds(id: &Id, map: &IDMap) -> Vec<GenericBounds> {
+    let Some(item) = map.get_item(id) else {
+        return Vec::new();
+    };
+    let owner = match &item.inner {
+        ItemEnum::Function(_) => owner_generics(id, map),
+        _ => None,
+    };
+    generic_bounds_with_owner(&item.inner, owner, map)
+}
+
+/// Like [`generic_bounds`], but the generics on the impl block or trait are given.
+pub(crate) fn generic_bounds_with_owner(
+    inner: &ItemEnum,
+    owner: Option<&Generics>,
+    map: &IDMap,
+) -> Vec<GenericBounds> {
+    let mut params = Vec::new();
+    if let Some(outer) = owner {
+        collect(outer, map, &mut params);
+    }
+    if let Some(generics) = generics_of(inner) {
+        collect(generics, map, &mut params);
+    }
+    params
+}
+
+pub(crate) fn generics_of(inner: &ItemEnum) -> Option<&Generics> {
+    Some(match inner {
+        ItemEnum::Function(f) => &f.generics,
+        ItemEnum::Struct(s) => &s.generics,
+        ItemEnum::Enum(e) => &e.generics,
+        ItemEnum::Union(u) => &u.generics,
+        ItemEnum::Trait(t) => &t.generics,
+        ItemEnum::TypeAlias(t) => &t.generics,
+        ItemEnum::Impl(imp) => &imp.generics,
+        _ => return None,
+    })
+}
+
+/// Generics on the impl block or trait that the associated function is defined in.
+fn owner_generics<'a>(id: &Id, map: &'a IDMap) -> Option<&'a Generics> {
+    map.indexmap().values().find_map(|item| match &item.inner {
+        ItemEnum::Impl(imp) if imp.items.contains(id) => Some(&imp.generics),
+        ItemEnum::Trait(t) if t.items.contains(id) => Some(&t.generics),
+        _ => None,
+    })
+}
+
+fn collect(generics: &Generics, map: &IDMap, params: &mut Vec<GenericBounds>) {
+    fn param<'p>(params: &'p mut Vec<GenericBounds>, name: &str) -> &'p mut GenericBounds {
+        match params.iter().position(|p| p.name == name) {
+            Some(idx) => &mut params[idx],
+            None => {
+                params.push(GenericBounds::new(name));
+                params.last_mut().unwrap()
+            }
+        }
+    }
+
+    for def in &generics.params {
+        if let GenericParamDefKind::Type { bounds, .. } = &def.kind {
+            param(params, &def.name).extend(bounds, map);
+        }
+    }
+    for pred in &generics.where_predicates {
+        if let WherePredicate::BoundPredicate {
+            type_: Type::Generic(name),
+            bounds,
+            ..
+        } = pred
+        {
+            param(params, name).extend(bounds, map);
+        }
+    }
+}
+
+/// A concrete type that implements some traits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    /// The full path of a type, or the type as written like `u8` or `&str`.
+    pub ty: XString,
+    /// The item of the type if it's defined in the crate.
+    pub id: Option<Id>,
+}
+
+impl Candidate {
+    fn new(ty: &Type, map: &IDMap) -> Option<Candidate> {
+        if !is_concrete(ty) {
+            return None;
+        }
+        Some(match ty {
+            Type::ResolvedPath(p) => Candidate {
+                ty: map.path(&p.id),
+                id: map.get_item(&p.id).map(|_| p.id),
+            },
+            _ => Candidate {
+                ty: long(ty).into(),
+                id: None,
+            },
+        })
+    }
+}
+
+/// Generic types like `T` or `&T` from blanket impls are not candidates, but `Vec<T>` is.
+fn is_concrete(ty: &Type) -> bool {
+    match ty {
+        Type::ResolvedPath(_) | Type::Primitive(_) => true,
+        Type::BorrowedRef { type_, .. }
+        | Type::RawPointer { type_, .. }
+        | Type::Slice(type_)
+        | Type::Array { type_, .. } => is_concrete(type_),
+        Type::Tuple(types) => types.iter().all(is_concrete),
+        _ => false,
+    }
+}
+
+/// Implementor types of each trait in a crate.
+#[derive(Default)]
+pub struct TraitImplementors {
+    /// Full paths of traits to implementors sorted by `Candidate::ty`.
+    traits: HashMap<XString, Vec<Candidate>>,
+}
+
+impl TraitImplementors {
+    pub fn new(map: &IDMap) -> Self {
+        let mut implementors = TraitImplementors {
+            traits: hashmap(64),
+        };
+        for item in map.indexmap().values() {
+            let impls = match &item.inner {
+                ItemEnum::Trait(t) => &t.implementations,
+                ItemEnum::Struct(s) => &s.impls,
+                ItemEnum::Enum(e) => &e.impls,
+                ItemEnum::Union(u) => &u.impls,
+                _ => continue,
+            };
+            for id in impls {
+                implementors.push_impl(id, map);
+            }
+        }
+        // an impl of a local trait for a local type is visited twice
+        for types in implementors.traits.values_mut() {
+            types.sort_unstable_by(|a, b| a.ty.cmp(&b.ty));
+            types.dedup_by(|a, b| a.ty == b.ty);
+        }
+        info!(
+            "collected implementors of {} traits in {}",
+            implementors.traits.len(),
+            map.crate_name()
+        );
+        implementors
+    }
+
+    fn push_impl(&mut self, id: &Id, map: &IDMap) {
+        let Some(ItemEnum::Impl(imp)) = map.get_item(id).map(|item| &item.inner) else {
+            return;
+        };
+        let Some(trait_) = imp.trait_.as_ref().filter(|_| !imp.is_negative) else {
+            return;
+        };
+        if let Some(ty) = Candidate::new(&imp.for_, map) {
+            let types = self.traits.entry(map.path(&trait_.id)).or_default();
+            types.push(ty);
+        }
+    }
+
+    /// Types that implement all the traits, sorted by their paths.
+    /// No bound means no candidate, instead of all types.
+    pub fn candidates(&self, traits: &[XString]) -> Vec<Candidate> {
+        let Some((first, rest)) = traits.split_first() else {
+            return Vec::new();
+        };
+        let Some(types) = self.traits.get(first) else {
+            return Vec::new();
+        };
+        let implements = |ty: &Candidate, path: &XString| {
+            self.traits
+                .get(path)
+                .is_some_and(|types| types.binary_search_by(|t| t.ty.cmp(&ty.ty)).is_ok())
+        };
+        types
+            .iter()
+            .filter(|ty| rest.iter().all(|path| implements(ty, path)))
+            .cloned()
+            .collect()
+    }
+}
@@ -1,5 +1,11 @@
+mod bounded_fns;
+mod candidates;
 mod render;
+mod signature;
 pub(crate) mod style;
 
+pub use bounded_fns::{BoundedFn, BoundedFns};
+pub use candidates::{generic_bounds, Candidate, GenericBounds, TraitImplementors};
 pub use render::{DeclarationLine, DeclarationLines, TextTag};
+pub use signature::{fn_signature, SignatureIndex, SignatureQuery};
 pub use style::StyledType;
@@ -0,0 +1,511 @@
+//! Type-directed search on function signatures.
+//!
+//! A query looks like `&str, usize -> Result<_, Error>`:
+//! * inputs are separated by `,` and matched against arguments in any order, but each
+//!   argument can only be used once; extra arguments in a function are allowed
+//! * the output after `->` is optional; if it's absent, any output is accepted
+//! * `_` matches any type
+//! * a single uppercase letter like `T` is a placeholder that matches any type, but the
+//!   same placeholder must refer to the same type in a signature
+//! * `impl Trait1 + Trait2` or `dyn Trait` matches generic types, `impl Trait` or
+//!   `dyn Trait` that hold all the trait bounds
+//! * only the last component of a path is compared, so `io::Result<T>` and `Result<T>` are
+//!   the same
+
+use super::style::long;
+use crate::{
+    tree::IDMap,
+    util::{hashmap, xformat, HashMap, XString},
+};
+use rustdoc_types::{
+    GenericArg, GenericArgs, GenericBound, GenericParamDefKind, Generics, Id, ItemEnum, Path, Type,
+    WherePredicate,
+};
+
+/// A parsed signature query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureQuery {
+    inputs: Vec<Pattern>,
+    output: Option<Pattern>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pattern {
+    /// `_`
+    Any,
+    /// A single uppercase letter optionally followed by digits, like `T` or `T1`.
+    Placeholder(XString),
+    /// A path or primitive type with optional generic arguments.
+    /// Only the last name in the path is stored.
+    Path {
+        name: XString,
+        args: Vec<Pattern>,
+    },
+    Ref {
+        mutable: bool,
+        inner: Box<Pattern>,
+    },
+    /// `[T]` or `[T; N]`
+    Slice(Box<Pattern>),
+    Tuple(Vec<Pattern>),
+    /// Names of traits from `impl Trait + ...` or `dyn Trait + ...`.
+    Bounds(Vec<XString>),
+}
+
+impl SignatureQuery {
+    /// Returns None if the query is not valid.
+    pub fn parse(query: &str) -> Option<Self> {
+        let tokens = tokenize(query)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let query = parser.query()?;
+        // all tokens must be consumed
+        (parser.pos == parser.tokens.len()).then_some(query)
+    }
+
+    /// Check if the function item satisfies the query.
+    ///
+    /// Returns the count of extra arguments that are not required by the query if matched.
+    /// So the less the better.
+    pub fn matches(&self, id: &Id, map: &IDMap, index: &SignatureIndex) -> Option<usize> {
+        let ItemEnum::Function(f) = &map.get_item(id)?.inner else {
+            return None;
+        };
+        let mut matcher = Matcher {
+            generics: Vec::with_capacity(2),
+            self_ty: None,
+            self_bound: None,
+            bindings: hashmap(4),
+        };
+        if let Some(owner) = index.owners.get(id).and_then(|id| map.get_item(id)) {
+            match &owner.inner {
+                ItemEnum::Impl(imp) => {
+                    matcher.generics.push(&imp.generics);
+                    matcher.self_ty = Some(&imp.for_);
+                    matcher.self_bound = imp.trait_.as_ref().map(|p| last_name(&p.path));
+                }
+                ItemEnum::Trait(t) => {
+                    matcher.generics.push(&t.generics);
+                    matcher.self_bound = owner.name.as_deref();
+                }
+                _ => (),
+            }
+        }
+        matcher.generics.push(&f.generics);
+
+        let inputs = &f.sig.inputs;
+        if self.inputs.len() > inputs.len() {
+            return None;
+        }
+        let mut used = vec![false; inputs.len()];
+        if !matcher.assign_inputs(&self.inputs, inputs, &mut used) {
+            return None;
+        }
+        if let Some(pat) = &self.output {
+            let unit = Type::Tuple(Vec::new());
+            let ty = f.sig.output.as_ref().unwrap_or(&unit);
+            if !matcher.matches(pat, ty) {
+                return None;
+            }
+        }
+        Some(inputs.len() - self.inputs.len())
+    }
+}
+
+/// Record the impl block or trait that a function is defined in, so `Self` type
+/// and generics from the outer item can be resolved.
+#[derive(Default)]
+pub struct SignatureIndex {
+    owners: HashMap<Id, Id>,
+}
+
+impl SignatureIndex {
+    pub fn new(map: &IDMap) -> Self {
+        let mut owners = hashmap(map.indexmap().len() / 4);
+        for (id, item) in map.indexmap() {
+            let items = match &item.inner {
+                ItemEnum::Impl(imp) => &imp.items,
+                ItemEnum::Trait(t) => &t.items,
+                _ => continue,
+            };
+            owners.extend(items.iter().map(|&assoc| (assoc, *id)));
+        }
+        SignatureIndex { owners }
+    }
+}
+
+/// A one-line signature of a function like `fn(&str, usize) -> bool`.
+pub fn fn_signature(id: &Id, map: &IDMap) -> Option<XString> {
+    let ItemEnum::Function(f) = &map.get_item(id)?.inner else {
+        return None;
+    };
+    let mut sig = XString::from("fn(");
+    for (idx, (_, ty)) in f.sig.inputs.iter().enumerate() {
+        if idx != 0 {
+            sig.push_str(", ");
+        }
+        sig.push_str(&long(ty));
+    }
+    sig.push(')');
+    if let Some(ty) = &f.sig.output {
+        sig.push_str(&xformat!(" -> {}", long(ty)));
+    }
+    Some(sig)
+}
+
+fn last_name(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+struct Matcher<'a> {
+    generics: Vec<&'a Generics>,
+    /// The implementor type in an impl block.
+    self_ty: Option<&'a Type>,
+    /// The trait name of a trait or trait impl block, which `Self` implements.
+    self_bound: Option<&'a str>,
+    /// Placeholder to the type it refers to.
+    bindings: HashMap<XString, String>,
+}
+
+impl Matcher<'_> {
+    /// Find a distinct argument for each input pattern with backtracking.
+    fn assign_inputs(
+        &mut self,
+        pats: &[Pattern],
+        inputs: &[(String, Type)],
+        used: &mut [bool],
+    ) -> bool {
+        let Some((pat, rest)) = pats.split_first() else {
+            return true;
+        };
+        for (idx, (_, ty)) in inputs.iter().enumerate() {
+            if used[idx] {
+                continue;
+            }
+            let bindings = self.bindings.clone();
+            if self.matches_autoref(pat, ty) {
+                used[idx] = true;
+                if self.assign_inputs(rest, inputs, used) {
+                    return true;
+                }
+                used[idx] = false;
+            }
+            self.bindings = bindings;
+        }
+        false
+    }
+
+    /// An argument like `&T` or `&mut T` is also accepted by the pattern for `T`,
+    /// which is handy for methods with `&self` receivers.
+    fn matches_autoref(&mut self, pat: &Pattern, ty: &Type) -> bool {
+        if self.matches(pat, ty) {
+            return true;
+        }
+        match (pat, ty) {
+            (Pattern::Ref { .. } | Pattern::Any, _) => false,
+            (_, Type::BorrowedRef { type_, .. }) => self.matches(pat, type_),
+            _ => false,
+        }
+    }
+
+    fn matches(&mut self, pat: &Pattern, ty: &Type) -> bool {
+        if let (Type::Generic(name), Some(self_ty)) = (ty, self.self_ty) {
+            if name == "Self" && !matches!(self_ty, Type::Generic(_)) {
+                return self.matches(pat, self_ty);
+            }
+        }
+        match pat {
+            Pattern::Any => true,
+            Pattern::Placeholder(name) => {
+                let ty = long(ty);
+                match self.bindings.get(name) {
+                    Some(bound) => *bound == ty,
+                    None => {
+                        self.bindings.insert(name.clone(), ty);
+                        true
+                    }
+                }
+            }
+            Pattern::Path { name, args } => match ty {
+                Type::ResolvedPath(p) => last_name(&p.path) == name && self.matches_args(args, p),
+                Type::Primitive(p) => p == name && args.is_empty(),
+                Type::Generic(g) => g == name && args.is_empty(),
+                Type::QualifiedPath { name: n, .. } => n == name && args.is_empty(),
+                _ => false,
+            },
+            Pattern::Ref { mutable, inner } => match ty {
+                Type::BorrowedRef {
+                    is_mutable, type_, ..
+                } => mutable == is_mutable && self.matches(inner, type_),
+                _ => false,
+            },
+            Pattern::Slice(inner) => match ty {
+                Type::Slice(t) | Type::Array { type_: t, .. } => self.matches(inner, t),
+                _ => false,
+            },
+            Pattern::Tuple(pats) => match ty {
+                Type::Tuple(types) => {
+                    pats.len() == types.len()
+                        && pats.iter().zip(types).all(|(p, t)| self.matches(p, t))
+                }
+                _ => false,
+            },
+            Pattern::Bounds(traits) => {
+                let bounds: Vec<&str> = match ty {
+                    Type::Generic(name) => self.bounds_of(name),
+                    Type::ImplTrait(bounds) => trait_names(bounds).collect(),
+                    Type::DynTrait(d) => {
+                        d.traits.iter().map(|t| last_name(&t.trait_.path)).collect()
+                    }
+                    _ => return false,
+                };
+                traits.iter().all(|t| bounds.contains(&t.as_str()))
+            }
+        }
+    }
+
+    fn matches_args(&mut self, pats: &[Pattern], path: &Path) -> bool {
+        if pats.is_empty() {
+            return true;
+        }
+        let Some(GenericArgs::AngleBracketed { args, .. }) = path.args.as_deref() else {
+            return false;
+        };
+        let types: Vec<_> = args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArg::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect();
+        pats.len() == types.len() && pats.iter().zip(types).all(|(p, t)| self.matches(p, t))
+    }
+
+    /// Trait names in the bounds on a generic type parameter from all the generics in scope.
+    fn bounds_of(&self, name: &str) -> Vec<&str> {
+        let mut bounds = Vec::new();
+        if name == "Self" {
+            bounds.extend(self.self_bound);
+        }
+        for generics in &self.generics {
+            for param in &generics.params {
+                if let GenericParamDefKind::Type { bounds: b, .. } = &param.kind {
+                    if param.name == name {
+                        bounds.extend(trait_names(b));
+                    }
+                }
+            }
+            for pred in &generics.where_predicates {
+                if let WherePredicate::BoundPredicate {
+                    type_: Type::Generic(ty),
+                    bounds: b,
+                    ..
+                } = pred
+                {
+                    if ty == name {
+                        bounds.extend(trait_names(b));
+                    }
+                }
+            }
+        }
+        bounds
+    }
+}
+
+fn trait_names(bounds: &[GenericBound]) -> impl Iterator<Item = &str> {
+    bounds.iter().filter_map(|b| match b {
+        GenericBound::TraitBound { trait_, .. } => Some(last_name(&trait_.path)),
+        _ => None,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'s> {
+    Ident(&'s str),
+    Punct(char),
+    Arrow,
+}
+
+fn tokenize(s: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = Vec::with_capacity(s.len() / 2);
+    let mut chars = s.char_indices().peekable();
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '-' => {
+                chars.next_if(|(_, c)| *c == '>')?;
+                tokens.push(Token::Arrow);
+            }
+            '&' | '<' | '>' | '(' | ')' | '[' | ']' | ',' | '+' | ':' | ';' | '\'' | '=' => {
+                tokens.push(Token::Punct(c))
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut end = pos + c.len_utf8();
+                while let Some((p, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                    end = p + c.len_utf8();
+                }
+                tokens.push(Token::Ident(&s[pos..end]));
+            }
+            _ => return None,
+        }
+    }
+    Some(tokens)
+}
+
+struct Parser<'s> {
+    tokens: Vec<Token<'s>>,
+    pos: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn peek(&self) -> Option<Token<'s>> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<Token<'s>> {
+        let token = self.peek();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: Token) -> bool {
+        let matched = self.peek() == Some(token);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn query(&mut self) -> Option<SignatureQuery> {
+        let mut inputs = Vec::new();
+        while !matches!(self.peek(), None | Some(Token::Arrow)) {
+            inputs.push(self.ty()?);
+            if !self.eat(Token::Punct(',')) {
+                break;
+            }
+        }
+        let output = if self.eat(Token::Arrow) {
+            Some(self.ty()?)
+        } else {
+            None
+        };
+        Some(SignatureQuery { inputs, output })
+    }
+
+    /// Comma separated types until the closing punctuation which is consumed.
+    fn list(&mut self, close: char) -> Option<Vec<Pattern>> {
+        let mut v = Vec::new();
+        while !self.eat(Token::Punct(close)) {
+            v.push(self.ty()?);
+            if !self.eat(Token::Punct(',')) {
+                self.eat(Token::Punct(close)).then_some(())?;
+                break;
+            }
+        }
+        Some(v)
+    }
+
+    fn lifetime(&mut self) -> Option<()> {
+        if self.eat(Token::Punct('\'')) {
+            matches!(self.bump(), Some(Token::Ident(_))).then_some(())?;
+        }
+        Some(())
+    }
+
+    fn ty(&mut self) -> Option<Pattern> {
+        Some(match self.bump()? {
+            Token::Punct('&') => {
+                self.lifetime()?;
+                let mutable = self.eat(Token::Ident("mut"));
+                Pattern::Ref {
+                    mutable,
+                    inner: Box::new(self.ty()?),
+                }
+            }
+            Token::Punct('[') => {
+                let inner = self.ty()?;
+                if self.eat(Token::Punct(';')) {
+                    matches!(self.bump(), Some(Token::Ident(_))).then_some(())?;
+                }
+                self.eat(Token::Punct(']')).then_some(())?;
+                Pattern::Slice(Box::new(inner))
+            }
+            Token::Punct('(') => {
+                let mut types = self.list(')')?;
+                if types.len() == 1 && self.tokens[self.pos - 2] != Token::Punct(',') {
+                    // `(T)` is `T`, but `(T,)` is a tuple
+                    types.pop()?
+                } else {
+                    Pattern::Tuple(types)
+                }
+            }
+            Token::Ident("impl" | "dyn") => {
+                let mut traits = Vec::new();
+                loop {
+                    if self.peek() == Some(Token::Punct('\'')) {
+                        // lifetime bounds
+                        self.lifetime()?;
+                    } else {
+                        let (name, _) = self.path()?;
+                        traits.push(name);
+                    }
+                    if !self.eat(Token::Punct('+')) {
+                        break;
+                    }
+                }
+                Pattern::Bounds(traits)
+            }
+            Token::Ident("_") => Pattern::Any,
+            Token::Ident(_) => {
+                self.pos -= 1;
+                let (name, args) = self.path()?;
+                if args.is_empty() && is_placeholder(&name) {
+                    Pattern::Placeholder(name)
+                } else {
+                    Pattern::Path { name, args }
+                }
+            }
+            _ => return None,
+        })
+    }
+
+    /// Returns the last name and generic arguments in a path.
+    /// Associated item constraints like `Item = T` are skipped.
+    fn path(&mut self) -> Option<(XString, Vec<Pattern>)> {
+        let Some(Token::Ident(mut name)) = self.bump() else {
+            return None;
+        };
+        while self.peek() == Some(Token::Punct(':')) {
+            self.pos += 1;
+            self.eat(Token::Punct(':')).then_some(())?;
+            let Some(Token::Ident(next)) = self.bump() else {
+                return None;
+            };
+            name = next;
+        }
+        let mut args = Vec::new();
+        if self.eat(Token::Punct('<')) {
+            while !self.eat(Token::Punct('>')) {
+                if let [Some(Token::Ident(_)), Some(Token::Punct('='))] =
+                    [self.peek(), self.tokens.get(self.pos + 1).copied()]
+                {
+                    self.pos += 2;
+                    self.ty()?;
+                } else if self.peek() == Some(Token::Punct('\'')) {
+                    self.lifetime()?;
+                } else {
+                    args.push(self.ty()?);
+                }
+                if !self.eat(Token::Punct(',')) {
+                    self.eat(Token::Punct('>')).then_some(())?;
+                    break;
+                }
+            }
+        }
+        Some((name.into(), args))
+    }
+}
+
+fn is_placeholder(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase()) && chars.all(|c| c.is_ascii_digit())
+}
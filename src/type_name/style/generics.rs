@@ -130,6 +130,14 @@ impl Format for TraitBoundModifier {
     }
 }
 
+/// Generic args like `<Item = u8>` or `(u8) -> bool` with full names in paths,
+/// used to compare bounds on different items.
+pub fn generic_args(args: &GenericArgs) -> String {
+    let mut buf = StyledType::with_capacity(8);
+    args.format::<Long>(&mut buf);
+    buf.to_non_wrapped_string()
+}
+
 impl Format for GenericArgs {
     /// `<...>` or `(...) -> ...`
     fn format<Kind: FindName>(&self, buf: &mut StyledType) {
@@ -10,6 +10,7 @@ use super::render::DeclarationLines;
 use crate::util::XString;
 use std::fmt;
 
+pub use generics::generic_args;
 pub use path::{long, long_path};
 use rustdoc_types::Id;
 
@@ -30,6 +30,7 @@ pub mod submod1 {
     }
 }
 
+/// Contains [`AUnitStruct`].
 pub struct FieldsNamedStruct {
     pub field1: AUnitStruct,
     pub field2: submod1::submod2::AStructAlias,
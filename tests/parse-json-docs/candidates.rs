@@ -0,0 +1,105 @@
+use crate::doc;
+use similar_asserts::assert_eq;
+use term_rustdoc::type_name::{generic_bounds, BoundedFn, BoundedFns, TraitImplementors};
+
+#[test]
+fn trait_bounds_on_generic_params() {
+    let map = &doc();
+    let bounds = |name: &str| {
+        let mut fns = map.dmodule().functions.iter();
+        let id = fns.find(|f| map.name(&f.id) == name).unwrap().id;
+        generic_bounds(&id, map)
+            .iter()
+            .map(|param| (param.to_string(), param.traits.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        bounds("func_trait_bounds"),
+        [(
+            "T: Clone + Copy".into(),
+            vec!["core::clone::Clone".into(), "core::marker::Copy".into()]
+        )]
+    );
+    assert_eq!(
+        bounds("func_with_const"),
+        [("T: Copy".into(), vec!["core::marker::Copy".into()])]
+    );
+    // `Sized` is implicit for all candidates
+    assert_eq!(bounds("no_synthetic"), [("impl Sized".into(), vec![])]);
+    assert_eq!(bounds("func_with_no_args"), []);
+}
+
+#[test]
+fn candidate_types() {
+    let map = &doc();
+    let implementors = TraitImplementors::new(map);
+    let candidates = |traits: &[&str]| {
+        let traits: Vec<_> = traits.iter().map(|&t| t.into()).collect();
+        implementors
+            .candidates(&traits)
+            .into_iter()
+            .map(|c| (c.ty, c.id.is_some()))
+            .collect::<Vec<_>>()
+    };
+
+    let a_unit_struct = [("integration::AUnitStruct".into(), true)];
+    assert_eq!(candidates(&["integration::ATrait"]), a_unit_struct);
+    assert_eq!(
+        candidates(&["integration::ATrait", "core::marker::Send"]),
+        a_unit_struct
+    );
+    assert_eq!(
+        candidates(&["core::default::Default", "core::marker::Sync"]),
+        [("integration::FieldsNamedStruct".into(), true)]
+    );
+    assert_eq!(
+        candidates(&["integration::submod1::submod2::ATraitNeverImplementedForTypes"]),
+        []
+    );
+    assert_eq!(candidates(&[]), []);
+}
+
+#[test]
+fn functions_with_same_bounds() {
+    let map = &doc();
+    let fns = BoundedFns::new(map);
+    let param = |name: &str| {
+        let mut fns = map.dmodule().functions.iter();
+        let id = fns.find(|f| map.name(&f.id) == name).unwrap().id;
+        generic_bounds(&id, map).remove(0)
+    };
+    let paths = |fns: Vec<BoundedFn>| {
+        fns.into_iter()
+            .map(|f| (f.item.path, f.param.to_string()))
+            .collect::<Vec<_>>()
+    };
+
+    let copy = param("func_with_const");
+    assert_eq!(
+        paths(fns.accepting(&copy)),
+        [("integration::func_with_const".into(), "T: Copy".into())]
+    );
+    // stronger bounds in `impl Trait`
+    assert_eq!(
+        paths(fns.returning(&copy)),
+        [(
+            "integration::func_fn_pointer_impl_trait".into(),
+            "impl Copy + Fn(*mut u8) -> *const u8".into()
+        )]
+    );
+
+    // `T: Copy` in parameter list and `T: Clone` in where clause
+    let copy_clone = param("func_trait_bounds");
+    assert_eq!(paths(fns.accepting(&copy_clone)), []);
+    assert_eq!(paths(fns.returning(&copy_clone)), []);
+
+    let iterator = param("func_qualified_path");
+    assert_eq!(
+        paths(fns.accepting(&iterator)),
+        [(
+            "integration::func_qualified_path".into(),
+            "I: Iterator".into()
+        )]
+    );
+}
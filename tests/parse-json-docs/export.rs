@@ -0,0 +1,21 @@
+use super::{doc, shot};
+use term_rustdoc::tree::ExportFormat;
+
+#[test]
+fn export_markdown() {
+    shot!("export-markdown", doc().export(ExportFormat::Markdown));
+}
+
+#[test]
+fn export_html() {
+    let html = doc().export(ExportFormat::Html);
+    // declarations link to items exported in the same file
+    let decl = html.lines().find(|line| line.contains("field1: ")).unwrap();
+    shot!(decl, @r##"    field1: <a href="#item-1">AUnitStruct</a>,"##);
+
+    // intra-doc links point to anchors too, and doc headings are below item headings
+    let docs = html.lines().find(|line| line.contains("Contains")).unwrap();
+    shot!(docs, @r##"<p>Contains <a href="#item-1"><code>AUnitStruct</code></a>.</p>"##);
+    let heading = html.lines().find(|line| line.contains("Safety")).unwrap();
+    shot!(heading, @"<h3>Safety</h3>");
+}
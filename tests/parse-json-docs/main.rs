@@ -13,15 +13,24 @@ use term_rustdoc::{
     util::{join_path, XString},
 };
 
+mod candidates;
+mod export;
 mod fn_item_decl;
 mod generate_doc_json;
 mod parse;
+mod reexport;
+mod signature;
 mod syntect_set;
+mod usage;
 
 static INTEGRATION: LazyLock<JsonDoc> = LazyLock::new(|| {
     tracing_subscriber::fmt::init();
     println!("start!");
-    get_integration_json_doc().expect("failed to get the json doc of tests/integration crate")
+    get_json_doc("integration").expect("failed to get the json doc of tests/integration crate")
+});
+
+static REEXPORT: LazyLock<JsonDoc> = LazyLock::new(|| {
+    get_json_doc("reexport").expect("failed to get the json doc of tests/reexport crate")
 });
 
 fn doc() -> CrateDoc {
@@ -49,15 +58,16 @@ impl JsonDoc {
     }
 }
 
-fn get_integration_json_doc() -> Result<JsonDoc> {
+/// Generate json doc for a crate under tests folder.
+fn get_json_doc(crate_dir: &str) -> Result<JsonDoc> {
     let json_path = rustdoc_json::Builder::default()
-        .toolchain("nightly")
+        .toolchain("nightly-2025-05-20")
         // .manifest_path(
         //     "/root/.cargo/registry/src/rsproxy.cn-0dccff568467c15b/syn-2.0.48/Cargo.toml",
         // )
         .all_features(true)
         .target_dir(PathBuf::from_iter(["target", "json-docs"]))
-        .manifest_path(PathBuf::from_iter(["tests", "integration", "Cargo.toml"]))
+        .manifest_path(PathBuf::from_iter(["tests", crate_dir, "Cargo.toml"]))
         // .manifest_path("./Cargo.toml")
         // .document_private_items(true)
         .build()?;
@@ -22,27 +22,27 @@ fn parse_module() {
     // item tree
     shot!("item-tree", dmod.item_tree(&doc));
 
-    snap!(dmod.current_items_counts(), @r###"
+    snap!(dmod.current_items_counts(), @r"
     ItemCount {
-        modules: 1,
+        modules: 2,
         structs: 2,
-        functions: 6,
-        traits: 1,
+        functions: 16,
+        traits: 2,
         constants: 2,
         macros_decl: 1,
     }
-    "###);
-    snap!(dmod.recursive_items_counts(), @r###"
+    ");
+    snap!(dmod.recursive_items_counts(), @r"
     ItemCount {
-        modules: 2,
-        structs: 2,
+        modules: 3,
+        structs: 18,
         enums: 1,
-        functions: 6,
-        traits: 1,
+        functions: 16,
+        traits: 2,
         constants: 2,
         macros_decl: 1,
     }
-    "###);
+    ");
 
     snap!(dmod.current_impls_counts(), @r###"
     ImplCounts {
@@ -90,13 +90,14 @@ fn parse_module() {
     let (struct_, _) = TreeLines::new_with(treelines.doc(), |doc| {
         doc.dmodule().structs[0].show_prettier(doc)
     });
-    shot!(struct_.display_as_plain_text(), @r###"
+    shot!(struct_.display_as_plain_text(), @r"
     integration::AUnitStruct
     ├── No Fields!
     └── Implementations
         ├── Trait Impls
         │   └── AUnitStruct: ATrait
         ├── Auto Impls
+        │   ├── AUnitStruct: Freeze
         │   ├── AUnitStruct: RefUnwindSafe
         │   ├── AUnitStruct: Send
         │   ├── AUnitStruct: Sync
@@ -110,5 +111,132 @@ fn parse_module() {
             ├── T: Into<U>
             ├── T: TryFrom<U>
             └── T: TryInto<U>
-    "###);
+    ");
+}
+
+#[test]
+fn impls() {
+    let doc = doc();
+    let dmod = doc.dmodule();
+    let fields_named = dmod
+        .structs
+        .iter()
+        .find(|s| doc.name(&s.id) == "FieldsNamedStruct")
+        .unwrap();
+    let impls = &fields_named.impls;
+    shot!(impls.inherent.len(), @"1");
+    shot!(impls.merged_inherent.functions.len(), @"5");
+    let (tree, _) = TreeLines::new_with(doc.clone(), |doc| fields_named.show_prettier(doc));
+    shot!(tree.display_as_plain_text(), @r"
+    integration::FieldsNamedStruct
+    ├── Fields
+    │   ├── [field] field1
+    │   ├── [field] field2
+    │   ├── [field] field3
+    │   └── /* private fields */
+    └── Implementations
+        ├── Inherent Impls
+        │   ├── [Fn] by_rc
+        │   ├── [Fn] by_ref
+        │   ├── [Fn] by_ref_mut
+        │   ├── [Fn] consume
+        │   └── [Fn] new
+        ├── Trait Impls
+        │   └── FieldsNamedStruct: Default
+        │       └── [Fn] default
+        ├── Auto Impls
+        │   ├── FieldsNamedStruct: Freeze
+        │   ├── FieldsNamedStruct: RefUnwindSafe
+        │   ├── FieldsNamedStruct: Send
+        │   ├── FieldsNamedStruct: Sync
+        │   ├── FieldsNamedStruct: Unpin
+        │   └── FieldsNamedStruct: UnwindSafe
+        └── Blanket Impls
+            ├── T: Any
+            ├── T: Borrow<T>
+            ├── T: BorrowMut<T>
+            ├── T: From<T>
+            ├── T: Into<U>
+            ├── T: TryFrom<U>
+            └── T: TryInto<U>
+    ");
+
+    let submod1 = dmod
+        .modules
+        .iter()
+        .find(|m| doc.name(&m.id) == "submod1")
+        .unwrap();
+    let enum_ = &submod1.enums[0];
+    let (tree, _) = TreeLines::new_with(doc.clone(), |doc| enum_.show_prettier(doc));
+    shot!(tree.display_as_plain_text(), @r"
+    integration::submod1::AUnitEnum
+    ├── Variants
+    │   ├── [variant] A
+    │   ├── [variant] B
+    │   └── [variant] C
+    └── Implementations
+        ├── Inherent Impls
+        │   └── [Fn] print
+        ├── Trait Impls
+        │   └── AUnitEnum: Debug
+        │       └── [Fn] fmt
+        ├── Auto Impls
+        │   ├── AUnitEnum: Freeze
+        │   ├── AUnitEnum: RefUnwindSafe
+        │   ├── AUnitEnum: Send
+        │   ├── AUnitEnum: Sync
+        │   ├── AUnitEnum: Unpin
+        │   └── AUnitEnum: UnwindSafe
+        └── Blanket Impls
+            ├── T: Any
+            ├── T: Borrow<T>
+            ├── T: BorrowMut<T>
+            ├── T: From<T>
+            ├── T: Into<U>
+            ├── T: TryFrom<U>
+            └── T: TryInto<U>
+    ");
+}
+
+#[test]
+fn implementors() {
+    let doc = doc();
+    let dmod = doc.dmodule();
+    let trait_ = dmod
+        .traits
+        .iter()
+        .find(|t| doc.name(&t.id) == "ATrait")
+        .unwrap();
+    let (tree, _) = TreeLines::new_with(doc.clone(), |doc| trait_.implementors(doc));
+    shot!(tree.display_as_plain_text(), @r"
+    integration::ATrait
+    ├── By Ownership
+    │   └── T (1)
+    │       └── AUnitStruct: ATrait
+    ├── By Concrete Or Generic
+    │   └── Concrete (1)
+    │       └── AUnitStruct: ATrait
+    └── By Local Or Foreign
+        └── Local Types (1)
+            └── AUnitStruct: ATrait
+    ");
+}
+
+#[test]
+fn trait_associated_items() {
+    let doc = doc();
+    let dmod = doc.dmodule();
+    let trait_ = dmod
+        .traits
+        .iter()
+        .find(|t| doc.name(&t.id) == "ATraitWithGAT")
+        .unwrap();
+    let (tree, _) = TreeLines::new_with(doc.clone(), |doc| trait_.associated_items(doc));
+    shot!(tree.display_as_plain_text(), @r"
+    integration::ATraitWithGAT
+    ├── Not Dyn Compatible
+    └── Required Items
+        ├── [type] Assoc
+        └── [fn] return_assoc
+    ");
 }
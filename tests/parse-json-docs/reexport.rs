@@ -0,0 +1,188 @@
+use crate::{doc, shot, REEXPORT};
+use term_rustdoc::tree::{CrateDoc, Show};
+
+#[test]
+fn inline_external_reexports() {
+    let facade = CrateDoc::new(REEXPORT.doc.clone());
+    shot!(facade.reexported_external_crates().join(", "), @"integration");
+    shot!(facade.dmodule_show_prettier(), @r"
+    reexport
+    ├── Traits
+    │   └── [Trait] integration::ATrait
+    │       └── No Associated Items Or Implementors!
+    ├── Structs
+    │   └── [Struct] integration::FieldsNamedStruct
+    │       ├── /* private fields */
+    │       └── No Implementations!
+    └── [Mod] integration::submod1
+    ");
+
+    // ATrait is inlined before submod1, which copies the impl of ATrait for AUnitStruct
+    // through `submod2::AUnitStruct`, so the impl is visited after the trait.
+    let doc = facade.inline_external_reexports(&[doc()]).unwrap();
+    assert!(doc.reexported_external_crates().is_empty());
+    shot!(doc.dmodule_show_prettier(), @r"
+    reexport
+    ├── Traits
+    │   └── [Trait] ATrait
+    │       └── Implementors
+    │           └── AUnitStruct: ATrait
+    ├── Structs
+    │   └── [Struct] FieldsNamedStruct
+    │       ├── Fields
+    │       │   ├── [field] field1
+    │       │   ├── [field] field2
+    │       │   ├── [field] field3
+    │       │   └── /* private fields */
+    │       └── Implementations
+    │           ├── Inherent Impls
+    │           │   ├── [Fn] by_rc
+    │           │   ├── [Fn] by_ref
+    │           │   ├── [Fn] by_ref_mut
+    │           │   ├── [Fn] consume
+    │           │   └── [Fn] new
+    │           ├── Trait Impls
+    │           │   └── FieldsNamedStruct: Default
+    │           │       └── [Fn] default
+    │           ├── Auto Impls
+    │           │   ├── FieldsNamedStruct: Freeze
+    │           │   ├── FieldsNamedStruct: RefUnwindSafe
+    │           │   ├── FieldsNamedStruct: Send
+    │           │   ├── FieldsNamedStruct: Sync
+    │           │   ├── FieldsNamedStruct: Unpin
+    │           │   └── FieldsNamedStruct: UnwindSafe
+    │           └── Blanket Impls
+    │               ├── T: Any
+    │               ├── T: Borrow<T>
+    │               ├── T: BorrowMut<T>
+    │               ├── T: From<T>
+    │               ├── T: Into<U>
+    │               ├── T: TryFrom<U>
+    │               └── T: TryInto<U>
+    └── [Mod] submod1
+        ├── Enums
+        │   └── [Enum] integration::submod1::AUnitEnum
+        │       ├── Variants
+        │       │   ├── [variant] A
+        │       │   ├── [variant] B
+        │       │   └── [variant] C
+        │       └── Implementations
+        │           ├── Inherent Impls
+        │           │   └── [Fn] print
+        │           ├── Trait Impls
+        │           │   └── AUnitEnum: Debug
+        │           │       └── [Fn] fmt
+        │           ├── Auto Impls
+        │           │   ├── AUnitEnum: Freeze
+        │           │   ├── AUnitEnum: RefUnwindSafe
+        │           │   ├── AUnitEnum: Send
+        │           │   ├── AUnitEnum: Sync
+        │           │   ├── AUnitEnum: Unpin
+        │           │   └── AUnitEnum: UnwindSafe
+        │           └── Blanket Impls
+        │               ├── T: Any
+        │               ├── T: Borrow<T>
+        │               ├── T: BorrowMut<T>
+        │               ├── T: From<T>
+        │               ├── T: Into<U>
+        │               ├── T: TryFrom<U>
+        │               └── T: TryInto<U>
+        └── [Mod] integration::submod1::submod2
+            ├── Traits
+            │   └── [Trait] integration::submod1::submod2::ATraitNeverImplementedForTypes
+            │       └── No Associated Items Or Implementors!
+            └── Structs
+                ├── [Struct] AStructAlias
+                │   ├── No Fields!
+                │   └── Implementations
+                │       ├── Trait Impls
+                │       │   └── AUnitStruct: ATrait
+                │       ├── Auto Impls
+                │       │   ├── AUnitStruct: Freeze
+                │       │   ├── AUnitStruct: RefUnwindSafe
+                │       │   ├── AUnitStruct: Send
+                │       │   ├── AUnitStruct: Sync
+                │       │   ├── AUnitStruct: Unpin
+                │       │   └── AUnitStruct: UnwindSafe
+                │       └── Blanket Impls
+                │           ├── T: Any
+                │           ├── T: Borrow<T>
+                │           ├── T: BorrowMut<T>
+                │           ├── T: From<T>
+                │           ├── T: Into<U>
+                │           ├── T: TryFrom<U>
+                │           └── T: TryInto<U>
+                └── [Struct] AUnitStruct
+                    ├── No Fields!
+                    └── Implementations
+                        ├── Trait Impls
+                        │   └── AUnitStruct: ATrait
+                        ├── Auto Impls
+                        │   ├── AUnitStruct: Freeze
+                        │   ├── AUnitStruct: RefUnwindSafe
+                        │   ├── AUnitStruct: Send
+                        │   ├── AUnitStruct: Sync
+                        │   ├── AUnitStruct: Unpin
+                        │   └── AUnitStruct: UnwindSafe
+                        └── Blanket Impls
+                            ├── T: Any
+                            ├── T: Borrow<T>
+                            ├── T: BorrowMut<T>
+                            ├── T: From<T>
+                            ├── T: Into<U>
+                            ├── T: TryFrom<U>
+                            └── T: TryInto<U>
+    ");
+
+    let dmod = doc.dmodule();
+    shot!(dmod.structs[0].show_prettier(&doc), @r"
+    FieldsNamedStruct
+    ├── Fields
+    │   ├── [field] field1
+    │   ├── [field] field2
+    │   ├── [field] field3
+    │   └── /* private fields */
+    └── Implementations
+        ├── Inherent Impls
+        │   ├── [Fn] by_rc
+        │   ├── [Fn] by_ref
+        │   ├── [Fn] by_ref_mut
+        │   ├── [Fn] consume
+        │   └── [Fn] new
+        ├── Trait Impls
+        │   └── FieldsNamedStruct: Default
+        │       └── [Fn] default
+        ├── Auto Impls
+        │   ├── FieldsNamedStruct: Freeze
+        │   ├── FieldsNamedStruct: RefUnwindSafe
+        │   ├── FieldsNamedStruct: Send
+        │   ├── FieldsNamedStruct: Sync
+        │   ├── FieldsNamedStruct: Unpin
+        │   └── FieldsNamedStruct: UnwindSafe
+        └── Blanket Impls
+            ├── T: Any
+            ├── T: Borrow<T>
+            ├── T: BorrowMut<T>
+            ├── T: From<T>
+            ├── T: Into<U>
+            ├── T: TryFrom<U>
+            └── T: TryInto<U>
+    ");
+}
+
+#[test]
+fn find_external_item() {
+    let facade = CrateDoc::new(REEXPORT.doc.clone());
+    let integration = doc();
+    shot!(facade.crate_name(), @"reexport");
+    assert!(facade.external_crate_names().contains(&"integration"));
+
+    let id = facade.dmodule().structs[0].id;
+    let (krate, summary) = facade.external_item(&id).unwrap();
+    assert_eq!(krate, integration.crate_name());
+    let target = integration
+        .find_local_item(&summary.path, summary.kind)
+        .unwrap();
+    shot!(integration.path(&target), @"integration::FieldsNamedStruct");
+    assert!(integration.external_item(&target).is_none());
+}
@@ -0,0 +1,67 @@
+use crate::doc;
+use similar_asserts::assert_eq;
+use term_rustdoc::type_name::{SignatureIndex, SignatureQuery};
+
+#[test]
+fn parse_signature_query() {
+    let valid = [
+        "",
+        "&str",
+        "&str -> usize",
+        "-> Self",
+        "Vec<T> -> usize",
+        "&str, usize -> Result<_, Error>",
+        "&'a mut [u8; 8], (A, B,) -> (A,)",
+        "impl Read -> String",
+        "&mut (dyn 'a + ATrait) -> impl Iterator<Item = u8> + Send",
+        "std::io::Result<()>",
+    ];
+    for query in valid {
+        assert!(
+            SignatureQuery::parse(query).is_some(),
+            "{query:?} should be valid"
+        );
+    }
+    let invalid = ["->", "&str ->", "Vec<T", "[u8", "a b", "fn(u8)", "impl"];
+    for query in invalid {
+        assert!(
+            SignatureQuery::parse(query).is_none(),
+            "{query:?} should be invalid"
+        );
+    }
+}
+
+#[test]
+fn search_by_signature() {
+    let map = &doc();
+    let index = SignatureIndex::new(map);
+    let search = |query: &str| {
+        let query = SignatureQuery::parse(query).unwrap();
+        let mut names: Vec<_> = map
+            .dmodule()
+            .functions
+            .iter()
+            .filter(|f| query.matches(&f.id, map, &index).is_some())
+            .map(|f| map.name(&f.id))
+            .collect();
+        names.sort_unstable();
+        names
+    };
+
+    assert_eq!(search("&str -> usize"), ["func_primitive"]);
+    assert_eq!(search("str"), ["func_primitive"]);
+    assert_eq!(
+        search("FieldsNamedStruct"),
+        ["func_with_1arg", "func_with_1arg_and_ret"]
+    );
+    assert_eq!(search("_ -> AUnitEnum"), ["func_with_1arg_and_ret"]);
+    assert_eq!(search("T -> [T]"), ["func_with_const"]);
+    assert_eq!(search("impl Sized"), ["no_synthetic"]);
+    assert_eq!(
+        search("impl Iterator -> Option<_>"),
+        ["func_qualified_path"]
+    );
+    assert_eq!(search("&dyn ATrait -> &dyn ATrait"), ["func_dyn_trait"]);
+    assert_eq!(search("impl ATrait + Send"), ["func_dyn_trait"]);
+    assert_eq!(search("Box<dyn ATrait>"), ["func_dyn_trait2"]);
+}
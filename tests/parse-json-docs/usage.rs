@@ -0,0 +1,64 @@
+use crate::doc;
+use similar_asserts::assert_eq;
+use term_rustdoc::tree::ItemPath;
+
+#[test]
+fn functions_using_types() {
+    let map = &doc();
+    let usages = |name: &str| {
+        let dmod = map.dmodule();
+        let id = dmod.structs.iter().map(|s| s.id);
+        let id = id
+            .chain(
+                dmod.modules
+                    .iter()
+                    .flat_map(|m| m.enums.iter().map(|e| e.id)),
+            )
+            .find(|id| map.name(id) == name)
+            .unwrap();
+        let fns = map.type_usages().get(&id).unwrap();
+        let paths = |fns: &[ItemPath]| fns.iter().map(|f| f.path.clone()).collect::<Vec<_>>();
+        [
+            paths(&fns.receivers),
+            paths(&fns.arguments),
+            paths(&fns.returns),
+        ]
+    };
+
+    let [receivers, arguments, returns] = usages("FieldsNamedStruct");
+    assert_eq!(
+        receivers,
+        [
+            "integration::FieldsNamedStruct::by_rc",
+            "integration::FieldsNamedStruct::by_ref",
+            "integration::FieldsNamedStruct::by_ref_mut",
+            "integration::FieldsNamedStruct::consume",
+        ]
+    );
+    assert_eq!(
+        arguments,
+        [
+            "integration::func_with_1arg",
+            "integration::func_with_1arg_and_ret"
+        ]
+    );
+    assert_eq!(
+        returns,
+        [
+            "integration::FieldsNamedStruct::default",
+            "integration::FieldsNamedStruct::new",
+        ]
+    );
+
+    let [receivers, arguments, returns] = usages("AUnitEnum");
+    // methods from derived impls count
+    assert_eq!(
+        receivers,
+        [
+            "integration::submod1::AUnitEnum::fmt",
+            "integration::submod1::AUnitEnum::print",
+        ]
+    );
+    assert_eq!(arguments, Vec::<&str>::new());
+    assert_eq!(returns, ["integration::func_with_1arg_and_ret"]);
+}
@@ -0,0 +1,3 @@
+//! A facade crate that re-exports items from the integration crate.
+
+pub use integration::{submod1, ATrait, FieldsNamedStruct};